@@ -0,0 +1,279 @@
+// src/cache/mod.rs
+// ═══════════════════════════════════════════════════════
+// Content-addressed cache
+//
+// Each stage's output is stored under a key hashed from everything it
+// depends on, so an unchanged pair is never rebuilt or re-explored:
+//   compile   preprocessed C, every file of the Rust crate, function,
+//             bounds and signature options,
+//             checker and tool versions      → bitcode and runner binaries
+//   symbolic  compile key, KLEE options      → PathSummary lists
+//   result    symbolic key, comparison and
+//             sampling options               → EquivalenceResult
+// Only settled outcomes are stored: summaries of an exploration KLEE cut
+// short, and results that are Unknown or rest on such an exploration, would
+// otherwise be replayed on every rerun instead of being tried again. A
+// result that depends on random inputs is stored only under an explicit
+// `--seed`; without one every run draws fresh inputs.
+// Entries live in $EQCHECK_CACHE, else ~/.cache/eqcheck; `--no-cache`
+// bypasses them. Artifacts are copied into the run's workspace, never
+// used in place.
+// ═══════════════════════════════════════════════════════
+
+use crate::compiler::IrFiles;
+use crate::symbolic::SymbolicSummaries;
+use crate::toolchain;
+use crate::types::{AnalysisConfig, EquivalenceResult, Evidence, Verdict};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+fn root() -> PathBuf {
+    if let Ok(dir) = std::env::var("EQCHECK_CACHE") {
+        return PathBuf::from(dir);
+    }
+    match std::env::var("HOME") {
+        Ok(home) => PathBuf::from(home).join(".cache/eqcheck"),
+        Err(_)   => std::env::temp_dir().join("equivalence_checker/cache"),
+    }
+}
+
+// ── Keys ──────────────────────────────────────────────
+
+/// SHA-256 hex digest of `parts`, tagged with the stage so keys never collide
+/// across stages. Harnesses change between releases, so the checker's
+/// version is part of every key. Each part is length-prefixed, so moving
+/// bytes from one part to the next changes the key.
+fn digest(stage: &str, parts: &[&[u8]]) -> String {
+    let mut h = Sha256::new();
+    let tags: [&[u8]; 2] = [stage.as_bytes(), env!("CARGO_PKG_VERSION").as_bytes()];
+    for p in tags.iter().chain(parts) {
+        h.update((p.len() as u64).to_le_bytes());
+        h.update(p);
+    }
+    h.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// `--version` line of every tool, computed once per process.
+fn tool_versions() -> &'static str {
+    static VERSIONS: OnceLock<String> = OnceLock::new();
+    VERSIONS.get_or_init(|| {
+        toolchain::get().tools().iter()
+            .map(|t| format!("{}={}", t.display(), toolchain::version_line(t).unwrap_or_default()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+/// The C source after preprocessing, so an edited header changes the key.
+/// Falls back to the raw file when clang cannot preprocess it.
+fn c_source(config: &AnalysisConfig) -> Vec<u8> {
+    let tc = toolchain::get();
+    let preprocessed = tc.clang.command()
+        .args(["-E", "-P"])
+        .args(tc.klee_include_flag())
+        .arg(&config.c_file)
+        .output();
+    match preprocessed {
+        Ok(o) if o.status.success() => o.stdout,
+        _ => fs::read(&config.c_file).unwrap_or_default(),
+    }
+}
+
+/// Path and contents of every file rustc reads for the Rust crate
+/// (`mod` files, `include!`d files), from its dep-info. Falls back to the
+/// raw file when rustc cannot produce one.
+fn rust_source(config: &AnalysisConfig) -> Vec<u8> {
+    let rustc    = &toolchain::get().rustc;
+    let dep_info = config.workspace.file("rust_deps.d");
+    let emitted = rustc.command()
+        .args(["--crate-type=lib", "--crate-name=deps", &format!("--emit=dep-info={}", dep_info), &config.rust_file])
+        .output()
+        .is_ok_and(|o| o.status.success());
+    let deps = if emitted { fs::read_to_string(&dep_info).unwrap_or_default() } else { String::new() };
+    let _ = fs::remove_file(&dep_info);
+    let files = dep_files(&deps);
+    if files.is_empty() {
+        return fs::read(&config.rust_file).unwrap_or_default();
+    }
+    let mut bytes = Vec::new();
+    for f in files {
+        bytes.extend(f.as_bytes());
+        bytes.push(0);
+        bytes.extend(fs::read(&f).unwrap_or_default());
+        bytes.push(0);
+    }
+    bytes
+}
+
+/// Input files of a dep-info file: each is listed on its own as `path:`
+/// after the `target: inputs` line, with spaces escaped.
+fn dep_files(deps: &str) -> Vec<String> {
+    deps.lines()
+        .skip(1)
+        .filter_map(|l| l.strip_suffix(':'))
+        .map(|p| p.replace("\\ ", " "))
+        .collect()
+}
+
+/// Key of the bitcode and runners: what the harnesses are generated from.
+pub fn compile_key(config: &AnalysisConfig) -> String {
+    let sources = config.workspace.sources.get_or_init(|| {
+        digest("sources", &[&c_source(config), &rust_source(config)])
+    });
+    let options = format!("{:?}", (
+        &config.bounds, &config.utf8, &config.return_struct, &config.outputs,
+        &config.error_map, &config.return_type, config.miter, config.has_float_results(),
+    ));
+    digest("compile", &[
+        sources.as_bytes(), config.function_name.as_bytes(), options.as_bytes(), tool_versions().as_bytes(),
+    ])
+}
+
+/// Key of the KLEE path summaries.
+pub fn symbolic_key(config: &AnalysisConfig) -> String {
+    let options = format!("{:?}", (config.max_paths, config.timeout, &config.klee));
+    digest("symbolic", &[compile_key(config).as_bytes(), options.as_bytes()])
+}
+
+/// Key of the whole check.
+pub fn result_key(config: &AnalysisConfig) -> String {
+    let options = format!("{:?}", (
+        &config.float_compare, config.exhaustive_limit, &config.sampling, config.collect_all,
+    ));
+    digest("result", &[symbolic_key(config).as_bytes(), options.as_bytes()])
+}
+
+// ── Entries ───────────────────────────────────────────
+
+fn entry(key: &str) -> PathBuf {
+    root().join(&key[..2]).join(key)
+}
+
+fn load_json<T: DeserializeOwned>(config: &AnalysisConfig, key: &str, name: &str) -> Option<T> {
+    if !config.cache {
+        return None;
+    }
+    let text = fs::read_to_string(entry(key).join(name)).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+/// Write `files` (name, contents) as the entry for `key`.
+/// The entry is assembled aside and renamed into place, so a reader never
+/// sees half of it and concurrent writers of the same key are harmless.
+fn store(key: &str, files: &[(&str, &[u8])]) {
+    let dest = entry(key);
+    if dest.exists() {
+        return;
+    }
+    let staging = root().join(format!("tmp-{}-{:04x}", key, rand::random::<u16>()));
+    let written = fs::create_dir_all(&staging).is_ok()
+        && files.iter().all(|(name, data)| fs::write(staging.join(name), data).is_ok())
+        && dest.parent().is_some_and(|p| fs::create_dir_all(p).is_ok())
+        && fs::rename(&staging, &dest).is_ok();
+    if !written {
+        let _ = fs::remove_dir_all(&staging);
+    }
+}
+
+fn store_json<T: Serialize>(config: &AnalysisConfig, key: &str, name: &str, value: &T) {
+    if !config.cache {
+        return;
+    }
+    if let Ok(json) = serde_json::to_vec(value) {
+        store(key, &[(name, &json)]);
+    }
+}
+
+// ── Stages ────────────────────────────────────────────
+
+/// Cached bitcode and runners, copied into the workspace.
+pub fn load_ir(config: &AnalysisConfig) -> Option<IrFiles> {
+    if !config.cache {
+        return None;
+    }
+    let dir = entry(&compile_key(config));
+    let copy = |name: &str| -> Option<String> {
+        let dst = config.workspace.file(name);
+        fs::copy(dir.join(name), &dst).ok()?;
+        Some(dst)
+    };
+    let miter_bc = if dir.join("miter.bc").exists() { Some(copy("miter.bc")?) } else { None };
+    Some(IrFiles {
+        c_ir_path:       copy("c.bc")?,
+        rust_ir_path:    copy("rust.bc")?,
+        c_runner_bin:    copy("c_runner")?,
+        rust_runner_bin: copy("rust_runner")?,
+        miter_bc,
+    })
+}
+
+pub fn store_ir(config: &AnalysisConfig, ir: &IrFiles) {
+    if !config.cache {
+        return;
+    }
+    let mut paths = vec![
+        ("c.bc", &ir.c_ir_path), ("rust.bc", &ir.rust_ir_path),
+        ("c_runner", &ir.c_runner_bin), ("rust_runner", &ir.rust_runner_bin),
+    ];
+    if let Some(bc) = &ir.miter_bc {
+        paths.push(("miter.bc", bc));
+    }
+    let Ok(data) = paths.iter().map(|(_, p)| fs::read(p)).collect::<Result<Vec<_>, _>>() else { return };
+    let files: Vec<(&str, &[u8])> = paths.iter().zip(&data).map(|((n, _), d)| (*n, d.as_slice())).collect();
+    store(&compile_key(config), &files);
+}
+
+pub fn load_summaries(config: &AnalysisConfig) -> Option<SymbolicSummaries> {
+    load_json(config, &symbolic_key(config), "summaries.json")
+}
+
+pub fn store_summaries(config: &AnalysisConfig, summaries: &SymbolicSummaries) {
+    if !summaries.coverage_complete() {
+        return;
+    }
+    store_json(config, &symbolic_key(config), "summaries.json", summaries);
+}
+
+pub fn load_result(config: &AnalysisConfig) -> Option<EquivalenceResult> {
+    load_json(config, &result_key(config), "result.json")
+}
+
+pub fn store_result(config: &AnalysisConfig, result: &EquivalenceResult) {
+    let settled = result.verdict != Verdict::Unknown && result.evidence != Some(Evidence::Incomplete);
+    let reproducible = result.verdict == Verdict::ProvedEquivalent
+        || matches!(result.evidence, Some(Evidence::SymbolicProof | Evidence::Exhaustive))
+        || config.sampling.seed.is_some();
+    if !settled || !reproducible {
+        return;
+    }
+    store_json(config, &result_key(config), "result.json", result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dep_info_lists_every_input() {
+        let deps = "/tmp/deps.d: my\\ lib.rs a.rs\n\nmy\\ lib.rs:\na.rs:\n";
+        assert_eq!(dep_files(deps), ["my lib.rs", "a.rs"]);
+    }
+
+    #[test]
+    fn keys_separate_stages_and_parts() {
+        let key = digest("compile", &[b"ab", b"c"]);
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, digest("compile", &[b"ab", b"c"]));
+        assert_ne!(key, digest("compile", &[b"a", b"bc"]));
+        assert_ne!(key, digest("symbolic", &[b"ab", b"c"]));
+    }
+
+    #[test]
+    fn empty_dep_info_has_no_inputs() {
+        assert!(dep_files("").is_empty());
+    }
+}
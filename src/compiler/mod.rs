@@ -7,11 +7,27 @@
 //   - InputBound extended with optional type tag
 //   - Harness correctly handles floats via klee_make_symbolic on float vars
 //   - Runner correctly parses float args from command line
+//   - Array parameters: C (pointer, length) ↔ Rust slice over the same
+//     symbolic buffer and symbolic length
+//   - String parameters: NUL-terminated C buffer ↔ Rust &[u8] / &str
+//   - #[repr(C)] struct parameters (one symbolic input per field) and
+//     struct returns printed field by field
+//   - Out-parameters: C passes the address of a local, Rust returns the
+//     value in a tuple/struct; runners print every output by name
+//   - Error maps: C sentinel/errno failures ↔ Rust None/Err; runners print
+//     "ok=value" or "err=detail"
+//   - Single-miter mode: C harness and Rust shim linked into one module
+//     whose entry point asserts both results are equal
 // ═══════════════════════════════════════════════════════
 
-use crate::types::{AnalysisConfig, CheckerError};
+use crate::cache;
+use crate::toolchain;
+use crate::types::{
+    AnalysisConfig, CheckerError, ErrorMap, ErrorSignal, InputBound, InputKind, OutputParam,
+    RustFallible, RustStr, StructLayout, VarType,
+};
+use crate::parallel::both_sides;
 use anyhow::Result;
-use std::process::Command;
 use std::path::Path;
 use std::fs;
 
@@ -21,183 +37,442 @@ pub struct IrFiles {
     pub rust_ir_path:    String,
     pub c_runner_bin:    String,
     pub rust_runner_bin: String,
+    /// C and Rust linked into one module for the single-miter run (`--miter`)
+    pub miter_bc:        Option<String>,
 }
 
-// ── Type system ────────────────────────────────────────
+// ── Typed literals ────────────────────────────────────
 
-/// The C/Rust type of one input variable.
-/// Parsed from the bound spec: "x:i32:-10:10" or just "x:-10:10" (defaults to i32)
-#[derive(Debug, Clone, PartialEq)]
-pub enum VarType {
-    I8, I16, I32, I64,
-    U8, U16, U32, U64,
-    F32, F64,
-    Bool,
-    Char,
+/// A C literal for an encoded bound value, spelled so the compiler neither
+/// truncates nor warns (e.g. INT64_MIN, 2^64-1).
+fn c_literal(ty: &VarType, v: i64) -> String {
+    if ty.is_float() {
+        return format!("{:?}", ty.decode_f64(v));
+    }
+    match ty {
+        VarType::I64 if v == i64::MIN => "(-9223372036854775807LL - 1)".into(),
+        VarType::I32 if v == i32::MIN as i64 => "(-2147483647 - 1)".into(),
+        VarType::I64 => format!("{}LL", v),
+        VarType::U32 => format!("{}U", ty.decode(v)),
+        VarType::U64 => format!("{}ULL", ty.decode(v)),
+        _            => ty.decode(v).to_string(),
+    }
 }
 
-impl VarType {
-    /// The C type string for declarations
-    fn c_type(&self) -> &'static str {
-        match self {
-            VarType::I8  => "int8_t",   VarType::I16 => "int16_t",
-            VarType::I32 => "int",       VarType::I64 => "long long",
-            VarType::U8  => "uint8_t",  VarType::U16 => "uint16_t",
-            VarType::U32 => "unsigned",  VarType::U64 => "unsigned long long",
-            VarType::F32 => "float",     VarType::F64 => "double",
-            VarType::Bool => "int",      VarType::Char => "char",
-        }
+/// A Rust literal for an encoded bound value, suffixed with its type.
+fn rust_literal(ty: &VarType, v: i64) -> String {
+    match ty {
+        VarType::F32 | VarType::F64 => format!("{:?}{}", ty.decode_f64(v), ty.rust_type()),
+        VarType::Bool               => (v != 0).to_string(),
+        // Compared against the u8 read back as i8 (see `rust_value`)
+        VarType::Char               => format!("{}i8", ty.decode(v)),
+        _                           => format!("{}{}", ty.decode(v), ty.rust_type()),
     }
+}
 
-    /// The Rust type string for declarations
-    fn rust_type(&self) -> &'static str {
-        match self {
-            VarType::I8  => "i8",  VarType::I16 => "i16",
-            VarType::I32 => "i32", VarType::I64 => "i64",
-            VarType::U8  => "u8",  VarType::U16 => "u16",
-            VarType::U32 => "u32", VarType::U64 => "u64",
-            VarType::F32 => "f32", VarType::F64 => "f64",
-            VarType::Bool => "bool", VarType::Char => "char",
-        }
+/// A Rust variable of type `ty` read as the value C sees: a `char` held in
+/// a `u8` is signed.
+fn rust_value(ty: &VarType, name: &str) -> String {
+    match ty {
+        VarType::Char => format!("({} as i8)", name),
+        _             => name.to_string(),
     }
+}
 
-    /// sizeof in C — used for klee_make_symbolic
-    fn c_sizeof(&self) -> &'static str {
-        match self {
-            VarType::I8  | VarType::U8  | VarType::Bool | VarType::Char => "1",
-            VarType::I16 | VarType::U16 => "2",
-            VarType::I32 | VarType::U32 | VarType::F32 => "4",
-            VarType::I64 | VarType::U64 | VarType::F64 => "8",
-        }
+/// Zero value of a Rust type, used to initialise symbolic storage.
+fn rust_zero(ty: &VarType) -> &'static str {
+    match ty {
+        VarType::F32  => "0.0f32",
+        VarType::F64  => "0.0f64",
+        VarType::Bool => "false",
+        _             => "0",
     }
+}
+
+// ── Strings ───────────────────────────────────────────
 
-    /// Is this a floating-point type?
-    fn is_float(&self) -> bool {
-        matches!(self, VarType::F32 | VarType::F64)
+/// Well-formed UTF-8 check over a NUL-terminated buffer, byte-for-byte the
+/// rules of `std::str::from_utf8`. The C and Rust harnesses carry the same
+/// check so both explore exactly the same string domain.
+const C_UTF8_VALID: &str = r#"static int __utf8_valid(const unsigned char *s) {
+    unsigned i = 0;
+    while (s[i]) {
+        unsigned char c = s[i++], lo = 0x80, hi = 0xBF;
+        unsigned n, k;
+        if (c < 0x80) continue;
+        else if (c >= 0xC2 && c <= 0xDF) n = 1;
+        else if (c >= 0xE0 && c <= 0xEF) { n = 2; if (c == 0xE0) lo = 0xA0; if (c == 0xED) hi = 0x9F; }
+        else if (c >= 0xF0 && c <= 0xF4) { n = 3; if (c == 0xF0) lo = 0x90; if (c == 0xF4) hi = 0x8F; }
+        else return 0;
+        if (s[i] < lo || s[i] > hi) return 0;
+        for (k = 1; k < n; k++) if ((s[i + k] & 0xC0) != 0x80) return 0;
+        i += n;
     }
+    return 1;
+}
+"#;
+
+const RUST_UTF8_VALID: &str = r#"fn __utf8_valid(s: &[u8]) -> bool {
+    let mut i = 0;
+    while s[i] != 0 {
+        let c = s[i];
+        i += 1;
+        let (mut lo, mut hi) = (0x80u8, 0xBFu8);
+        let n = if c < 0x80 { continue }
+            else if (0xC2..=0xDF).contains(&c) { 1 }
+            else if (0xE0..=0xEF).contains(&c) { if c == 0xE0 { lo = 0xA0 } if c == 0xED { hi = 0x9F } 2 }
+            else if (0xF0..=0xF4).contains(&c) { if c == 0xF0 { lo = 0x90 } if c == 0xF4 { hi = 0x8F } 3 }
+            else { return false };
+        if s[i] < lo || s[i] > hi { return false; }
+        for k in 1..n { if s[i + k] & 0xC0 != 0x80 { return false; } }
+        i += n;
+    }
+    true
+}
+"#;
 
-    /// Parse from a string like "i32", "f64", "int", "double", etc.
-    fn parse(s: &str) -> Option<VarType> {
-        match s.trim().to_lowercase().as_str() {
-            "i8"  | "int8_t"            => Some(VarType::I8),
-            "i16" | "int16_t"           => Some(VarType::I16),
-            "i32" | "int"               => Some(VarType::I32),
-            "i64" | "long" | "long long" | "int64_t" => Some(VarType::I64),
-            "u8"  | "uint8_t"           => Some(VarType::U8),
-            "u16" | "uint16_t"          => Some(VarType::U16),
-            "u32" | "unsigned" | "uint" | "uint32_t" => Some(VarType::U32),
-            "u64" | "uint64_t"          => Some(VarType::U64),
-            "f32" | "float"             => Some(VarType::F32),
-            "f64" | "double"            => Some(VarType::F64),
-            "bool"                      => Some(VarType::Bool),
-            "char"                      => Some(VarType::Char),
-            _                           => None,
+/// Does any string input need the UTF-8 check?
+fn needs_utf8_check(bounds: &[InputBound]) -> bool {
+    bounds.iter().any(|b| matches!(b.kind, InputKind::Str { rust: RustStr::Utf8, .. }))
+}
+
+// ── Call arguments ────────────────────────────────────
+
+/// C call arguments: an array is passed as (pointer, length), a string as its
+/// buffer, an out-parameter as the address of its local.
+fn c_call_args(bounds: &[InputBound], outputs: &[OutputParam]) -> String {
+    let out_at = |pos: usize| outputs.iter().find(|o| o.c_index == Some(pos));
+    let mut args: Vec<String> = Vec::new();
+    let mut pos = 0;
+    for b in bounds {
+        while let Some(o) = out_at(pos) {
+            args.push(format!("&{}", o.name));
+            pos += 1;
         }
+        match b.kind {
+            InputKind::Scalar | InputKind::Str { .. } | InputKind::Struct { .. } => {
+                args.push(b.name.clone());
+                pos += 1;
+            }
+            InputKind::Array { .. } => {
+                args.push(format!("{}, {}", b.name, b.len_name()));
+                pos += 2;
+            }
+        }
+    }
+    while let Some(o) = out_at(pos) {
+        args.push(format!("&{}", o.name));
+        pos += 1;
     }
+    args.join(", ")
 }
 
-/// Extended InputBound with type information
-#[derive(Debug, Clone)]
-struct TypedBound {
-    name:     String,
-    var_type: VarType,
-    min:      i64,
-    max:      i64,
-}
-
-/// Parse typed bounds from AnalysisConfig.
-/// Format: "x:0:100" (default i32) or "x:i32:0:100" (explicit type)
-fn parse_typed_bounds(config: &AnalysisConfig) -> Vec<TypedBound> {
-    config.bounds.iter().map(|b| {
-        // For now, default everything to i32 (can extend with type annotation in UI)
-        // The type could be passed as e.g. InputBound { name, type_hint, min, max }
-        TypedBound {
-            name:     b.name.clone(),
-            var_type: VarType::I32,  // TODO: extend InputBound with type_hint field
-            min:      b.min,
-            max:      b.max,
+/// C statements declaring a zeroed local for every out-parameter.
+fn c_output_decls(outputs: &[OutputParam]) -> String {
+    outputs.iter()
+        .filter(|o| !o.is_return())
+        .map(|o| format!("    {} {} = 0;\n", o.var_type.c_type(), o.name))
+        .collect()
+}
+
+/// Type of the symbolic `result` object holding a value of `ty`: booleans
+/// and chars are stored as one unsigned byte so both sides agree on layout.
+pub fn result_storage(ty: VarType) -> VarType {
+    match ty {
+        VarType::Bool | VarType::Char => VarType::U8,
+        other                         => other,
+    }
+}
+
+/// Match arms (success pattern, failure pattern) for the Rust fallible type.
+fn rust_fallible_arms(map: &ErrorMap) -> (&'static str, &'static str) {
+    match map.rust {
+        RustFallible::Option => ("Some(v)", "None"),
+        RustFallible::Result => ("Ok(v)", "Err(_)"),
+    }
+}
+
+/// Where output `o` lives on the Rust return value bound to `var`.
+fn rust_output_expr(var: &str, o: &OutputParam) -> String {
+    if o.rust.is_empty() { var.to_string() } else { format!("{}.{}", var, o.rust) }
+}
+
+/// Rust call arguments: an array is passed as a slice of its first `len`
+/// elements, a string as its bytes up to the terminator. The harness has
+/// already constrained `&str` bytes to valid UTF-8 and converts unchecked;
+/// the runner checks.
+fn rust_call_args(bounds: &[InputBound], checked_utf8: bool) -> String {
+    bounds.iter().map(|b| match b.kind {
+        InputKind::Scalar | InputKind::Struct { .. } => b.name.clone(),
+        InputKind::Array { .. } => format!("&{}[..{} as usize]", b.name, b.len_name()),
+        InputKind::Str { rust: RustStr::Bytes, .. } => format!("&{}[..{}]", b.name, b.len_name()),
+        InputKind::Str { .. } if checked_utf8 => {
+            format!("std::str::from_utf8(&{}[..{}]).unwrap()", b.name, b.len_name())
         }
-    }).collect()
+        InputKind::Str { .. } => {
+            format!("unsafe {{ std::str::from_utf8_unchecked(&{}[..{}]) }}", b.name, b.len_name())
+        }
+    }).collect::<Vec<_>>().join(", ")
 }
 
-// ── Harness generation ────────────────────────────────
+/// Scalar pieces of an input that carry a range: (C expression, Rust
+/// expression, bound). Strings are constrained separately.
+fn constrained_elems(b: &InputBound) -> Vec<(String, String, &InputBound)> {
+    match b.kind {
+        InputKind::Scalar => vec![(b.name.clone(), b.name.clone(), b)],
+        InputKind::Array { max_len } => (0..max_len)
+            .map(|i| (format!("{}[{}]", b.name, i), format!("{}[{}]", b.name, i), b))
+            .collect(),
+        InputKind::Str { .. } => Vec::new(),
+        InputKind::Struct { ref layout, ref fields } => layout.fields.iter().zip(fields)
+            .map(|(f, fb)| (fb.name.clone(), format!("{}.{}", b.name, f.rust_name), fb))
+            .collect(),
+    }
+}
 
-fn generate_c_harness(
-    c_file:        &str,
-    function_name: &str,
-    bounds:        &[TypedBound],
-) -> Result<String> {
-    println!("    Generating C harness with KLEE directives...");
-    let content = fs::read_to_string(c_file)?;
+/// Rust struct literal with every field zeroed.
+fn rust_zero_struct(layout: &StructLayout) -> String {
+    let fields: Vec<String> = layout.fields.iter()
+        .map(|f| format!("{}: {}", f.rust_name, rust_zero(&f.var_type)))
+        .collect();
+    format!("{} {{ {} }}", layout.rust_type, fields.join(", "))
+}
 
-    let mut h = String::new();
-    h.push_str("#include <klee/klee.h>\n");
-    h.push_str("#include <stdint.h>\n\n");
-    h.push_str("// Original function\n");
-    h.push_str(&content);
-    h.push_str("\n\n// Auto-generated KLEE harness\n");
-    h.push_str("int main() {\n");
+/// Rust statement binding `{name}_len` to the string's length.
+fn rust_str_len(b: &InputBound, max_len: usize) -> String {
+    format!("    let {}: usize = {}.iter().position(|&c| c == 0).unwrap_or({});\n",
+        b.len_name(), b.name, max_len)
+}
+
+// ── Harness generation ────────────────────────────────
 
-    // Declare variables
+/// C statements declaring every input, making it symbolic under its KLEE
+/// object name and constraining it to its bounds.
+fn c_symbolic_inputs(bounds: &[InputBound]) -> String {
+    let mut h = String::new();
+    // Declare variables — an array gets a fixed buffer plus a symbolic length
     for b in bounds {
-        h.push_str(&format!("    {} {};\n", b.var_type.c_type(), b.name));
+        match b.kind {
+            InputKind::Scalar => {
+                h.push_str(&format!("    {} {};\n", b.var_type.c_type(), b.name));
+            }
+            InputKind::Array { max_len } => {
+                h.push_str(&format!("    {} {}[{}];\n", b.var_type.c_type(), b.name, max_len));
+                h.push_str(&format!("    unsigned {};\n", b.len_name()));
+            }
+            // One extra byte so the terminator always fits
+            InputKind::Str { max_len, .. } => {
+                h.push_str(&format!("    char {}[{}];\n", b.name, max_len + 1));
+            }
+            InputKind::Struct { ref layout, .. } => {
+                h.push_str(&format!("    {} {};\n", layout.c_type, b.name));
+            }
+        }
     }
     h.push('\n');
 
-    // Make symbolic
+    // Make symbolic — struct fields are separate objects named "p.x"
     for b in bounds {
-        h.push_str(&format!(
-            "    klee_make_symbolic(&{name}, sizeof({name}), \"{name}\");\n",
-            name = b.name
-        ));
+        match b.kind {
+            InputKind::Struct { ref fields, .. } => {
+                for f in fields {
+                    h.push_str(&format!(
+                        "    klee_make_symbolic(&{name}, sizeof({name}), \"{name}\");\n",
+                        name = f.name
+                    ));
+                }
+            }
+            InputKind::Scalar => h.push_str(&format!(
+                "    klee_make_symbolic(&{name}, sizeof({name}), \"{name}\");\n",
+                name = b.name
+            )),
+            InputKind::Str { .. } => h.push_str(&format!(
+                "    klee_make_symbolic({name}, sizeof({name}), \"{name}\");\n",
+                name = b.name
+            )),
+            InputKind::Array { .. } => {
+                h.push_str(&format!(
+                    "    klee_make_symbolic({name}, sizeof({name}), \"{name}\");\n",
+                    name = b.name
+                ));
+                h.push_str(&format!(
+                    "    klee_make_symbolic(&{len}, sizeof({len}), \"{len}\");\n",
+                    len = b.len_name()
+                ));
+            }
+        }
     }
     h.push('\n');
 
     // Apply range constraints
     for b in bounds {
-        if b.var_type.is_float() {
-            h.push_str(&format!(
-                "    klee_assume({name} >= {min} && {name} <= {max});\n",
-                name = b.name,
-                min  = b.min,
-                max  = b.max
-            ));
-        } else {
+        match b.kind {
+            InputKind::Array { max_len } => {
+                h.push_str(&format!("    klee_assume({} <= {}U);\n", b.len_name(), max_len));
+            }
+            // Every byte is either the terminator or within range
+            InputKind::Str { max_len, rust } => {
+                h.push_str(&format!("    klee_assume({}[{}] == 0);\n", b.name, max_len));
+                for i in 0..max_len {
+                    h.push_str(&format!(
+                        "    klee_assume({name}[{i}] == 0 || ((unsigned char){name}[{i}] >= {min} && (unsigned char){name}[{i}] <= {max}));\n",
+                        name = b.name, i = i,
+                        min  = c_literal(&b.var_type, b.min),
+                        max  = c_literal(&b.var_type, b.max)
+                    ));
+                }
+                if rust == RustStr::Utf8 {
+                    h.push_str(&format!(
+                        "    klee_assume(__utf8_valid((const unsigned char *){}));\n", b.name
+                    ));
+                }
+            }
+            _ => {}
+        }
+        for (name, _, eb) in constrained_elems(b) {
             h.push_str(&format!(
                 "    klee_assume({name} >= {min} && {name} <= {max});\n",
-                name = b.name,
-                min  = b.min,
-                max  = b.max
+                name = name,
+                min  = c_literal(&eb.var_type, eb.min),
+                max  = c_literal(&eb.var_type, eb.max)
             ));
         }
     }
+    h
+}
+
+/// The C expression tracked as the harness result, emitting any statements
+/// it needs first.
+fn c_tracked_call(config: &AnalysisConfig, args: &str, h: &mut String) -> String {
+    let function_name = config.function_name.as_str();
+    let outputs       = config.outputs.as_slice();
+    // With out-parameters the result tracked is the return value, or the
+    // first output of a void function. Under an error map a failure becomes
+    // the sentinel the Rust harness uses for None/Err, so a success returning
+    // the sentinel looks the same and the symbolic stages cannot prove anything.
+    match (config.error_map.as_ref(), outputs.iter().find(|o| o.is_return()), outputs.first()) {
+        (Some(map), _, _) => {
+            if map.signal == ErrorSignal::Errno {
+                h.push_str("    errno = 0;\n");
+            }
+            let (failed, sentinel) = match map.signal {
+                ErrorSignal::Sentinel(v) => (format!("__ret == {}", v), v),
+                ErrorSignal::Negative    => ("__ret < 0".to_string(), -1),
+                ErrorSignal::Errno       => ("errno != 0".to_string(), -1),
+            };
+            match map.ok_type {
+                Some(ty) => {
+                    h.push_str(&format!("    {} __ret = {}({});\n", ty.c_type(), function_name, args));
+                    format!("({} ? {} : __ret)", failed, sentinel)
+                }
+                None => {
+                    h.push_str(&format!("    {}({});\n", function_name, args));
+                    format!("({} ? {} : 0)", failed, sentinel)
+                }
+            }
+        }
+        (None, _, None) => format!("{}({})", function_name, args),
+        (None, Some(ret), _) => {
+            h.push_str(&c_output_decls(outputs));
+            h.push_str(&format!("    {} __ret = {}({});\n", ret.var_type.c_type(), function_name, args));
+            "__ret".to_string()
+        }
+        (None, None, Some(first)) => {
+            h.push_str(&c_output_decls(outputs));
+            h.push_str(&format!("    {}({});\n", function_name, args));
+            first.name.clone()
+        }
+    }
+}
+
+/// Same result as the C harness: the return value or the first output.
+/// A failure becomes the C sentinel (-1 when C has no single one).
+fn rust_tracked_call(config: &AnalysisConfig, args: &str, h: &mut String) -> String {
+    let function_name = config.function_name.as_str();
+    let outputs       = config.outputs.as_slice();
+    let result_ty     = config.result_type().map(result_storage);
+    match (config.error_map.as_ref(), outputs.first()) {
+        (Some(map), _) => {
+            let (ok, err) = rust_fallible_arms(map);
+            let sentinel = match map.signal { ErrorSignal::Sentinel(v) => v, _ => -1 };
+            let storage = result_ty.unwrap_or(VarType::I32).rust_type();
+            let value = if map.ok_type.is_some() { format!("v as {}", storage) } else { "0".to_string() };
+            format!("(match {}({}) {{ {} => {}, {} => ({}i64) as {} }})",
+                function_name, args, ok, value, err, sentinel, storage)
+        }
+        (None, None) => format!("{}({})", function_name, args),
+        (None, Some(first)) => {
+            h.push_str(&format!("    let __r = {}({});\n", function_name, args));
+            rust_output_expr("__r", first)
+        }
+    }
+}
+
+fn generate_c_harness(config: &AnalysisConfig) -> Result<String> {
+    let c_file        = config.c_file.as_str();
+    let function_name = config.function_name.as_str();
+    let bounds        = config.bounds.as_slice();
+    let ret_struct    = config.return_struct.as_ref();
+    let outputs       = config.outputs.as_slice();
+    println!("    Generating C harness with KLEE directives...");
+    let content = fs::read_to_string(c_file)?;
+
+    let mut h = String::new();
+    h.push_str("#include <klee/klee.h>\n");
+    h.push_str("#include <stdint.h>\n");
+    if config.error_map.as_ref().is_some_and(|m| m.signal == ErrorSignal::Errno) {
+        h.push_str("#include <errno.h>\n");
+    }
     h.push('\n');
+    h.push_str("// Original function\n");
+    h.push_str(&content);
+    h.push_str("\n\n// Auto-generated KLEE harness\n");
+    if needs_utf8_check(bounds) {
+        h.push_str(C_UTF8_VALID);
+        h.push('\n');
+    }
+    h.push_str("int main() {\n");
 
-    // Call function — cast return to volatile int to prevent elimination
-    let args: Vec<String> = bounds.iter().map(|b| b.name.clone()).collect();
+    h.push_str(&c_symbolic_inputs(bounds));
+
+    // Call function — the result is bound to a symbolic object of its own type
+    let args = c_call_args(bounds, outputs);
+    if let Some(layout) = ret_struct {
+        // Struct results are compared field by field on the concrete runners
+        h.push_str(&format!("    {} __r = {}({});\n", layout.c_type, function_name, args));
+        h.push_str("    (void)__r;\n");
+        h.push_str("    return 0;\n");
+        h.push_str("}\n");
+        let path = config.workspace.file("c_harness.c");
+        fs::write(&path, h)?;
+        return Ok(path);
+    }
+    let call = c_tracked_call(config, &args, &mut h);
     // Make the return value symbolic so KLEE includes it in the result section
     // of the .kquery file. Without this, KLEE only writes (query [constraints] false)
     // with no result expression, making symbolic comparison impossible.
-    h.push_str("    int __result[1];\n");
-    h.push_str("    klee_make_symbolic(__result, sizeof(__result), \"result\");\n");
-    h.push_str(&format!(
-        "    klee_assume(__result[0] == (int){fn_name}({args}));\n",
-        fn_name = function_name,
-        args    = args.join(", ")
-    ));
-    h.push_str("    return __result[0];\n");
+    match config.result_type().map(result_storage) {
+        Some(ty) => {
+            h.push_str(&format!("    {} __result[1];\n", ty.c_type()));
+            h.push_str("    klee_make_symbolic(__result, sizeof(__result), \"result\");\n");
+            h.push_str(&format!("    klee_assume(__result[0] == ({}){});\n", ty.c_type(), call));
+        }
+        // Nothing to compare: just run the function for its paths
+        None => h.push_str(&format!("    {};\n", call)),
+    }
+    h.push_str("    return 0;\n");
     h.push_str("}\n");
 
-    let path = format!("/tmp/equivalence_checker/{}_c_harness.c", function_name);
+    let path = config.workspace.file("c_harness.c");
     fs::write(&path, h)?;
     Ok(path)
 }
 
-fn generate_rust_harness(
-    rust_file:     &str,
-    function_name: &str,
-    bounds:        &[TypedBound],
-) -> Result<String> {
+fn generate_rust_harness(config: &AnalysisConfig) -> Result<String> {
+    let rust_file     = config.rust_file.as_str();
+    let function_name = config.function_name.as_str();
+    let bounds        = config.bounds.as_slice();
+    let ret_struct    = config.return_struct.as_ref();
     println!("    Generating Rust harness with KLEE FFI...");
     let content = fs::read_to_string(rust_file)?;
 
@@ -210,157 +485,618 @@ fn generate_rust_harness(
     h.push_str("}\n\n");
     h.push_str(&content);
     h.push_str("\n\n");
+    if needs_utf8_check(bounds) {
+        h.push_str(RUST_UTF8_VALID);
+        h.push('\n');
+    }
     h.push_str("#[no_mangle]\n");
     h.push_str("pub extern \"C\" fn klee_harness() -> i32 {\n");
 
-    // Declare variables
+    // Declare variables — same objects, names and sizes as the C harness
     for b in bounds {
-        let default_val = match b.var_type {
-            VarType::F32 => "0.0f32".to_string(),
-            VarType::F64 => "0.0f64".to_string(),
-            VarType::Bool => "false".to_string(),
-            VarType::Char => "'\\0'".to_string(),
-            _              => "0".to_string(),
-        };
-        h.push_str(&format!("    let mut {}: {} = {};\n",
-            b.name, b.var_type.rust_type(), default_val));
+        let ty = b.var_type.rust_type();
+        match b.kind {
+            InputKind::Scalar => {
+                h.push_str(&format!("    let mut {}: {} = {};\n",
+                    b.name, ty, rust_zero(&b.var_type)));
+            }
+            InputKind::Array { max_len } => {
+                h.push_str(&format!("    let mut {}: [{}; {}] = [{}; {}];\n",
+                    b.name, ty, max_len, rust_zero(&b.var_type), max_len));
+                h.push_str(&format!("    let mut {}: u32 = 0;\n", b.len_name()));
+            }
+            InputKind::Str { max_len, .. } => {
+                h.push_str(&format!("    let mut {}: [u8; {}] = [0; {}];\n",
+                    b.name, max_len + 1, max_len + 1));
+            }
+            InputKind::Struct { ref layout, .. } => {
+                h.push_str(&format!("    let mut {}: {} = {};\n",
+                    b.name, layout.rust_type, rust_zero_struct(layout)));
+            }
+        }
     }
     h.push('\n');
 
-    // Make symbolic
+    // Make symbolic — (Rust place, Rust type, KLEE object name)
     h.push_str("    unsafe {\n");
     for b in bounds {
-        h.push_str(&format!(
-            "        klee_make_symbolic(\n            &mut {name} as *mut {ty} as *mut c_void,\n            std::mem::size_of::<{ty}>(),\n            b\"{name}\\0\".as_ptr()\n        );\n",
-            name = b.name,
-            ty   = b.var_type.rust_type(),
-        ));
+        let objects: Vec<(String, String, String)> = match b.kind {
+            InputKind::Scalar => vec![
+                (b.name.clone(), b.var_type.rust_type().to_string(), b.name.clone()),
+            ],
+            InputKind::Array { max_len } => vec![
+                (b.name.clone(), format!("[{}; {}]", b.var_type.rust_type(), max_len), b.name.clone()),
+                (b.len_name(), "u32".to_string(), b.len_name()),
+            ],
+            InputKind::Str { max_len, .. } => vec![
+                (b.name.clone(), format!("[u8; {}]", max_len + 1), b.name.clone()),
+            ],
+            InputKind::Struct { ref layout, ref fields } => layout.fields.iter().zip(fields)
+                .map(|(f, fb)| (
+                    format!("{}.{}", b.name, f.rust_name),
+                    f.var_type.rust_type().to_string(),
+                    fb.name.clone(),
+                ))
+                .collect(),
+        };
+        for (place, ty, name) in objects {
+            h.push_str(&format!(
+                "        klee_make_symbolic(\n            &mut {place} as *mut {ty} as *mut c_void,\n            std::mem::size_of::<{ty}>(),\n            b\"{name}\\0\".as_ptr()\n        );\n",
+                place = place,
+                ty    = ty,
+                name  = name,
+            ));
+        }
     }
 
     // Apply constraints
     for b in bounds {
-        match b.var_type {
-            VarType::F32 | VarType::F64 => {
+        match b.kind {
+            InputKind::Array { max_len } => {
                 h.push_str(&format!(
-                    "        klee_assume(({name} >= {min} as {ty} && {name} <= {max} as {ty}) as i32);\n",
-                    name = b.name, min = b.min, max = b.max, ty = b.var_type.rust_type()
+                    "        klee_assume(({} <= {}u32) as i32);\n", b.len_name(), max_len
                 ));
             }
-            VarType::Bool => {
-                // bool is just 0 or 1
-                h.push_str(&format!(
-                    "        klee_assume(({name} == false || {name} == true) as i32);\n",
-                    name = b.name
-                ));
+            InputKind::Str { max_len, rust } => {
+                h.push_str(&format!("        klee_assume(({}[{}] == 0) as i32);\n", b.name, max_len));
+                for i in 0..max_len {
+                    h.push_str(&format!(
+                        "        klee_assume(({name}[{i}] == 0 || ({name}[{i}] >= {min} && {name}[{i}] <= {max})) as i32);\n",
+                        name = b.name, i = i,
+                        min  = rust_literal(&b.var_type, b.min),
+                        max  = rust_literal(&b.var_type, b.max)
+                    ));
+                }
+                if rust == RustStr::Utf8 {
+                    h.push_str(&format!("        klee_assume(__utf8_valid(&{}) as i32);\n", b.name));
+                }
             }
-            _ => {
-                h.push_str(&format!(
-                    "        klee_assume(({name} >= {min} && {name} <= {max}) as i32);\n",
-                    name = b.name, min = b.min, max = b.max
-                ));
+            _ => {}
+        }
+        for (_, name, eb) in constrained_elems(b) {
+            match eb.var_type {
+                VarType::Bool => {
+                    // bool is just 0 or 1
+                    h.push_str(&format!(
+                        "        klee_assume(({name} == false || {name} == true) as i32);\n",
+                        name = name
+                    ));
+                }
+                _ => {
+                    h.push_str(&format!(
+                        "        klee_assume(({name} >= {min} && {name} <= {max}) as i32);\n",
+                        name = rust_value(&eb.var_type, &name),
+                        min  = rust_literal(&eb.var_type, eb.min),
+                        max  = rust_literal(&eb.var_type, eb.max)
+                    ));
+                }
             }
         }
     }
-    h.push_str("    }\n\n");
+    h.push_str("    }\n");
+    for b in bounds {
+        if let InputKind::Str { max_len, .. } = b.kind {
+            h.push_str(&rust_str_len(b, max_len));
+        }
+    }
+    h.push('\n');
 
-    let args: Vec<String> = bounds.iter().map(|b| b.name.clone()).collect();
+    let args = rust_call_args(bounds, false);
+    if ret_struct.is_some() {
+        h.push_str(&format!("    let __r = {}({});\n", function_name, args));
+        h.push_str("    0\n");
+        h.push_str("}\n");
+        let path = config.workspace.file("rust_harness.rs");
+        fs::write(&path, h)?;
+        return Ok(path);
+    }
+    let result_ty = config.result_type().map(result_storage);
+    let call = rust_tracked_call(config, &args, &mut h);
     // Make the return value symbolic so KLEE tracks it in the result section.
-    h.push_str("    let mut __result: i32 = 0;\n");
+    let Some(ty) = result_ty else {
+        h.push_str(&format!("    {};\n", call));
+        h.push_str("    0\n");
+        h.push_str("}\n");
+        let path = config.workspace.file("rust_harness.rs");
+        fs::write(&path, h)?;
+        return Ok(path);
+    };
+    let rty = ty.rust_type();
+    h.push_str(&format!("    let mut __result: {} = {};\n", rty, rust_zero(&ty)));
     h.push_str("    unsafe {\n");
     h.push_str("        klee_make_symbolic(\n");
-    h.push_str("            &mut __result as *mut i32 as *mut c_void,\n");
-    h.push_str("            std::mem::size_of::<i32>(),\n");
+    h.push_str(&format!("            &mut __result as *mut {} as *mut c_void,\n", rty));
+    h.push_str(&format!("            std::mem::size_of::<{}>(),\n", rty));
     h.push_str("            b\"result\\0\".as_ptr()\n");
     h.push_str("        );\n");
-    h.push_str(&format!(
-        "        klee_assume((__result == {fn_name}({args}) as i32) as i32);\n",
-        fn_name = function_name,
-        args    = args.join(", ")
-    ));
+    h.push_str(&format!("        klee_assume((__result == {} as {}) as i32);\n", call, rty));
     h.push_str("    }\n");
-    h.push_str("    __result\n");
+    h.push_str("    0\n");
+    h.push_str("}\n");
+    let path = config.workspace.file("rust_harness.rs");
+    fs::write(&path, h)?;
+    Ok(path)
+}
+
+// ── Miter generation ──────────────────────────────────
+//
+// One module, one KLEE run: the C entry point makes the inputs symbolic
+// once, calls the C function directly and the Rust function through an
+// `extern "C"` shim taking every input by address, then asserts the two
+// results are equal. A failing `klee_assert` is a divergence; no failure
+// over complete exploration is a proof.
+
+/// Type the miter compares: the tracked result's storage type, or the
+/// result struct.
+fn miter_result(config: &AnalysisConfig) -> Option<(String, String)> {
+    if let Some(layout) = &config.return_struct {
+        return Some((layout.c_type.clone(), layout.rust_type.clone()));
+    }
+    config.result_type().map(result_storage)
+        .map(|ty| (ty.c_type().to_string(), ty.rust_type().to_string()))
+}
+
+/// Addresses passed to the Rust shim: every input, then an array's length.
+fn miter_shim_args(bounds: &[InputBound]) -> Vec<String> {
+    bounds.iter().flat_map(|b| match b.kind {
+        InputKind::Array { .. } => vec![format!("&{}", b.name), format!("&{}", b.len_name())],
+        InputKind::Str { .. }   => vec![b.name.clone()],
+        _                       => vec![format!("&{}", b.name)],
+    }).collect()
+}
+
+fn generate_c_miter(config: &AnalysisConfig) -> Result<String> {
+    let function_name = config.function_name.as_str();
+    let bounds        = config.bounds.as_slice();
+    println!("    Generating C miter entry point...");
+    let content = fs::read_to_string(&config.c_file)?;
+
+    let mut h = String::new();
+    h.push_str("#include <klee/klee.h>\n");
+    h.push_str("#include <stdint.h>\n");
+    if config.error_map.as_ref().is_some_and(|m| m.signal == ErrorSignal::Errno) {
+        h.push_str("#include <errno.h>\n");
+    }
+    h.push('\n');
+    h.push_str("// Original function\n");
+    h.push_str(&content);
+    h.push_str("\n\n// Auto-generated miter: C and Rust on the same symbolic inputs\n");
+    if needs_utf8_check(bounds) {
+        h.push_str(C_UTF8_VALID);
+        h.push('\n');
+    }
+    let shim_args = miter_shim_args(bounds);
+    let params = if shim_args.is_empty() {
+        "void".to_string()
+    } else {
+        vec!["const void *"; shim_args.len()].join(", ")
+    };
+    let result = miter_result(config);
+    let ret = result.as_ref().map_or("void", |(c, _)| c.as_str());
+    h.push_str(&format!("extern {} __miter_rust({});\n\n", ret, params));
+    h.push_str("int main() {\n");
+    h.push_str(&c_symbolic_inputs(bounds));
+    h.push('\n');
+
+    let args      = c_call_args(bounds, &config.outputs);
+    let rust_call = format!("__miter_rust({})", shim_args.join(", "));
+    match (&config.return_struct, result) {
+        (Some(layout), _) => {
+            h.push_str(&format!("    {} __c = {}({});\n", layout.c_type, function_name, args));
+            h.push_str(&format!("    {} __rust = {};\n", layout.c_type, rust_call));
+            for f in &layout.fields {
+                h.push_str(&format!("    klee_assert(__c.{f} == __rust.{f});\n", f = f.c_name));
+            }
+        }
+        (None, Some((ty, _))) => {
+            let call = c_tracked_call(config, &args, &mut h);
+            h.push_str(&format!("    {ty} __c = ({ty}){call};\n", ty = ty, call = call));
+            h.push_str(&format!("    {} __rust = {};\n", ty, rust_call));
+            // The tracked result only: further outputs are left to the runners
+            h.push_str("    klee_assert(__c == __rust);\n");
+        }
+        // Nothing to compare: both sides still run, so a crash on either shows up
+        (None, None) => {
+            let call = c_tracked_call(config, &args, &mut h);
+            h.push_str(&format!("    {};\n", call));
+            h.push_str(&format!("    {};\n", rust_call));
+        }
+    }
+    h.push_str("    return 0;\n");
     h.push_str("}\n");
-    let path = format!("/tmp/equivalence_checker/{}_rust_harness.rs", function_name);
+
+    let path = config.workspace.file("c_miter.c");
+    fs::write(&path, h)?;
+    Ok(path)
+}
+
+fn generate_rust_miter(config: &AnalysisConfig) -> Result<String> {
+    let function_name = config.function_name.as_str();
+    let bounds        = config.bounds.as_slice();
+    println!("    Generating Rust miter shim...");
+    let content = fs::read_to_string(&config.rust_file)?;
+
+    let mut h = String::new();
+    h.push_str("#![allow(unused)]\n");
+    h.push_str("use std::os::raw::c_void;\n\n");
+    h.push_str(&content);
+    h.push_str("\n\n");
+    if needs_utf8_check(bounds) {
+        h.push_str(RUST_UTF8_VALID);
+        h.push('\n');
+    }
+
+    // Read every input back from the C objects: (binding, read type, conversion)
+    let mut params = Vec::new();
+    let mut reads  = String::new();
+    let mut read = |name: String, ty: String, read_ty: String, conv: &str| {
+        let p = format!("__p{}", params.len());
+        reads.push_str(&format!("    let {}: {} = unsafe {{ *({} as *const {}) }}{};\n",
+            name, ty, p, read_ty, conv));
+        params.push(format!("{}: *const c_void", p));
+    };
+    for b in bounds {
+        let ty = b.var_type.rust_type();
+        match b.kind {
+            // C stores a bool as int
+            InputKind::Scalar if b.var_type == VarType::Bool => {
+                read(b.name.clone(), "bool".into(), "i32".into(), " != 0");
+            }
+            InputKind::Scalar => read(b.name.clone(), ty.into(), ty.into(), ""),
+            InputKind::Array { max_len } if b.var_type == VarType::Bool => {
+                read(b.name.clone(), format!("[bool; {}]", max_len),
+                    format!("[i32; {}]", max_len), ".map(|v| v != 0)");
+                read(b.len_name(), "u32".into(), "u32".into(), "");
+            }
+            InputKind::Array { max_len } => {
+                read(b.name.clone(), format!("[{}; {}]", ty, max_len),
+                    format!("[{}; {}]", ty, max_len), "");
+                read(b.len_name(), "u32".into(), "u32".into(), "");
+            }
+            InputKind::Str { max_len, .. } => {
+                let buf = format!("[u8; {}]", max_len + 1);
+                read(b.name.clone(), buf.clone(), buf, "");
+            }
+            InputKind::Struct { ref layout, .. } => {
+                read(b.name.clone(), layout.rust_type.clone(), layout.rust_type.clone(), "");
+            }
+        }
+    }
+    for b in bounds {
+        if let InputKind::Str { max_len, .. } = b.kind {
+            reads.push_str(&rust_str_len(b, max_len));
+        }
+    }
+
+    let result = miter_result(config);
+    let ret = result.as_ref().map_or(String::new(), |(_, r)| format!(" -> {}", r));
+    h.push_str("#[no_mangle]\n");
+    h.push_str(&format!("pub extern \"C\" fn __miter_rust({}){} {{\n", params.join(", "), ret));
+    h.push_str(&reads);
+    let args = rust_call_args(bounds, false);
+    match (&config.return_struct, result) {
+        (Some(_), _)          => h.push_str(&format!("    {}({})\n", function_name, args)),
+        (None, Some((_, ty))) => {
+            let call = rust_tracked_call(config, &args, &mut h);
+            h.push_str(&format!("    {} as {}\n", call, ty));
+        }
+        (None, None) => {
+            let call = rust_tracked_call(config, &args, &mut h);
+            h.push_str(&format!("    {};\n", call));
+        }
+    }
+    h.push_str("}\n");
+
+    let path = config.workspace.file("rust_miter.rs");
     fs::write(&path, h)?;
     Ok(path)
 }
 
 // ── Runner generation ─────────────────────────────────
 
-fn generate_c_runner(
-    c_file:        &str,
-    function_name: &str,
-    bounds:        &[TypedBound],
-) -> Result<String> {
+/// C expression parsing `argv[i]` as `ty`.
+fn c_parse_arg(ty: &VarType, i: usize) -> String {
+    match ty {
+        VarType::F32 => format!("strtof(argv[{}], NULL)", i),
+        VarType::F64 => format!("strtod(argv[{}], NULL)", i),
+        VarType::I64 => format!("strtoll(argv[{}], NULL, 10)", i),
+        VarType::U64 => format!("strtoull(argv[{}], NULL, 10)", i),
+        VarType::U32 => format!("(unsigned)strtoul(argv[{}], NULL, 10)", i),
+        _            => format!("({})atoi(argv[{}])", ty.c_type(), i),
+    }
+}
+
+/// Rust expression parsing `args[i]` as `ty`.
+fn rust_parse_arg(ty: &VarType, i: usize) -> String {
+    match ty {
+        VarType::Bool => format!("args[{}].parse::<i32>().unwrap() != 0", i),
+        VarType::Char => format!("args[{}].parse::<i8>().unwrap() as u8", i),
+        _             => format!("args[{}].parse::<{}>().unwrap()", i, ty.rust_type()),
+    }
+}
+
+/// C statement printing `expr` of type `ty` at full width and signedness,
+/// after `prefix`. Booleans print as 0/1, floats as their hex bit pattern.
+fn c_print_line(prefix: &str, expr: &str, ty: &VarType) -> String {
+    // Floats print their IEEE bit pattern so no precision is lost in decimal
+    let (spec, arg) = match ty {
+        VarType::F64  => ("0x%016llx", format!("__f64_bits({})", expr)),
+        VarType::F32  => ("0x%08llx", format!("__f32_bits({})", expr)),
+        VarType::U64  => ("%llu", format!("(unsigned long long){}", expr)),
+        // Signed whatever the host's `char`, as the Rust runner prints it
+        VarType::Char => ("%lld", format!("(long long)(signed char){}", expr)),
+        _             => ("%lld", format!("(long long){}", expr)),
+    };
+    format!("    printf(\"{}{}\\n\", {});\n", prefix, spec, arg)
+}
+
+/// Bit-pattern accessors used by the runner to print float results exactly.
+const C_FLOAT_BITS: &str = "\
+static unsigned long long __f64_bits(double v) { uint64_t b; memcpy(&b, &v, sizeof b); return b; }
+static unsigned long long __f32_bits(float v) { uint32_t b; memcpy(&b, &v, sizeof b); return b; }
+
+";
+
+/// C statement printing `expr` of type `ty` as a "name=value" line.
+fn c_print_named(name: &str, expr: &str, ty: &VarType) -> String {
+    c_print_line(&format!("{}=", name), expr, ty)
+}
+
+/// Rust counterpart of `c_print_line`.
+fn rust_print_line(prefix: &str, expr: &str, ty: &VarType) -> String {
+    match ty {
+        VarType::F64  => format!("    println!(\"{}{{:#018x}}\", ({}).to_bits());\n", prefix, expr),
+        VarType::F32  => format!("    println!(\"{}{{:#010x}}\", ({}).to_bits());\n", prefix, expr),
+        VarType::Bool => format!("    println!(\"{}{{}}\", {} as i64);\n", prefix, expr),
+        VarType::Char => format!("    println!(\"{}{{}}\", ({}) as i8);\n", prefix, expr),
+        _             => format!("    println!(\"{}{{}}\", {});\n", prefix, expr),
+    }
+}
+
+/// Rust statement printing `expr` of type `ty` as a "name=value" line.
+fn rust_print_named(name: &str, expr: &str, ty: &VarType) -> String {
+    rust_print_line(&format!("{}=", name), expr, ty)
+}
+
+fn generate_c_runner(config: &AnalysisConfig) -> Result<String> {
+    let c_file        = config.c_file.as_str();
+    let function_name = config.function_name.as_str();
+    let bounds        = config.bounds.as_slice();
+    let ret_struct    = config.return_struct.as_ref();
+    let outputs       = config.outputs.as_slice();
+    let error_map     = config.error_map.as_ref();
     let content = fs::read_to_string(c_file)?;
     let mut s = String::new();
-    s.push_str("#include <stdio.h>\n#include <stdlib.h>\n#include <stdint.h>\n\n");
+    s.push_str("#include <stdio.h>\n#include <stdlib.h>\n#include <stdint.h>\n#include <string.h>\n");
+    if error_map.is_some_and(|m| m.signal == ErrorSignal::Errno) {
+        s.push_str("#include <errno.h>\n");
+    }
+    s.push('\n');
+    if config.has_float_results() {
+        s.push_str(C_FLOAT_BITS);
+    }
     s.push_str(&content);
     s.push_str("\n\nint main(int argc, char** argv) {\n");
-    s.push_str(&format!("    if (argc != {}) return 2;\n", bounds.len() + 1));
-    for (i, b) in bounds.iter().enumerate() {
-        let parse_fn = match b.var_type {
-            VarType::F32            => format!("(float)atof(argv[{}])", i + 1),
-            VarType::F64            => format!("atof(argv[{}])", i + 1),
-            VarType::I64 | VarType::U64 => format!("atoll(argv[{}])", i + 1),
-            VarType::Bool           => format!("(int)atoi(argv[{}])", i + 1),
-            _                       => format!("atoi(argv[{}])", i + 1),
+    let argc: usize = bounds.iter().map(|b| b.slots().len()).sum();
+    s.push_str(&format!("    if (argc != {}) return 2;\n", argc + 1));
+    // One argv slot per scalar; an array takes its length then every element
+    let mut i = 1;
+    for b in bounds {
+        match b.kind {
+            InputKind::Scalar => {
+                s.push_str(&format!("    {} {} = {};\n",
+                    b.var_type.c_type(), b.name, c_parse_arg(&b.var_type, i)));
+                i += 1;
+            }
+            InputKind::Array { max_len } => {
+                s.push_str(&format!("    unsigned {} = {};\n",
+                    b.len_name(), c_parse_arg(&VarType::U32, i)));
+                s.push_str(&format!("    {} {}[{}];\n", b.var_type.c_type(), b.name, max_len));
+                for e in 0..max_len {
+                    s.push_str(&format!("    {}[{}] = {};\n",
+                        b.name, e, c_parse_arg(&b.var_type, i + 1 + e)));
+                }
+                i += 1 + max_len;
+            }
+            // One argv slot per byte, terminator appended
+            InputKind::Str { max_len, .. } => {
+                s.push_str(&format!("    char {}[{}];\n", b.name, max_len + 1));
+                for e in 0..max_len {
+                    s.push_str(&format!("    {}[{}] = (char){};\n",
+                        b.name, e, c_parse_arg(&VarType::U8, i + e)));
+                }
+                s.push_str(&format!("    {}[{}] = 0;\n", b.name, max_len));
+                i += max_len;
+            }
+            InputKind::Struct { ref layout, .. } => {
+                s.push_str(&format!("    {} {};\n", layout.c_type, b.name));
+                for f in &layout.fields {
+                    s.push_str(&format!("    {}.{} = {};\n",
+                        b.name, f.c_name, c_parse_arg(&f.var_type, i)));
+                    i += 1;
+                }
+            }
+        }
+    }
+    // Struct results and outputs are printed one "name=value" line each
+    let args = c_call_args(bounds, outputs);
+    if let Some(map) = error_map {
+        if map.signal == ErrorSignal::Errno {
+            s.push_str("    errno = 0;\n");
+        }
+        match map.ok_type {
+            Some(ty) => s.push_str(&format!("    {} r = {}({});\n", ty.c_type(), function_name, args)),
+            None     => s.push_str(&format!("    {}({});\n", function_name, args)),
+        }
+        let (failed, err) = match map.signal {
+            ErrorSignal::Sentinel(v) => (format!("r == {}", v), c_print_named("err", "r", &VarType::I64)),
+            ErrorSignal::Negative    => ("r < 0".to_string(), c_print_named("err", "r", &VarType::I64)),
+            ErrorSignal::Errno       => (
+                "errno != 0".to_string(),
+                "    printf(\"err=errno %d\\n\", errno);\n".to_string(),
+            ),
         };
-        s.push_str(&format!("    {} {} = {};\n", b.var_type.c_type(), b.name, parse_fn));
+        let ok = match map.ok_type {
+            Some(ty) => c_print_named("ok", "r", &ty),
+            None     => "    printf(\"ok\\n\");\n".to_string(),
+        };
+        s.push_str(&format!("    if ({}) {{\n    {}    }} else {{\n    {}    }}\n", failed, err, ok));
+        s.push_str("    return 0;\n}\n");
+    } else if let Some(layout) = ret_struct {
+        s.push_str(&format!(
+            "    {} r = {}({});\n", layout.c_type, function_name, args
+        ));
+        for f in &layout.fields {
+            s.push_str(&c_print_named(&f.c_name, &format!("r.{}", f.c_name), &f.var_type));
+        }
+        s.push_str("    return 0;\n}\n");
+    } else if !outputs.is_empty() {
+        s.push_str(&c_output_decls(outputs));
+        match outputs.iter().find(|o| o.is_return()) {
+            Some(ret) => s.push_str(&format!(
+                "    {} r = {}({});\n", ret.var_type.c_type(), function_name, args
+            )),
+            None => s.push_str(&format!("    {}({});\n", function_name, args)),
+        }
+        for o in outputs {
+            let expr = if o.is_return() { "r" } else { o.name.as_str() };
+            s.push_str(&c_print_named(&o.name, expr, &o.var_type));
+        }
+        s.push_str("    return 0;\n}\n");
+    } else {
+        match config.return_type {
+            Some(ty) => {
+                s.push_str(&format!("    {} r = {}({});\n", ty.c_type(), function_name, args));
+                s.push_str(&c_print_line("", "r", &ty));
+            }
+            None => {
+                s.push_str(&format!("    {}({});\n", function_name, args));
+                s.push_str("    printf(\"void\\n\");\n");
+            }
+        }
+        s.push_str("    return 0;\n}\n");
     }
-    let args: Vec<String> = bounds.iter().map(|b| b.name.clone()).collect();
-    s.push_str(&format!(
-        "    int r = (int){}({});\n",
-        function_name,
-        args.join(", ")
-    ));
-    s.push_str("    printf(\"%d\\n\", r);\n    return 0;\n}\n");
-    let path = format!("/tmp/equivalence_checker/{}_c_runner.c", function_name);
+    let path = config.workspace.file("c_runner.c");
     fs::write(&path, s)?;
     Ok(path)
 }
 
-fn generate_rust_runner(
-    rust_file:     &str,
-    function_name: &str,
-    bounds:        &[TypedBound],
-) -> Result<String> {
+fn generate_rust_runner(config: &AnalysisConfig) -> Result<String> {
+    let rust_file     = config.rust_file.as_str();
+    let function_name = config.function_name.as_str();
+    let bounds        = config.bounds.as_slice();
+    let ret_struct    = config.return_struct.as_ref();
+    let outputs       = config.outputs.as_slice();
+    let error_map     = config.error_map.as_ref();
     let content = fs::read_to_string(rust_file)?;
     let mut s = String::new();
     s.push_str("#![allow(unused)]\nuse std::env;\n\n");
     s.push_str(&content);
     s.push_str("\n\nfn main() {\n");
     s.push_str("    let args: Vec<String> = env::args().collect();\n");
+    let argc: usize = bounds.iter().map(|b| b.slots().len()).sum();
     s.push_str(&format!(
         "    if args.len() != {} {{ std::process::exit(2); }}\n",
-        bounds.len() + 1
+        argc + 1
     ));
-    for (i, b) in bounds.iter().enumerate() {
-        let parse_expr = match b.var_type {
-            VarType::F32  => format!("args[{}].parse::<f32>().unwrap()", i + 1),
-            VarType::F64  => format!("args[{}].parse::<f64>().unwrap()", i + 1),
-            VarType::Bool => format!("args[{}].parse::<i32>().unwrap() != 0", i + 1),
-            VarType::I8   => format!("args[{}].parse::<i8>().unwrap()", i + 1),
-            VarType::I16  => format!("args[{}].parse::<i16>().unwrap()", i + 1),
-            VarType::I32  => format!("args[{}].parse::<i32>().unwrap()", i + 1),
-            VarType::I64  => format!("args[{}].parse::<i64>().unwrap()", i + 1),
-            VarType::U8   => format!("args[{}].parse::<u8>().unwrap()", i + 1),
-            VarType::U16  => format!("args[{}].parse::<u16>().unwrap()", i + 1),
-            VarType::U32  => format!("args[{}].parse::<u32>().unwrap()", i + 1),
-            VarType::U64  => format!("args[{}].parse::<u64>().unwrap()", i + 1),
-            VarType::Char => format!("args[{}].chars().next().unwrap()", i + 1),
+    let mut i = 1;
+    for b in bounds {
+        match b.kind {
+            InputKind::Scalar => {
+                s.push_str(&format!("    let {}: {} = {};\n",
+                    b.name, b.var_type.rust_type(), rust_parse_arg(&b.var_type, i)));
+                i += 1;
+            }
+            InputKind::Array { max_len } => {
+                s.push_str(&format!("    let {}: u32 = {};\n",
+                    b.len_name(), rust_parse_arg(&VarType::U32, i)));
+                let elems: Vec<String> = (0..max_len)
+                    .map(|e| rust_parse_arg(&b.var_type, i + 1 + e))
+                    .collect();
+                s.push_str(&format!("    let {}: [{}; {}] = [{}];\n",
+                    b.name, b.var_type.rust_type(), max_len, elems.join(", ")));
+                i += 1 + max_len;
+            }
+            InputKind::Str { max_len, .. } => {
+                let bytes: Vec<String> = (0..max_len)
+                    .map(|e| rust_parse_arg(&VarType::U8, i + e))
+                    .collect();
+                s.push_str(&format!("    let {}: [u8; {}] = [{}, 0];\n",
+                    b.name, max_len + 1, bytes.join(", ")));
+                s.push_str(&rust_str_len(b, max_len));
+                i += max_len;
+            }
+            InputKind::Struct { ref layout, .. } => {
+                let fields: Vec<String> = layout.fields.iter().enumerate()
+                    .map(|(e, f)| format!("{}: {}", f.rust_name, rust_parse_arg(&f.var_type, i + e)))
+                    .collect();
+                s.push_str(&format!("    let {}: {} = {} {{ {} }};\n",
+                    b.name, layout.rust_type, layout.rust_type, fields.join(", ")));
+                i += layout.fields.len();
+            }
+        }
+    }
+    // Printed under the C names so both runners' output lines match
+    if let Some(map) = error_map {
+        let (ok, err) = rust_fallible_arms(map);
+        let ok_print = match map.ok_type {
+            Some(ty) => rust_print_named("ok", "v", &ty),
+            None     => "    println!(\"ok\");\n".to_string(),
         };
-        s.push_str(&format!("    let {}: {} = {};\n",
-            b.name, b.var_type.rust_type(), parse_expr));
+        let detail = match map.rust { RustFallible::Option => "None", RustFallible::Result => "Err" };
+        s.push_str(&format!("    match {}({}) {{\n", function_name, rust_call_args(bounds, true)));
+        s.push_str(&format!("        {} => {{ {} }}\n", ok, ok_print.trim()));
+        s.push_str(&format!("        {} => {{ println!(\"err={}\"); }}\n", err, detail));
+        s.push_str("    }\n}\n");
+    } else if let Some(layout) = ret_struct {
+        s.push_str(&format!(
+            "    let r = {}({});\n", function_name, rust_call_args(bounds, true)
+        ));
+        for f in &layout.fields {
+            s.push_str(&rust_print_named(&f.c_name, &format!("r.{}", f.rust_name), &f.var_type));
+        }
+        s.push_str("}\n");
+    } else if !outputs.is_empty() {
+        s.push_str(&format!(
+            "    let r = {}({});\n", function_name, rust_call_args(bounds, true)
+        ));
+        for o in outputs {
+            s.push_str(&rust_print_named(&o.name, &rust_output_expr("r", o), &o.var_type));
+        }
+        s.push_str("}\n");
+    } else {
+        s.push_str(&format!(
+            "    let r = {}({});\n",
+            function_name,
+            rust_call_args(bounds, true)
+        ));
+        match config.return_type {
+            Some(ty) => s.push_str(&rust_print_line("", "r", &ty)),
+            None     => s.push_str("    println!(\"void\");\n"),
+        }
+        s.push_str("}\n");
     }
-    let call_args: Vec<String> = bounds.iter().map(|b| b.name.clone()).collect();
-    s.push_str(&format!(
-        "    let r = {}({}) as i64;\n",
-        function_name,
-        call_args.join(", ")
-    ));
-    s.push_str("    println!(\"{}\", r);\n}\n");
-    let path = format!("/tmp/equivalence_checker/{}_rust_runner.rs", function_name);
+    let path = config.workspace.file("rust_runner.rs");
     fs::write(&path, s)?;
     Ok(path)
 }
@@ -368,9 +1104,11 @@ fn generate_rust_runner(
 // ── Compilation ───────────────────────────────────────
 
 fn compile_c_runner(src: &str, out: &str) -> Result<()> {
-    let o = Command::new("clang-15")
+    let clang = &toolchain::get().clang;
+    let o = clang.command()
         .args(["-O0", src, "-o", out])
-        .output()?;
+        .output()
+        .map_err(|e| clang.spawn_error(e))?;
     if !o.status.success() {
         return Err(CheckerError::CompilationError(format!(
             "C runner build failed:\n{}",
@@ -381,9 +1119,11 @@ fn compile_c_runner(src: &str, out: &str) -> Result<()> {
 }
 
 fn compile_rust_runner(src: &str, out: &str) -> Result<()> {
-    let o = Command::new("rustup")
-        .args(["run", "1.69.0", "rustc", "-C", "opt-level=0", src, "-o", out])
-        .output()?;
+    let rustc = &toolchain::get().rustc;
+    let o = rustc.command()
+        .args(["-C", "opt-level=0", src, "-o", out])
+        .output()
+        .map_err(|e| rustc.spawn_error(e))?;
     if !o.status.success() {
         return Err(CheckerError::CompilationError(format!(
             "Rust runner build failed:\n{}",
@@ -394,16 +1134,18 @@ fn compile_rust_runner(src: &str, out: &str) -> Result<()> {
 }
 
 fn compile_c_to_ir(c_file: &str, output: &str) -> Result<()> {
-    let o = Command::new("clang-15")
+    let tc = toolchain::get();
+    let o = tc.clang.command()
         .args([
             "-emit-llvm", "-c", "-O0",
             "-Xclang", "-disable-O0-optnone",
             "-fno-stack-protector",
             "-fno-inline",
-            "-I/home/fathima/klee/include",
-            c_file, "-o", output,
         ])
-        .output()?;
+        .args(tc.klee_include_flag())
+        .args([c_file, "-o", output])
+        .output()
+        .map_err(|e| tc.clang.spawn_error(e))?;
     if !o.status.success() {
         return Err(CheckerError::CompilationError(format!(
             "C compilation failed:\n{}",
@@ -414,9 +1156,9 @@ fn compile_c_to_ir(c_file: &str, output: &str) -> Result<()> {
 }
 
 fn compile_rust_to_ir(rust_file: &str, output: &str) -> Result<()> {
-    let o = Command::new("rustup")
+    let rustc = &toolchain::get().rustc;
+    let o = rustc.command()
         .args([
-            "run", "1.69.0", "rustc",
             "--emit=llvm-bc",
             "-C", "opt-level=0",
             "-C", "inline-threshold=0",
@@ -426,7 +1168,8 @@ fn compile_rust_to_ir(rust_file: &str, output: &str) -> Result<()> {
             "-o", output,
             rust_file,
         ])
-        .output()?;
+        .output()
+        .map_err(|e| rustc.spawn_error(e))?;
     if !o.status.success() {
         return Err(CheckerError::CompilationError(format!(
             "Rust compilation failed:\n{}",
@@ -436,6 +1179,37 @@ fn compile_rust_to_ir(rust_file: &str, output: &str) -> Result<()> {
     Ok(())
 }
 
+fn link_ir(inputs: &[&str], output: &str) -> Result<()> {
+    let llvm_link = &toolchain::get().llvm_link;
+    let o = llvm_link.command()
+        .args(inputs)
+        .args(["-o", output])
+        .output()
+        .map_err(|e| llvm_link.spawn_error(e))?;
+    if !o.status.success() {
+        return Err(CheckerError::CompilationError(format!(
+            "Linking {} failed:\n{}",
+            output,
+            String::from_utf8_lossy(&o.stderr)
+        )).into());
+    }
+    Ok(())
+}
+
+/// Compile both miter halves to bitcode and link them into one module.
+fn build_miter(config: &AnalysisConfig) -> Result<String> {
+    let c_src    = generate_c_miter(config)?;
+    let rust_src = generate_rust_miter(config)?;
+    let c_bc     = config.workspace.file("c_miter.bc");
+    let rust_bc  = config.workspace.file("rust_miter.bc");
+    let miter_bc = config.workspace.file("miter.bc");
+    compile_c_to_ir(&c_src, &c_bc)?;
+    compile_rust_to_ir(&rust_src, &rust_bc)?;
+    link_ir(&[&c_bc, &rust_bc], &miter_bc)?;
+    verify_ir_basic(&miter_bc)?;
+    Ok(miter_bc)
+}
+
 fn verify_ir_basic(ir_path: &str) -> Result<()> {
     let meta = fs::metadata(ir_path)?;
     if meta.len() == 0 {
@@ -456,15 +1230,17 @@ fn verify_ir_basic(ir_path: &str) -> Result<()> {
 }
 
 fn emit_c_ll(c_file: &str, out_ll: &str) -> Result<()> {
-    let o = Command::new("clang-15")
+    let tc = toolchain::get();
+    let o = tc.clang.command()
         .args([
             "-S", "-emit-llvm", "-O0",
             "-Xclang", "-disable-O0-optnone",
             "-fno-stack-protector", "-fno-inline",
-            "-I/home/fathima/klee/include",
-            c_file, "-o", out_ll,
         ])
-        .output()?;
+        .args(tc.klee_include_flag())
+        .args([c_file, "-o", out_ll])
+        .output()
+        .map_err(|e| tc.clang.spawn_error(e))?;
     if !o.status.success() {
         return Err(CheckerError::CompilationError(format!(
             "C .ll generation failed:\n{}",
@@ -475,9 +1251,9 @@ fn emit_c_ll(c_file: &str, out_ll: &str) -> Result<()> {
 }
 
 fn emit_rust_ll(rust_file: &str, out_ll: &str) -> Result<()> {
-    let o = Command::new("rustup")
+    let rustc = &toolchain::get().rustc;
+    let o = rustc.command()
         .args([
-            "run", "1.69.0", "rustc",
             "--emit=llvm-ir",
             "-C", "opt-level=0",
             "-C", "inline-threshold=0",
@@ -486,7 +1262,8 @@ fn emit_rust_ll(rust_file: &str, out_ll: &str) -> Result<()> {
             "--crate-type=lib",
             rust_file, "-o", out_ll,
         ])
-        .output()?;
+        .output()
+        .map_err(|e| rustc.spawn_error(e))?;
     if !o.status.success() {
         return Err(CheckerError::CompilationError(format!(
             "Rust .ll generation failed:\n{}",
@@ -499,69 +1276,80 @@ fn emit_rust_ll(rust_file: &str, out_ll: &str) -> Result<()> {
 // ── Main entry point ──────────────────────────────────
 
 pub fn compile(config: &AnalysisConfig) -> Result<IrFiles> {
-    fs::create_dir_all("/tmp/equivalence_checker")?;
-
-    // Parse typed bounds (defaults to i32 for all)
-    let typed_bounds = parse_typed_bounds(config);
+    if let Some(ir) = cache::load_ir(config) {
+        println!("  ✓ Reusing cached bitcode and runners");
+        return Ok(ir);
+    }
 
     // ── Harnesses ─────────────────────────────────────
     println!("  Generating KLEE harnesses...");
-    let c_harness    = generate_c_harness(&config.c_file, &config.function_name, &typed_bounds)?;
-    let rust_harness = generate_rust_harness(&config.rust_file, &config.function_name, &typed_bounds)?;
+    let c_harness    = generate_c_harness(config)?;
+    let rust_harness = generate_rust_harness(config)?;
 
     // ── Runners ───────────────────────────────────────
     println!("  Generating runner programs...");
-    let c_runner_src    = generate_c_runner(&config.c_file, &config.function_name, &typed_bounds)?;
-    let rust_runner_src = generate_rust_runner(&config.rust_file, &config.function_name, &typed_bounds)?;
+    let c_runner_src    = generate_c_runner(config)?;
+    let rust_runner_src = generate_rust_runner(config)?;
 
-    let c_runner_bin = format!("/tmp/equivalence_checker/{}_c_runner", config.function_name);
-    let rust_runner_bin = format!("/tmp/equivalence_checker/{}_rust_runner", config.function_name);
+    // ── Builds ────────────────────────────────────────
+    // The two sides share nothing, so each is built on its own thread:
+    // runner, human-readable .ll (non-fatal, only for debugging), bitcode for KLEE
+    let c_runner_bin    = config.workspace.file("c_runner");
+    let rust_runner_bin = config.workspace.file("rust_runner");
+    let c_ll            = config.workspace.output("ir/c_harness.ll")?;
+    let r_ll            = config.workspace.output("ir/rust_harness.ll")?;
+    let c_ir_path       = config.workspace.file("c.bc");
+    let rust_ir_path    = config.workspace.file("rust.bc");
 
-    println!("  Compiling runners...");
-    compile_c_runner(&c_runner_src, &c_runner_bin)?;
-    compile_rust_runner(&rust_runner_src, &rust_runner_bin)?;
+    println!("  Compiling C and Rust runners and harnesses in parallel...");
+    both_sides(
+        || {
+            compile_c_runner(&c_runner_src, &c_runner_bin)?;
+            emit_c_ll(&c_harness, &c_ll).ok();
+            compile_c_to_ir(&c_harness, &c_ir_path)?;
+            if !Path::new(&c_ir_path).exists() {
+                return Err(CheckerError::CompilationError("C compilation produced no output".into()).into());
+            }
+            verify_ir_basic(&c_ir_path)
+        },
+        || {
+            compile_rust_runner(&rust_runner_src, &rust_runner_bin)?;
+            emit_rust_ll(&rust_harness, &r_ll).ok();
+            compile_rust_to_ir(&rust_harness, &rust_ir_path)?;
+            if !Path::new(&rust_ir_path).exists() {
+                return Err(CheckerError::CompilationError("Rust compilation produced no output".into()).into());
+            }
+            verify_ir_basic(&rust_ir_path)
+        },
+    )?;
     println!("    → C runner:    {}", c_runner_bin);
     println!("    → Rust runner: {}", rust_runner_bin);
+    println!("    → C .ll:       {}", c_ll);
+    println!("    → Rust .ll:    {}", r_ll);
+    println!("    → C IR:        {}", c_ir_path);
+    println!("    → Rust IR:     {}", rust_ir_path);
 
-    // ── Human-readable IR (.ll) ───────────────────────
-    let ll_dir = "output/ir";
-    fs::create_dir_all(ll_dir)?;
-    let c_ll = format!("{}/{}_c_harness.ll",    ll_dir, config.function_name);
-    let r_ll = format!("{}/{}_rust_harness.ll", ll_dir, config.function_name);
-
-    println!("  Dumping human-readable LLVM IR (.ll)...");
-    emit_c_ll(&c_harness, &c_ll).ok();    // non-fatal — only for debugging
-    emit_rust_ll(&rust_harness, &r_ll).ok();
-    println!("    → C .ll:    {}", c_ll);
-    println!("    → Rust .ll: {}", r_ll);
-
-    // ── Bitcode for KLEE ─────────────────────────────
-    let c_ir_path = format!("/tmp/equivalence_checker/{}_c.bc", config.function_name);
-    let rust_ir_path = format!("/tmp/equivalence_checker/{}_rust.bc", config.function_name);
-
-    println!("  Compiling C harness to LLVM IR...");
-    compile_c_to_ir(&c_harness, &c_ir_path)?;
-    if !Path::new(&c_ir_path).exists() {
-        return Err(CheckerError::CompilationError("C compilation produced no output".into()).into());
-    }
-    println!("    → Generated: {}", c_ir_path);
-
-    println!("  Compiling Rust harness to LLVM IR...");
-    compile_rust_to_ir(&rust_harness, &rust_ir_path)?;
-    if !Path::new(&rust_ir_path).exists() {
-        return Err(CheckerError::CompilationError("Rust compilation produced no output".into()).into());
-    }
-    println!("    → Generated: {}", rust_ir_path);
-
-    println!("  Verifying IR files...");
-    verify_ir_basic(&c_ir_path)?;
-    verify_ir_basic(&rust_ir_path)?;
-    println!("    → IR files created successfully");
+    // ── Single-miter module ──────────────────────────
+    // The assertion compares with ==, which cannot honour --float policies
+    let miter_bc = if !config.miter {
+        None
+    } else if config.has_float_results() {
+        println!("  ⚠ Miter mode compares results exactly; float results use separate runs");
+        None
+    } else {
+        println!("  Building single-miter module...");
+        let bc = build_miter(config)?;
+        println!("    → Generated: {}", bc);
+        Some(bc)
+    };
 
-    Ok(IrFiles {
+    let ir = IrFiles {
         c_ir_path,
         rust_ir_path,
         c_runner_bin,
         rust_runner_bin,
-    })
+        miter_bc,
+    };
+    cache::store_ir(config, &ir);
+    Ok(ir)
 }
\ No newline at end of file
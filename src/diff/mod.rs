@@ -1,7 +1,7 @@
 // src/diff/mod.rs
 // Semantic divergence detection using KLEE path constraints
 
-use crate::types::PathSummary;
+use crate::types::{InputRegion, PathSummary};
 
 #[derive(Debug, Clone)]
 pub struct SemanticDiff {
@@ -12,16 +12,21 @@ pub struct SemanticDiff {
     pub human_c:         String,
     pub human_rust:      String,
     pub suggestion:      String,
+    /// Every input on this path pair whose results differ, e.g.
+    /// "C and Rust differ for all x in [11, 15]"
+    pub region:          Option<String>,
 }
 
 pub fn find_semantic_divergence(
     c_path:    Option<&PathSummary>,
     rust_path: Option<&PathSummary>,
+    region:    Option<&InputRegion>,
     c_src:     &str,
     rust_src:  &str,
 ) -> Option<SemanticDiff> {
     let cp = c_path?;
     let rp = rust_path?;
+    let region = region.map(|r| describe_region(r, cp, rp));
 
     if cp.constraints == vec!["true".to_string()]
     && rp.constraints == vec!["true".to_string()] {
@@ -99,6 +104,7 @@ pub fn find_semantic_divergence(
                     human_c,
                     human_rust,
                     suggestion,
+                    region,
                 });
             }
         }
@@ -122,12 +128,48 @@ pub fn find_semantic_divergence(
             human_c,
             human_rust,
             suggestion,
+            region,
         });
     }
 
     None
 }
 
+// ── Input region ──────────────────────────────────────────────────────────────
+
+/// "C and Rust differ for all x in [11, 15]" when the intervals are the
+/// whole region; otherwise the intervals plus the path conditions that
+/// narrow them, as source-level expressions.
+pub fn describe_region(region: &InputRegion, c_path: &PathSummary, rust_path: &PathSummary) -> String {
+    let intervals: Vec<String> = region.intervals.iter()
+        .map(|(name, lo, hi)| if lo == hi {
+            format!("{} = {}", name, lo)
+        } else {
+            format!("{} in [{}, {}]", name, lo, hi)
+        })
+        .collect();
+    if region.exact {
+        return if intervals.is_empty() {
+            "C and Rust differ for every input in bounds".into()
+        } else {
+            format!("C and Rust differ for all {}", intervals.join(", "))
+        };
+    }
+
+    let mut conditions: Vec<String> = Vec::new();
+    for c in c_path.constraints.iter().chain(&rust_path.constraints) {
+        let human = smt_to_human(c);
+        if human != "true" && !conditions.contains(&human) {
+            conditions.push(human);
+        }
+    }
+    if let (Some(cr), Some(rr)) = (&c_path.return_expr, &rust_path.return_expr) {
+        conditions.push(format!("{} != {}", smt_to_human(cr), smt_to_human(rr)));
+    }
+    let within = if intervals.is_empty() { String::new() } else { format!(" with {}", intervals.join(", ")) };
+    format!("C and Rust differ for inputs{} where {}", within, conditions.join(" ∧ "))
+}
+
 // ── Variable extraction ───────────────────────────────────────────────────────
 
 /// Extract variable names from a KLEE constraint.
@@ -213,7 +255,12 @@ pub fn smt_to_human(s: &str) -> String {
     // Arithmetic
     for &(prefix, op) in &[("(Add ", "+"), ("(Sub ", "-"), ("(Mul ", "*")] {
         if let Some(rest) = s.strip_prefix(prefix) {
-            let rest = rest.trim_end_matches(')');
+            let rest = rest.strip_suffix(')').unwrap_or(rest).trim();
+            // KLEE writes the width first: (Add w32 a b)
+            let rest = match rest.split_once(' ') {
+                Some((w, tail)) if w.strip_prefix('w').is_some_and(|n| n.parse::<u32>().is_ok()) => tail,
+                _ => rest,
+            };
             if let Some((l, r)) = split_two_smt(rest) {
                 return format!("({} {} {})", smt_to_human(l), op, smt_to_human(r));
             }
@@ -1,176 +1,561 @@
 // src/equivalence/mod.rs
 use crate::types::{
-    AnalysisConfig, EquivalenceResult, Verdict, Counterexample,
+    AnalysisConfig, EquivalenceResult, Verdict, Evidence, ExhaustiveRun, Counterexample,
     ConcreteBehavior, Difference, DifferenceKind, PathSummary,
-    CheckerStatistics, ReturnKind, EquivalenceDetail, DivergingInput, ParamType,
+    CheckerStatistics, ReturnKind, DivergenceCluster, InputRegion, Partition, PartitionRow,
+    PathCorrespondence, UnmatchedPath, UnmatchedKind, ProgramKind, InputBound, InputKind, Exploration,
+    ErrorMap, RustStr, VarType, Distribution, FloatCompare, FloatMode, NanPolicy, ZeroPolicy, InfPolicy,
 };
 use crate::compiler::IrFiles;
+use crate::symbolic::{MiterRun, SymbolicSummaries};
 use anyhow::Result;
-use std::time::Instant;
-use std::collections::HashMap;
-use std::process::Command;
-use z3::{Config, Context, Solver, SatResult, ast::{Ast, Int, Bool}};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::collections::HashSet;
+use std::process::{Child, Command, Output, Stdio};
+use crate::smt::{branch_constants, KleeEncoder, EncodedPath};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use z3::{Config, Context, Params, Solver, SatResult, ast::Bool};
 
 pub fn check(
-    config:         &AnalysisConfig,
-    ir_files:       &IrFiles,
-    c_summaries:    &[PathSummary],
-    rust_summaries: &[PathSummary],
+    config:    &AnalysisConfig,
+    ir_files:  &IrFiles,
+    summaries: &SymbolicSummaries,
 ) -> Result<EquivalenceResult> {
     let start = Instant::now();
+    let mut result = compare(config, ir_files, summaries)?;
+    result.float_compare = config.has_float_results().then(|| config.float_compare.to_string());
+    result.exploration = Some(Exploration::of(config));
+    let cfg = Config::new();
+    let ctx = Context::new(&cfg);
+    let encoder = KleeEncoder::new(&ctx, config);
+    if let (Some(ce), Some(c), Some(rust)) = (&result.counterexample, &result.c_path, &result.rust_path) {
+        result.region = generalise(&encoder, config, &ce.inputs, c, rust, &mut result.statistics);
+    }
+    if let Some(corr) = path_correspondence(&encoder, summaries, &mut result.statistics) {
+        result.partitions = partition_table(&encoder, config, summaries, &corr, &mut result.statistics);
+        result.correspondence = Some(corr);
+    }
+    result.time_taken = start.elapsed().as_secs_f64();
+    Ok(result)
+}
+
+fn compare(
+    config:    &AnalysisConfig,
+    ir_files:  &IrFiles,
+    summaries: &SymbolicSummaries,
+) -> Result<EquivalenceResult> {
+    let c_summaries    = summaries.c_summaries.as_slice();
+    let rust_summaries = summaries.rust_summaries.as_slice();
     let mut stats = CheckerStatistics::default();
     stats.total_paths_c    = c_summaries.len();
     stats.total_paths_rust = rust_summaries.len();
+    // Recorded only once the random stages run
+    let seed = config.sampling.seed.unwrap_or_else(fresh_seed);
+    let mut sampled_seed = None;
 
     println!("\n  -- Equivalence Checking (KLEE + Concrete Execution) --");
 
     let cfg = Config::new();
     let ctx = Context::new(&cfg);
+    let encoder = KleeEncoder::new(&ctx, config);
 
-    // Collect test inputs from three sources:
-    // (a) KLEE witnesses embedded in path summaries
-    // (b) Z3-solved inputs satisfying each path's constraints
-    // (c) Systematic boundary + interior sweep
-
-    let mut all_inputs: Vec<Vec<(String, i64)>> = Vec::new();
+    // Collect-all mode keeps every divergence here instead of returning the first
+    let mut divergences: Vec<Divergence> = Vec::new();
 
-    // (a) witnesses
-    for ps in c_summaries.iter().chain(rust_summaries.iter()) {
-        if !ps.witness.is_empty() {
-            push_unique(&mut all_inputs, clamp_to_bounds(&ps.witness, config));
+    // Symbolic proof first: a satisfiable pair (or a failed miter assertion)
+    // is a counterexample once the runners confirm it
+    let mut more_refuting = Vec::new();
+    let mut proof = match &summaries.miter {
+        Some(run) => {
+            stats.miter_paths = run.paths.len();
+            miter_proof(config, run)
         }
-    }
-
-    // (b) Z3: up to 4 distinct inputs per path
-    for ps in c_summaries.iter().chain(rust_summaries.iter()) {
-        let mut seen: Vec<Vec<(String, i64)>> = Vec::new();
-        for _ in 0..4 {
-            if let Some(inp) = solve_single_input(
-                &ctx, config, &ps.constraints, &ps.label_map, &seen,
-            ) {
-                seen.push(inp.clone());
-                push_unique(&mut all_inputs, inp);
+        None => prove(&ctx, &encoder, config, c_summaries, rust_summaries, &mut more_refuting, &mut stats),
+    };
+    if let Proof::Refuted { input, paths } = &proof {
+        let c_out    = run_binary(&ir_files.c_runner_bin,    input, config);
+        let rust_out = run_binary(&ir_files.rust_runner_bin, input, config);
+        let confirmed = !matches!(c_out, BinaryOutput::Error)
+            && !matches!(rust_out, BinaryOutput::Error)
+            && c_out.differs_from(&rust_out, &config.float_compare);
+        if confirmed {
+            println!("       ✗ Confirmed: C={}  Rust={}", c_out.to_string_repr(), rust_out.to_string_repr());
+            stats.merged_pairs += 1;
+            let found = (input.clone(), c_out, rust_out);
+            if !config.collect_all {
+                let (ce, pair) = shrink_divergence(&ctx, &encoder, config, ir_files, summaries, found, &mut stats);
+                let pair = pair.or(*paths);
+                return Ok(EquivalenceResult {
+                    paths_compared: 1,
+                    counterexample: Some(ce),
+                    c_path: pair.map(|(i, _)| c_summaries[i].clone()),
+                    rust_path: pair.map(|(_, j)| rust_summaries[j].clone()),
+                    seed: sampled_seed,
+                    ..EquivalenceResult::new(Verdict::NotEquivalent, stats)
+                });
             }
+            divergences.push(found);
+        } else {
+            println!("       ? Runners agree on the Z3 model -- proof inconclusive");
+            proof = Proof::Inconclusive("Z3 model not confirmed by the runners".into());
         }
     }
-
-    // (c) boundary + interior sweep
-    for inp in generate_boundary_inputs(config) {
-        push_unique(&mut all_inputs, inp);
+    // Every other refuted path pair, once the runners confirm it
+    for input in more_refuting {
+        if let Some((c_out, rust_out)) = diverges(config, ir_files, &input) {
+            println!("       ✗ Confirmed {:?}: C={}  Rust={}", input, c_out.to_string_repr(), rust_out.to_string_repr());
+            stats.merged_pairs += 1;
+            divergences.push((input, c_out, rust_out));
+        }
     }
 
-    println!("     Generated {} test inputs", all_inputs.len());
-
+    // Small domains: run every input instead of sampling
     let mut checked = 0u32;
-    let mut detail  = EquivalenceDetail::default();
+    let mut exhaustive = None;
+    let domain = config.domain_size()
+        .filter(|&n| config.exhaustive_limit > 0 && n <= config.exhaustive_limit as u128);
+    if let Some(domain) = domain {
+        let (run, mut found) = enumerate_domain(config, ir_files, domain as u64, &mut stats);
+        if !config.collect_all && !found.is_empty() {
+            let first = found.remove(0);
+            let (ce, pair) = shrink_divergence(&ctx, &encoder, config, ir_files, summaries, first, &mut stats);
+            let (cp, rp) = closest_paths(config, summaries, &ce.inputs, pair);
+            return Ok(EquivalenceResult {
+                paths_compared: run.inputs_run as u32,
+                counterexample: Some(ce),
+                c_path: cp,
+                rust_path: rp,
+                exhaustive: Some(run),
+                seed: sampled_seed,
+                ..EquivalenceResult::new(Verdict::NotEquivalent, stats)
+            });
+        }
+        divergences.append(&mut found);
+        checked = run.inputs_run as u32;
+        exhaustive = Some(run);
+    } else {
+        // Collect test inputs from four sources:
+        // (a) KLEE witnesses embedded in path summaries
+        // (b) Z3-solved inputs satisfying each path's constraints
+        // (c) Systematic boundary + interior sweep
+        // (d) Seeded random draws
+        // then fuzz around the KLEE branch constants for the time budget
+        sampled_seed = Some(seed);
+        let mut rng = StdRng::seed_from_u64(seed);
 
-    for input in &all_inputs {
-        println!("     Testing {:?}", input);
-        let c_out    = run_binary(&ir_files.c_runner_bin,    input, config);
-        let rust_out = run_binary(&ir_files.rust_runner_bin, input, config);
+        let mut all_inputs: Vec<Vec<(String, i64)>> = Vec::new();
 
-        match (&c_out, &rust_out) {
-            (BinaryOutput::Error, _) | (_, BinaryOutput::Error) => {
-                println!("       ? runner error -- skipping");
-                continue;
+        // (a) witnesses
+        let miter_paths = summaries.miter.iter().flat_map(|run| run.paths.iter());
+        for ps in c_summaries.iter().chain(rust_summaries.iter()).chain(miter_paths) {
+            if !ps.witness.is_empty() {
+                push_unique(&mut all_inputs, clamp_to_bounds(&ps.witness, config));
             }
-            _ => {}
         }
 
-        detail.inputs_tested += 1;
-        checked += 1;
-        stats.merged_pairs += 1;
+        // (b) Z3: up to 4 distinct inputs per path
+        for ps in c_summaries.iter().chain(rust_summaries.iter()) {
+            let path = encoder.encode_path(&ps.constraints, ps.return_expr.as_deref());
+            for reason in &path.unsupported {
+                println!("       ⚠ {}: constraint not encoded -- {}", ps.id, reason);
+            }
+            stats.unsupported_constraints += path.unsupported.len();
+            let mut seen: Vec<Vec<(String, i64)>> = Vec::new();
+            for _ in 0..4 {
+                if let Some(inp) = solve_single_input(&ctx, &encoder, &path, &seen, &mut stats) {
+                    seen.push(inp.clone());
+                    push_unique(&mut all_inputs, inp);
+                }
+            }
+        }
 
-        println!("       C={}  Rust={}", c_out.to_string_repr(), rust_out.to_string_repr());
-
-        if c_out.differs_from(&rust_out) {
-            println!("       ✗ DIFFER -- counterexample found");
-            detail.return_value_match = Some(false);
-
-            let input_strings: Vec<(String, String)> =
-                input.iter().map(|(n, v)| (n.clone(), v.to_string())).collect();
-
-            let ce = Counterexample {
-                inputs: input.clone(),
-                input_strings,
-                c_behavior: ConcreteBehavior {
-                    return_value: c_out.to_string_repr(),
-                    ..Default::default()
-                },
-                rust_behavior: ConcreteBehavior {
-                    return_value: rust_out.to_string_repr(),
-                    ..Default::default()
-                },
-                differences: vec![Difference {
-                    kind: DifferenceKind::ReturnValue,
-                    c_value: c_out.to_string_repr(),
-                    rust_value: rust_out.to_string_repr(),
-                }],
-            };
+        // (c) boundary + interior sweep
+        for inp in generate_boundary_inputs(config) {
+            push_unique(&mut all_inputs, inp);
+        }
 
-            let cp = c_summaries.iter()
-                .min_by_key(|p| witness_dist(&p.witness, input));
-            let rp = rust_summaries.iter()
-                .min_by_key(|p| witness_dist(&p.witness, input));
+        // (d) seeded random draws
+        if config.sampling.count > 0 {
+            println!("     Random sampling: {} draws, {} distribution, seed {}",
+                config.sampling.count, config.sampling.distribution, seed);
+            for inp in random_inputs(config, &mut rng) {
+                push_unique(&mut all_inputs, inp);
+            }
+        }
 
-            return Ok(EquivalenceResult {
-                verdict: Verdict::NotEquivalent,
-                paths_compared: checked,
-                counterexample: Some(ce),
-                time_taken: start.elapsed().as_secs_f64(),
-                statistics: stats,
-                c_path: cp.cloned(),
-                rust_path: rp.cloned(),
-            });
+        // String bytes are generated per slot: drop buffers outside the string
+        // domain and zero everything after each terminator
+        let all_inputs: Vec<Vec<(String, i64)>> = all_inputs.into_iter()
+            .filter(|inp| strings_admissible(inp, config))
+            .map(|inp| truncate_strings(inp, config))
+            .fold(Vec::new(), |mut acc, inp| { push_unique(&mut acc, inp); acc });
+
+        println!("     Generated {} test inputs", all_inputs.len());
+
+        for input in &all_inputs {
+            println!("     Testing {:?}", input);
+            let c_out    = run_binary(&ir_files.c_runner_bin,    input, config);
+            let rust_out = run_binary(&ir_files.rust_runner_bin, input, config);
+
+            match (&c_out, &rust_out) {
+                (BinaryOutput::Error, _) | (_, BinaryOutput::Error) => {
+                    println!("       ? runner error -- skipping");
+                    continue;
+                }
+                _ => {}
+            }
+
+            checked += 1;
+            stats.merged_pairs += 1;
+
+            println!("       C={}  Rust={}", c_out.to_string_repr(), rust_out.to_string_repr());
+
+            if c_out.differs_from(&rust_out, &config.float_compare) {
+                println!("       ✗ DIFFER -- counterexample found");
+
+                let found = (input.clone(), c_out, rust_out);
+                if config.collect_all {
+                    divergences.push(found);
+                    continue;
+                }
+                let (ce, pair) = shrink_divergence(&ctx, &encoder, config, ir_files, summaries, found, &mut stats);
+                let (cp, rp) = closest_paths(config, summaries, &ce.inputs, pair);
+
+                return Ok(EquivalenceResult {
+                    paths_compared: checked,
+                    counterexample: Some(ce),
+                    c_path: cp,
+                    rust_path: rp,
+                    seed: sampled_seed,
+                    ..EquivalenceResult::new(Verdict::NotEquivalent, stats)
+                });
+            }
+
+            println!("       ✓ Both return {}", c_out.to_string_repr());
+        }
+
+        // Fuzzing adds nothing once the proof covers every path
+        let settled = matches!(proof, Proof::Proved) && summaries.coverage_complete();
+        if !settled {
+            let constraints: Vec<String> = c_summaries.iter().chain(rust_summaries.iter())
+                .chain(summaries.miter.iter().flat_map(|run| run.paths.iter()))
+                .flat_map(|ps| ps.constraints.iter().cloned())
+                .collect();
+            let constants = branch_constants(&constraints);
+            let (tested, mut found) = fuzz(config, ir_files, &all_inputs, &constants, &mut rng, &mut stats);
+            checked += tested;
+            if !config.collect_all && !found.is_empty() {
+                let first = found.remove(0);
+                let (ce, pair) = shrink_divergence(&ctx, &encoder, config, ir_files, summaries, first, &mut stats);
+                let (cp, rp) = closest_paths(config, summaries, &ce.inputs, pair);
+                return Ok(EquivalenceResult {
+                    paths_compared: checked,
+                    counterexample: Some(ce),
+                    c_path: cp,
+                    rust_path: rp,
+                    seed: sampled_seed,
+                    ..EquivalenceResult::new(Verdict::NotEquivalent, stats)
+                });
+            }
+            divergences.append(&mut found);
         }
+    }
 
-        println!("       ✓ Both return {}", c_out.to_string_repr());
+    if !divergences.is_empty() {
+        let total = divergences.len();
+        let mut clustered = cluster_divergences(&ctx, &encoder, config, ir_files, summaries, divergences, &mut stats);
+        println!("\n  ✗ Programs are NOT EQUIVALENT: {} divergent input(s) in {} cluster(s)", total, clustered.len());
+        let (pair, first) = clustered.remove(0);
+        let (cp, rp) = closest_paths(config, summaries, &first.representative.inputs, pair);
+        let clusters: Vec<DivergenceCluster> = std::iter::once(first)
+            .chain(clustered.into_iter().map(|(_, c)| c))
+            .collect();
+        return Ok(EquivalenceResult {
+            paths_compared: checked.max(1),
+            counterexample: Some(clusters[0].representative.clone()),
+            c_path: cp,
+            rust_path: rp,
+            exhaustive,
+            seed: sampled_seed,
+            clusters,
+            ..EquivalenceResult::new(Verdict::NotEquivalent, stats)
+        });
     }
 
     if checked == 0 {
         println!("\n  ⚠ Could not execute any inputs -- check runner binaries");
         return Ok(EquivalenceResult {
-            verdict: Verdict::Unknown,
-            paths_compared: 0,
-            counterexample: None,
-            time_taken: start.elapsed().as_secs_f64(),
-            statistics: stats,
-            c_path: None,
-            rust_path: None,
+            seed: sampled_seed,
+            ..EquivalenceResult::new(Verdict::Unknown, stats)
         });
     }
 
-    println!("\n  ✓ Programs are SEMANTICALLY EQUIVALENT ({} inputs checked)", checked);
+    let evidence = evidence_for(&proof, summaries, exhaustive.as_ref());
+    let verdict = match evidence {
+        Evidence::SymbolicProof => {
+            println!("\n  ✓ Programs are PROVED EQUIVALENT within bounds ({} inputs also checked)", checked);
+            Verdict::ProvedEquivalent
+        }
+        _ => {
+            println!("\n  ✓ Programs are SEMANTICALLY EQUIVALENT ({} inputs checked)", checked);
+            if matches!(proof, Proof::Proved) {
+                println!("    → Every explored path pair agrees, but KLEE did not explore every path");
+            }
+            Verdict::Equivalent
+        }
+    };
+    println!("    → Evidence: {} -- {}", evidence.label(), evidence.meaning());
+    let cut_short = summaries.cut_short();
+    if let Some(why) = &cut_short {
+        println!("    → KLEE stopped early: {}", why);
+    }
     Ok(EquivalenceResult {
-        verdict: Verdict::Equivalent,
         paths_compared: checked,
-        counterexample: None,
-        time_taken: start.elapsed().as_secs_f64(),
-        statistics: stats,
-        c_path: None,
-        rust_path: None,
+        evidence: Some(evidence),
+        cut_short,
+        exhaustive,
+        seed: sampled_seed,
+        ..EquivalenceResult::new(verdict, stats)
     })
 }
 
+/// Strongest evidence the run supports for an equivalent verdict.
+fn evidence_for(
+    proof:      &Proof,
+    summaries:  &SymbolicSummaries,
+    exhaustive: Option<&ExhaustiveRun>,
+) -> Evidence {
+    let complete = summaries.coverage_complete();
+    let paths: Vec<&PathSummary> = match &summaries.miter {
+        Some(run) => run.paths.iter().collect(),
+        None      => summaries.c_summaries.iter().chain(&summaries.rust_summaries).collect(),
+    };
+    if matches!(proof, Proof::Proved) && complete {
+        Evidence::SymbolicProof
+    } else if exhaustive.is_some_and(ExhaustiveRun::is_complete) {
+        Evidence::Exhaustive
+    } else if !complete {
+        Evidence::Incomplete
+    } else if paths.iter().all(|p| !p.witness.is_empty()) {
+        Evidence::PathCoverage
+    } else {
+        Evidence::Sampled
+    }
+}
+
+fn counterexample(
+    config:   &AnalysisConfig,
+    input:    &[(String, i64)],
+    c_out:    &BinaryOutput,
+    rust_out: &BinaryOutput,
+) -> Counterexample {
+    Counterexample {
+        inputs:        input.to_vec(),
+        input_strings: format_input_strings(input, config),
+        c_behavior:    c_out.behavior(config),
+        rust_behavior: rust_out.behavior(config),
+        differences:   c_out.differences(rust_out, config),
+        original:      None,
+    }
+}
+
+// ── Symbolic proof ────────────────────────────────────────────────────────────
+
+enum Proof {
+    /// Every C/Rust path pair returns the same value wherever both are feasible
+    Proved,
+    /// Inputs on which the results differ: found by Z3 on a (C path, Rust
+    /// path) pair, or by KLEE on a failing miter assertion (no pair)
+    Refuted { input: Vec<(String, i64)>, paths: Option<(usize, usize)> },
+    Inconclusive(String),
+}
+
+/// Product query over every pair of paths: is
+/// `bounds ∧ pc_C ∧ pc_Rust ∧ ret_C ≠ ret_Rust` satisfiable? In collect-all
+/// mode every refuted pair after the first adds its model to `more`.
+fn prove<'ctx>(
+    ctx:            &'ctx Context,
+    encoder:        &KleeEncoder<'ctx>,
+    config:         &AnalysisConfig,
+    c_summaries:    &[PathSummary],
+    rust_summaries: &[PathSummary],
+    more:           &mut Vec<Vec<(String, i64)>>,
+    stats:          &mut CheckerStatistics,
+) -> Proof {
+    println!("\n  -- Symbolic Proof (C path × Rust path) --");
+    let proof = prove_pairs(ctx, encoder, config, c_summaries, rust_summaries, more, stats);
+    match &proof {
+        Proof::Proved => println!(
+            "     ✓ {} path pair(s): return values agree wherever both paths are feasible",
+            c_summaries.len() * rust_summaries.len()
+        ),
+        Proof::Refuted { paths: Some((i, j)), .. } => println!(
+            "     ✗ {} × {}: return values can differ{}",
+            c_summaries[*i].id, rust_summaries[*j].id,
+            if more.is_empty() { String::new() } else { format!(" ({} more pair(s))", more.len()) }
+        ),
+        Proof::Refuted { paths: None, .. } => {}
+        Proof::Inconclusive(why) => println!("     ? Inconclusive: {}", why),
+    }
+    proof
+}
+
+fn prove_pairs<'ctx>(
+    ctx:            &'ctx Context,
+    encoder:        &KleeEncoder<'ctx>,
+    config:         &AnalysisConfig,
+    c_summaries:    &[PathSummary],
+    rust_summaries: &[PathSummary],
+    more:           &mut Vec<Vec<(String, i64)>>,
+    stats:          &mut CheckerStatistics,
+) -> Proof {
+    // Only a single integer result is bound symbolically by the harnesses
+    if config.result_type().is_none_or(|t| t.is_float()) || config.outputs.len() > 1 {
+        return Proof::Inconclusive("the compared result is not a single integer value".into());
+    }
+    if config.slots().iter().any(|b| b.var_type.is_float()) {
+        return Proof::Inconclusive("float inputs are not modelled".into());
+    }
+    if c_summaries.is_empty() || rust_summaries.is_empty() {
+        return Proof::Inconclusive("no path summaries".into());
+    }
+
+    let encode = |paths: &[PathSummary]| -> Result<Vec<(EncodedPath<'ctx>, z3::ast::BV<'ctx>)>, String> {
+        paths.iter().map(|ps| {
+            let path = encoder.encode_path(&ps.constraints, ps.return_expr.as_deref());
+            if let Some(reason) = path.unsupported.first() {
+                return Err(format!("{}: {}", ps.id, reason));
+            }
+            let ret = path.ret.clone().ok_or_else(|| format!("{} has no return value", ps.id))?;
+            Ok((path, ret))
+        }).collect()
+    };
+    let (c_paths, rust_paths) = match (encode(c_summaries), encode(rust_summaries)) {
+        (Ok(c), Ok(r))                => (c, r),
+        (Err(why), _) | (_, Err(why)) => return Proof::Inconclusive(why),
+    };
+
+    let mut params = Params::new(ctx);
+    params.set_u32("timeout", config.timeout.saturating_mul(1000));
+    let bounds = encoder.bounds();
+    let mut refuted = None;
+    for (i, (cp, c_ret)) in c_paths.iter().enumerate() {
+        for (j, (rp, rust_ret)) in rust_paths.iter().enumerate() {
+            let solver = Solver::new(ctx);
+            solver.set_params(&params);
+            for b in bounds.iter().chain(&cp.constraints).chain(&rp.constraints) {
+                solver.assert(b);
+            }
+            solver.assert(&encoder.returns_differ(c_ret, rust_ret));
+            match timed_check(&solver, stats) {
+                SatResult::Unsat => {}
+                SatResult::Sat => {
+                    let input = solver.get_model()
+                        .and_then(|m| encoder.inputs(&m))
+                        .map(|inp| truncate_strings(inp, config));
+                    let Some(input) = input else {
+                        return Proof::Inconclusive("Z3 returned no model".into());
+                    };
+                    match refuted {
+                        None if !config.collect_all => return Proof::Refuted { input, paths: Some((i, j)) },
+                        None => refuted = Some(Proof::Refuted { input, paths: Some((i, j)) }),
+                        Some(_) => more.push(input),
+                    }
+                }
+                SatResult::Unknown if refuted.is_some() => {}
+                SatResult::Unknown => return Proof::Inconclusive(format!(
+                    "Z3 gave up on {} × {}", c_summaries[i].id, rust_summaries[j].id
+                )),
+            }
+        }
+    }
+    match refuted {
+        Some(refuted) => refuted,
+        None => unprovable(config).map_or(Proof::Proved, |why| Proof::Inconclusive(why.into())),
+    }
+}
+
+/// Why agreement of the tracked results proves nothing about the outputs
+/// the runners compare. Refutations still hold: the runners confirm them.
+fn unprovable(config: &AnalysisConfig) -> Option<&'static str> {
+    if config.outputs.len() > 1 {
+        // Only the return value or first output is tracked (and asserted by the miter)
+        return Some("only the first of several outputs is compared symbolically");
+    }
+    // A failure is tracked as the sentinel, indistinguishable from a
+    // success returning the same value
+    config.error_map.is_some()
+        .then_some("under an error map a failure and a success returning the sentinel are tracked alike")
+}
+
+/// Single-miter mode: KLEE has already checked `C result == Rust result`
+/// on every path it explored. The assertion covers the tracked result only,
+/// so it proves nothing when other outputs go unchecked (see `unprovable`).
+fn miter_proof(config: &AnalysisConfig, run: &MiterRun) -> Proof {
+    println!("\n  -- Miter Proof (klee_assert over C + Rust) --");
+    let witness = run.failures.iter().find(|p| !p.witness.is_empty());
+    let proof = if let Some(ps) = witness {
+        let input = truncate_strings(clamp_to_bounds(&ps.witness, config), config);
+        Proof::Refuted { input, paths: None }
+    } else if let Some(ps) = run.failures.first() {
+        Proof::Inconclusive(format!("{}: assertion failed but KLEE wrote no input", ps.id))
+    } else if let Some((id, kind)) = run.errors.first() {
+        Proof::Inconclusive(format!(
+            "{}: KLEE '{}' error ({} error path(s) in total)", id, kind, run.errors.len()
+        ))
+    } else if let Some(why) = unprovable(config) {
+        Proof::Inconclusive(why.into())
+    } else {
+        Proof::Proved
+    };
+    match &proof {
+        Proof::Proved => println!(
+            "     ✓ {} miter path(s): the assertion holds on every one", run.paths.len()
+        ),
+        Proof::Refuted { input, .. } => println!(
+            "     ✗ klee_assert failed: results differ on {:?}", input
+        ),
+        Proof::Inconclusive(why) => println!("     ? Inconclusive: {}", why),
+    }
+    proof
+}
+
+/// `solver.check()`, counted in the statistics.
+fn timed_check(solver: &Solver, stats: &mut CheckerStatistics) -> SatResult {
+    let t = Instant::now();
+    let result = solver.check();
+    stats.z3_queries += 1;
+    stats.z3_time_ms += t.elapsed().as_millis() as u64;
+    result
+}
+
 // ── BinaryOutput ──────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
 enum BinaryOutput {
     Int(i64),
-    Float(f64),
+    Float(FloatBits),
+    /// "name=value" lines: the fields of a #[repr(C)] struct result, or the
+    /// return value and out-parameters, under their C names
+    Named(Vec<(String, BinaryOutput)>),
+    /// The failure side of an error map: C's sentinel/errno, Rust's None/Err
+    Failed(String),
     Void,
     Error,
 }
 
 impl BinaryOutput {
-    fn differs_from(&self, other: &BinaryOutput) -> bool {
+    fn differs_from(&self, other: &BinaryOutput, cmp: &FloatCompare) -> bool {
         match (self, other) {
-            (BinaryOutput::Int(a),   BinaryOutput::Int(b))   => a != b,
-            (BinaryOutput::Float(a), BinaryOutput::Float(b)) => {
-                let diff = (a - b).abs();
-                diff > 1e-9 * a.abs().max(b.abs()).max(1.0)
+            (BinaryOutput::Named(a), BinaryOutput::Named(b)) => {
+                a.len() != b.len()
+                    || a.iter().zip(b).any(|((na, va), (nb, vb))| na != nb || va.differs_from(vb, cmp))
             }
+            (BinaryOutput::Int(a),   BinaryOutput::Int(b))   => a != b,
+            (BinaryOutput::Float(a), BinaryOutput::Float(b)) => !a.matches(b, cmp),
             (BinaryOutput::Void, BinaryOutput::Void) => false,
+            // Both failed — how each language reports it is the mapping itself
+            (BinaryOutput::Failed(_), BinaryOutput::Failed(_)) => false,
             _ => true,
         }
     }
@@ -178,11 +563,230 @@ impl BinaryOutput {
     fn to_string_repr(&self) -> String {
         match self {
             BinaryOutput::Int(v)   => v.to_string(),
-            BinaryOutput::Float(v) => format!("{:.17e}", v),
+            BinaryOutput::Float(v) => v.to_string(),
+            BinaryOutput::Named(fields) => {
+                let shown: Vec<String> = fields.iter()
+                    .map(|(n, v)| format!("{}: {}", n, v.to_string_repr()))
+                    .collect();
+                format!("{{ {} }}", shown.join(", "))
+            }
+            BinaryOutput::Failed(detail) => format!("failure ({})", detail),
             BinaryOutput::Void     => "void".into(),
             BinaryOutput::Error    => "error".into(),
         }
     }
+
+    /// Differences against `other`: one per differing struct field or
+    /// output, otherwise a single return-value difference.
+    fn differences(&self, other: &BinaryOutput, config: &AnalysisConfig) -> Vec<Difference> {
+        if let (BinaryOutput::Named(a), BinaryOutput::Named(b)) = (self, other) {
+            let fields: Vec<Difference> = a.iter().zip(b)
+                .filter(|((_, va), (_, vb))| va.differs_from(vb, &config.float_compare))
+                .map(|((name, va), (_, vb))| Difference {
+                    kind: if config.return_struct.is_some() {
+                        DifferenceKind::ReturnField(name.clone())
+                    } else if name == "return" {
+                        DifferenceKind::ReturnValue
+                    } else {
+                        DifferenceKind::OutputParam(name.clone())
+                    },
+                    c_value:    va.to_string_repr(),
+                    rust_value: vb.to_string_repr(),
+                })
+                .collect();
+            if !fields.is_empty() { return fields; }
+        }
+        vec![Difference {
+            kind:       DifferenceKind::ReturnValue,
+            c_value:    self.to_string_repr(),
+            rust_value: other.to_string_repr(),
+        }]
+    }
+
+    /// What one side did, split into its return value and named outputs.
+    fn behavior(&self, config: &AnalysisConfig) -> ConcreteBehavior {
+        match self {
+            BinaryOutput::Named(values) if config.return_struct.is_none() => ConcreteBehavior {
+                return_value: values.iter()
+                    .find(|(n, _)| n == "return")
+                    .map_or_else(|| "void".to_string(), |(_, v)| v.to_string_repr()),
+                outputs: values.iter()
+                    .filter(|(n, _)| n != "return")
+                    .map(|(n, v)| (n.clone(), v.to_string_repr()))
+                    .collect(),
+                ..Default::default()
+            },
+            _ => ConcreteBehavior {
+                return_value: self.to_string_repr(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+// ── Floats ────────────────────────────────────────────────────────────────────
+
+/// A float result exactly as the runner printed it: the IEEE bit pattern of
+/// an f32 (`single`) or f64.
+#[derive(Debug, Clone, Copy)]
+struct FloatBits {
+    bits:   u64,
+    single: bool,
+}
+
+impl FloatBits {
+    fn value(&self) -> f64 {
+        if self.single { f32::from_bits(self.bits as u32) as f64 } else { f64::from_bits(self.bits) }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value() == 0.0
+    }
+
+    /// Largest finite magnitude of this width.
+    fn max_finite(&self) -> f64 {
+        if self.single { f32::MAX as f64 } else { f64::MAX }
+    }
+
+    /// Position on a line where adjacent representable values are 1 apart
+    /// (-0.0 and +0.0 share position 0).
+    fn ordinal(&self) -> i128 {
+        let (sign, magnitude) = if self.single {
+            (self.bits & 0x8000_0000 != 0, self.bits & 0x7fff_ffff)
+        } else {
+            (self.bits >> 63 != 0, self.bits & 0x7fff_ffff_ffff_ffff)
+        };
+        if sign { -(magnitude as i128) } else { magnitude as i128 }
+    }
+
+    /// Equal under `cmp`: the NaN, signed-zero and infinity policies decide
+    /// any special value first, then the comparison mode.
+    fn matches(&self, other: &FloatBits, cmp: &FloatCompare) -> bool {
+        let (a, b) = (self.value(), other.value());
+        if a.is_nan() || b.is_nan() {
+            return match cmp.nan {
+                NanPolicy::Any     => a.is_nan() && b.is_nan(),
+                NanPolicy::Payload => a.is_nan() && b.is_nan() && self.bits == other.bits,
+                NanPolicy::Never   => false,
+            };
+        }
+        if self.is_zero() && other.is_zero() {
+            return match cmp.zero {
+                ZeroPolicy::Distinct => self.bits == other.bits,
+                ZeroPolicy::Equal    => true,
+            };
+        }
+        if a.is_infinite() || b.is_infinite() {
+            return match cmp.inf {
+                InfPolicy::Strict   => a == b,
+                InfPolicy::Saturate => {
+                    a.signum() == b.signum()
+                        && (a.is_infinite() || a.abs() == self.max_finite())
+                        && (b.is_infinite() || b.abs() == other.max_finite())
+                }
+            };
+        }
+        match cmp.mode {
+            FloatMode::Exact    => self.bits == other.bits,
+            FloatMode::Ulp(max) => (self.ordinal() - other.ordinal()).unsigned_abs() <= max as u128,
+            FloatMode::Abs(eps) => (a - b).abs() <= eps,
+            FloatMode::Rel(eps) => (a - b).abs() <= eps * a.abs().max(b.abs()),
+        }
+    }
+}
+
+/// Shortest round-tripping decimal plus the exact hex-float spelling,
+/// e.g. "0.1 [0x1.999999999999ap-4]"; NaNs show their raw bits.
+impl std::fmt::Display for FloatBits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let v = self.value();
+        if v.is_nan() {
+            let width = if self.single { 10 } else { 18 };
+            return write!(f, "NaN [{:#0w$x}]", self.bits, w = width);
+        }
+        if v.is_infinite() {
+            return write!(f, "{}", if v > 0.0 { "inf" } else { "-inf" });
+        }
+        if self.single {
+            write!(f, "{:?} [{}]", v as f32, hex_float(v))
+        } else {
+            write!(f, "{:?} [{}]", v, hex_float(v))
+        }
+    }
+}
+
+/// C99 `%a`-style spelling of a finite value.
+fn hex_float(v: f64) -> String {
+    let bits     = v.to_bits();
+    let sign     = if bits >> 63 != 0 { "-" } else { "" };
+    let exponent = ((bits >> 52) & 0x7ff) as i64;
+    let mantissa = bits & ((1u64 << 52) - 1);
+    if exponent == 0 && mantissa == 0 {
+        return format!("{}0x0p+0", sign);
+    }
+    let (lead, exp) = if exponent == 0 { (0, -1022) } else { (1, exponent - 1023) };
+    let digits = format!("{:013x}", mantissa);
+    let digits = digits.trim_end_matches('0');
+    let dot    = if digits.is_empty() { "" } else { "." };
+    format!("{}0x{}{}{}p{:+}", sign, lead, dot, digits, exp)
+}
+
+/// Names and types of the "name=value" lines a runner prints, if it prints any.
+fn named_outputs(config: &AnalysisConfig) -> Option<Vec<(String, VarType)>> {
+    if let Some(layout) = &config.return_struct {
+        return Some(layout.fields.iter().map(|f| (f.c_name.clone(), f.var_type)).collect());
+    }
+    if config.outputs.is_empty() { return None; }
+    Some(config.outputs.iter().map(|o| (o.name.clone(), o.var_type)).collect())
+}
+
+/// Parse the "name=value" lines printed for struct results and outputs.
+fn parse_named_output(stdout: &str, names: &[(String, VarType)]) -> BinaryOutput {
+    let mut fields = Vec::new();
+    for (name, var_type) in names {
+        match named_value(stdout, name, var_type) {
+            Some(v) => fields.push((name.clone(), v)),
+            None    => return BinaryOutput::Error,
+        }
+    }
+    BinaryOutput::Named(fields)
+}
+
+/// The value of the first "name=value" line, parsed as `var_type`.
+fn named_value(stdout: &str, name: &str, var_type: &VarType) -> Option<BinaryOutput> {
+    let raw = stdout.lines()
+        .filter_map(|l| l.trim().split_once('='))
+        .find(|(n, _)| *n == name)
+        .map(|(_, v)| v.trim())?;
+    parse_scalar(raw, var_type)
+}
+
+/// A printed scalar of type `var_type`; u64 values above i64::MAX keep their
+/// bits, floats arrive as their hex bit pattern.
+fn parse_scalar(raw: &str, var_type: &VarType) -> Option<BinaryOutput> {
+    match var_type {
+        VarType::F32 | VarType::F64 => raw.strip_prefix("0x")
+            .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+            .map(|bits| BinaryOutput::Float(FloatBits { bits, single: *var_type == VarType::F32 })),
+        _ => raw.parse::<i64>().ok()
+            .or_else(|| raw.parse::<u64>().ok().map(|u| u as i64))
+            .map(BinaryOutput::Int),
+    }
+}
+
+/// Parse the "ok=value" / "ok" / "err=detail" line printed under an error map.
+fn parse_fallible_output(stdout: &str, map: &ErrorMap) -> BinaryOutput {
+    for line in stdout.lines().map(str::trim) {
+        if let Some(detail) = line.strip_prefix("err=") {
+            return BinaryOutput::Failed(detail.to_string());
+        }
+        if line == "ok" && map.ok_type.is_none() {
+            return BinaryOutput::Void;
+        }
+    }
+    map.ok_type.as_ref()
+        .and_then(|ty| named_value(stdout, "ok", ty))
+        .unwrap_or(BinaryOutput::Error)
 }
 
 // ── Runner ────────────────────────────────────────────────────────────────────
@@ -192,36 +796,53 @@ fn run_binary(
     inputs: &[(String, i64)],
     config: &AnalysisConfig,
 ) -> BinaryOutput {
-    // Pass args in declaration order (must match how the runner was generated)
-    let str_args: Vec<String> = config.bounds.iter().map(|b| {
-        inputs.iter()
-            .find(|(n, _)| n == &b.name)
-            .map(|(_, v)| v.to_string())
-            .unwrap_or_else(|| "0".into())
-    }).collect();
-
-    let out = match Command::new(bin).args(&str_args).output() {
-        Ok(o)  => o,
-        Err(e) => {
+    match Command::new(bin).args(runner_args(inputs, config)).output() {
+        Ok(out) => parse_runner_output(&out, config),
+        Err(e)  => {
             eprintln!("    [runner] exec failed {}: {}", bin, e);
-            return BinaryOutput::Error;
+            BinaryOutput::Error
         }
-    };
+    }
+}
+
+/// Runner arguments in declaration order (must match how the runner was generated).
+fn runner_args(inputs: &[(String, i64)], config: &AnalysisConfig) -> Vec<String> {
+    config.slots().iter().map(|b| {
+        let v = inputs.iter()
+            .find(|(n, _)| n == &b.name)
+            .map(|(_, v)| *v)
+            .unwrap_or(b.min);
+        b.var_type.format_value(v)
+    }).collect()
+}
 
-    // C runner:    printf("%d\n", r)
-    // Rust runner: println!("{}", r)
+fn parse_runner_output(out: &Output, config: &AnalysisConfig) -> BinaryOutput {
+    // Both runners print the result last, at its declared width:
+    // a plain value, "void", or "name=value" / "ok=value" / "err=..." lines
     let stdout = String::from_utf8_lossy(&out.stdout);
-    for line in stdout.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() { continue; }
-        if let Ok(n) = trimmed.parse::<i64>() { return BinaryOutput::Int(n); }
-        if trimmed == "true"  { return BinaryOutput::Int(1); }
-        if trimmed == "false" { return BinaryOutput::Int(0); }
+    if let Some(map) = &config.error_map {
+        return parse_fallible_output(&stdout, map);
+    }
+    if let Some(names) = named_outputs(config) {
+        return parse_named_output(&stdout, &names);
+    }
+    let last = stdout.lines().map(str::trim).rev().find(|l| !l.is_empty());
+    let kind = config.return_kind();
+    let parsed = match (&kind, last, config.return_type) {
+        (ReturnKind::Void, Some("void"), _) => Some(BinaryOutput::Void),
+        (_, Some(line), Some(ty))          => parse_scalar(line, &ty),
+        _                                  => None,
+    };
+    if let Some(v) = parsed {
+        return v;
     }
 
-    // Last resort: exit code (unreliable for negative/large values)
-    if let Some(code) = out.status.code() {
-        return BinaryOutput::Int(code as i64);
+    // Last resort for integer results: the exit code of a runner that died
+    // before printing (e.g. a Rust panic), unreliable for negative/large values
+    if matches!(kind, ReturnKind::Integer | ReturnKind::Bool) {
+        if let Some(code) = out.status.code() {
+            return BinaryOutput::Int(code as i64);
+        }
     }
     BinaryOutput::Error
 }
@@ -229,15 +850,91 @@ fn run_binary(
 // ── Input generators ──────────────────────────────────────────────────────────
 
 fn clamp_to_bounds(witness: &[(String, i64)], config: &AnalysisConfig) -> Vec<(String, i64)> {
-    config.bounds.iter().map(|b| {
+    config.slots().iter().map(|b| {
         let v = witness.iter()
             .find(|(n, _)| n == &b.name)
-            .map(|(_, v)| *v)
+            .map(|(_, v)| b.var_type.normalize_raw(*v))
             .unwrap_or(b.min);
-        (b.name.clone(), v.max(b.min).min(b.max))
+        (b.name.clone(), b.clamp(v))
     }).collect()
 }
 
+/// One display string per parameter; arrays print their first `len` elements.
+fn format_input_strings(input: &[(String, i64)], config: &AnalysisConfig) -> Vec<(String, String)> {
+    let value = |name: &str| input.iter().find(|(n, _)| n == name).map(|(_, v)| *v);
+    config.bounds.iter().map(|b| {
+        let shown = match b.kind {
+            InputKind::Scalar => b.var_type.format_value(value(&b.name).unwrap_or(b.min)),
+            InputKind::Array { max_len } => {
+                let len = value(&b.len_name()).unwrap_or(0).clamp(0, max_len as i64) as usize;
+                let elems: Vec<String> = (0..len).map(|i| {
+                    value(&format!("{}[{}]", b.name, i))
+                        .map(|v| b.var_type.format_value(v))
+                        .unwrap_or_else(|| "?".into())
+                }).collect();
+                format!("[{}]", elems.join(", "))
+            }
+            InputKind::Str { rust: RustStr::Utf8, .. } => {
+                format!("{:?}", String::from_utf8_lossy(&string_bytes(input, b)))
+            }
+            InputKind::Struct { ref layout, ref fields } => {
+                let shown: Vec<String> = layout.fields.iter().zip(fields).map(|(f, fb)| {
+                    let v = value(&fb.name).unwrap_or(fb.min);
+                    format!("{}: {}", f.c_name, fb.var_type.format_value(v))
+                }).collect();
+                format!("{{ {} }}", shown.join(", "))
+            }
+            InputKind::Str { .. } => {
+                let bytes = string_bytes(input, b);
+                let escaped: String = bytes.iter()
+                    .flat_map(|&c| std::ascii::escape_default(c))
+                    .map(char::from)
+                    .collect();
+                format!("\"{}\"", escaped)
+            }
+        };
+        (b.name.clone(), shown)
+    }).collect()
+}
+
+/// A string input's bytes up to (not including) its terminator.
+fn string_bytes(input: &[(String, i64)], b: &InputBound) -> Vec<u8> {
+    let InputKind::Str { max_len, .. } = b.kind else { return Vec::new() };
+    (0..max_len)
+        .map(|i| input.iter()
+            .find(|(n, _)| *n == format!("{}[{}]", b.name, i))
+            .map_or(0, |(_, v)| *v as u8))
+        .take_while(|&c| c != 0)
+        .collect()
+}
+
+/// Zero every string byte after the first terminator.
+fn truncate_strings(mut input: Vec<(String, i64)>, config: &AnalysisConfig) -> Vec<(String, i64)> {
+    for b in &config.bounds {
+        if let InputKind::Str { max_len, .. } = b.kind {
+            let len = string_bytes(&input, b).len();
+            for i in len..max_len {
+                let name = format!("{}[{}]", b.name, i);
+                if let Some(slot) = input.iter_mut().find(|(n, _)| *n == name) { slot.1 = 0; }
+            }
+        }
+    }
+    input
+}
+
+/// Would the harnesses accept these string bytes? Non-terminator bytes must
+/// lie in the bound's range, and `&str` inputs checked as UTF-8 must be well-formed.
+fn strings_admissible(input: &[(String, i64)], config: &AnalysisConfig) -> bool {
+    config.bounds.iter().all(|b| match b.kind {
+        InputKind::Str { rust, .. } => {
+            let bytes = string_bytes(input, b);
+            bytes.iter().all(|&c| (c as i64) >= b.min && (c as i64) <= b.max)
+                && (rust != RustStr::Utf8 || std::str::from_utf8(&bytes).is_ok())
+        }
+        _ => true,
+    })
+}
+
 fn push_unique(list: &mut Vec<Vec<(String, i64)>>, input: Vec<(String, i64)>) {
     if !list.contains(&input) { list.push(input); }
 }
@@ -245,26 +942,11 @@ fn push_unique(list: &mut Vec<Vec<(String, i64)>>, input: Vec<(String, i64)>) {
 /// Cartesian product of candidate values for each variable (capped at 500).
 /// Candidates per variable: min, min+1, q1, mid, q3, max-1, max, 0, 1, -1
 fn generate_boundary_inputs(config: &AnalysisConfig) -> Vec<Vec<(String, i64)>> {
-    let candidates: Vec<Vec<i64>> = config.bounds.iter().map(|b| {
-        let mut vals = vec![b.min, b.max];
-        let mid = b.min + (b.max - b.min) / 2;
-        let q1  = b.min + (b.max - b.min) / 4;
-        let q3  = b.min + 3 * (b.max - b.min) / 4;
-        vals.push(mid);
-        vals.push(q1);
-        vals.push(q3);
-        if b.min + 1 <= b.max { vals.push(b.min + 1); }
-        if b.max - 1 >= b.min { vals.push(b.max - 1); }
-        for v in [0i64, 1, -1] {
-            if v >= b.min && v <= b.max { vals.push(v); }
-        }
-        vals.sort();
-        vals.dedup();
-        vals
-    }).collect();
+    let slots = config.slots();
+    let candidates: Vec<Vec<i64>> = slots.iter().map(boundary_candidates).collect();
 
     let mut result: Vec<Vec<(String, i64)>> = vec![vec![]];
-    for (b, vals) in config.bounds.iter().zip(candidates.iter()) {
+    for (b, vals) in slots.iter().zip(candidates.iter()) {
         let mut next = Vec::new();
         'outer: for prefix in &result {
             for &v in vals {
@@ -279,267 +961,978 @@ fn generate_boundary_inputs(config: &AnalysisConfig) -> Vec<Vec<(String, i64)>>
     result
 }
 
-// ── Z3 solver ─────────────────────────────────────────────────────────────────
+/// Boundary candidates for one variable, computed in the declared type's
+/// value space and returned encoded.
+fn boundary_candidates(b: &InputBound) -> Vec<i64> {
+    let ty = &b.var_type;
+    let mut vals: Vec<i64> = if ty.is_float() {
+        let (lo, hi) = (ty.decode_f64(b.min), ty.decode_f64(b.max));
+        let span = hi - lo;
+        let mut fs = vec![lo, hi, lo + span / 2.0, lo + span / 4.0, lo + 3.0 * span / 4.0];
+        for v in [0.0f64, 1.0, -1.0] {
+            if v >= lo && v <= hi { fs.push(v); }
+        }
+        fs.into_iter().map(|f| ty.encode_f64(f)).collect()
+    } else {
+        let (lo, hi) = (ty.decode(b.min), ty.decode(b.max));
+        let span = hi - lo;
+        let mut is = vec![lo, hi, lo + span / 2, lo + span / 4, lo + 3 * span / 4];
+        if lo < hi { is.push(lo + 1); is.push(hi - 1); }
+        for v in [0i128, 1, -1] {
+            if v >= lo && v <= hi { is.push(v); }
+        }
+        is.into_iter().map(|v| ty.encode(v)).collect()
+    };
+    vals.sort_by(|a, c| ty.cmp_values(*a, *c));
+    vals.dedup();
+    vals
+}
 
-fn solve_single_input(
-    ctx:         &Context,
-    config:      &AnalysisConfig,
-    constraints: &[String],
-    labels:      &HashMap<String, String>,
-    exclude:     &[Vec<(String, i64)>],
-) -> Option<Vec<(String, i64)>> {
-    let solver = Solver::new(ctx);
-    let mut int_vars: HashMap<String, Int> = HashMap::new();
+// ── Random sampling ───────────────────────────────────────────────────────────
 
-    for b in &config.bounds {
-        let v = Int::new_const(ctx, b.name.clone());
-        solver.assert(&v.ge(&Int::from_i64(ctx, b.min)));
-        solver.assert(&v.le(&Int::from_i64(ctx, b.max)));
-        int_vars.insert(b.name.clone(), v);
+/// A seed for runs without `--seed`; reported so the run can be repeated.
+/// Kept to 53 bits so it survives the round trip through JSON in the web UI.
+fn fresh_seed() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0)
+        & ((1 << 53) - 1)
+}
+
+/// `sampling.count` random inputs drawn from the configured distribution.
+fn random_inputs(config: &AnalysisConfig, rng: &mut StdRng) -> Vec<Vec<(String, i64)>> {
+    let slots = config.slots();
+    (0..config.sampling.count).map(|_| {
+        slots.iter()
+            .map(|b| (b.name.clone(), random_value(b, config.sampling.distribution, rng)))
+            .collect()
+    }).collect()
+}
+
+/// One encoded value of slot `b`, within its bounds.
+fn random_value(b: &InputBound, dist: Distribution, rng: &mut StdRng) -> i64 {
+    let ty = &b.var_type;
+    if ty.is_float() {
+        let (lo, hi) = (ty.decode_f64(b.min), ty.decode_f64(b.max));
+        if lo >= hi {
+            return b.min;
+        }
+        let f = match dist {
+            Distribution::EdgeBiased if rng.gen_bool(0.5) => {
+                [lo, hi, 0.0f64.clamp(lo, hi)][rng.gen_range(0..3)]
+            }
+            Distribution::LogScale => {
+                let top = lo.abs().max(hi.abs()).log2().max(-20.0);
+                let mag = 2f64.powf(rng.gen_range(-20.0..=top));
+                let neg = lo < 0.0 && (hi <= 0.0 || rng.gen_bool(0.5));
+                (if neg { -mag } else { mag }).clamp(lo, hi)
+            }
+            _ => rng.gen_range(lo..=hi),
+        };
+        return ty.encode_f64(f);
+    }
+    let (lo, hi) = (ty.decode(b.min), ty.decode(b.max));
+    let v = match dist {
+        // Within a few steps of either end or zero
+        Distribution::EdgeBiased if rng.gen_bool(0.5) => {
+            let anchor = [lo, hi, 0i128.clamp(lo, hi)][rng.gen_range(0..3)];
+            (anchor + rng.gen_range(-8..=8)).clamp(lo, hi)
+        }
+        // Sign, then bit length uniform up to that side's bound, then a
+        // magnitude of that length
+        Distribution::LogScale => {
+            let neg = lo < 0 && (hi <= 0 || rng.gen_bool(0.5));
+            let end = if neg { lo } else { hi };
+            let bits = 128 - end.unsigned_abs().leading_zeros();
+            let mag = match rng.gen_range(0..=bits) {
+                0 => 0,
+                k => rng.gen_range(1u128 << (k - 1)..=(1u128 << (k - 1)) * 2 - 1) as i128,
+            };
+            (if neg { -mag } else { mag }).clamp(lo, hi)
+        }
+        _ => rng.gen_range(lo..=hi),
+    };
+    ty.encode(v)
+}
+
+// ── Fuzzing ───────────────────────────────────────────────────────────────────
+
+/// Mutate inputs from the corpus until the time budget runs out: one or two
+/// slots at a time, moved next to a branch constant from the KLEE
+/// constraints, nudged, or redrawn. Returns the inputs run and the first
+/// divergence, or every divergence in collect-all mode.
+fn fuzz(
+    config:    &AnalysisConfig,
+    ir_files:  &IrFiles,
+    corpus:    &[Vec<(String, i64)>],
+    constants: &[i128],
+    rng:       &mut StdRng,
+    stats:     &mut CheckerStatistics,
+) -> (u32, Vec<Divergence>) {
+    let slots = config.slots();
+    let mut found = Vec::new();
+    if config.sampling.fuzz_secs == 0 || corpus.is_empty() || slots.is_empty() {
+        return (0, found);
     }
+    println!("\n  -- Fuzzing ({}s, {} branch constant(s)) --", config.sampling.fuzz_secs, constants.len());
+    let deadline = Instant::now() + Duration::from_secs(config.sampling.fuzz_secs);
+    let mut tried: HashSet<Vec<(String, i64)>> = corpus.iter().cloned().collect();
+    let mut tested = 0u32;
+
+    while Instant::now() < deadline {
+        let mut input = corpus[rng.gen_range(0..corpus.len())].clone();
+        for _ in 0..rng.gen_range(1..=2) {
+            let b = &slots[rng.gen_range(0..slots.len())];
+            let v = input.iter().find(|(n, _)| n == &b.name).map_or(b.min, |(_, v)| *v);
+            let next = mutate(b, v, constants, rng);
+            match input.iter_mut().find(|(n, _)| n == &b.name) {
+                Some(slot) => slot.1 = next,
+                None       => input.push((b.name.clone(), next)),
+            }
+        }
+        if !strings_admissible(&input, config) {
+            continue;
+        }
+        let input = truncate_strings(input, config);
+        if !tried.insert(input.clone()) {
+            continue;
+        }
 
-    for c in constraints {
-        if let Some(b) = parse_klee_bool(ctx, &int_vars, labels, c) {
-            solver.assert(&b);
+        let c_out    = run_binary(&ir_files.c_runner_bin,    &input, config);
+        let rust_out = run_binary(&ir_files.rust_runner_bin, &input, config);
+        if matches!(c_out, BinaryOutput::Error) || matches!(rust_out, BinaryOutput::Error) {
+            continue;
+        }
+        tested += 1;
+        stats.merged_pairs += 1;
+        if c_out.differs_from(&rust_out, &config.float_compare) {
+            println!("       ✗ {:?}: C={}  Rust={}", input, c_out.to_string_repr(), rust_out.to_string_repr());
+            found.push((input, c_out, rust_out));
+            if !config.collect_all {
+                return (tested, found);
+            }
         }
     }
+    println!("     {} fuzzed inputs, {} divergent", tested, found.len());
+    (tested, found)
+}
 
-    for prev in exclude {
-        let clauses: Vec<Bool> = prev.iter().filter_map(|(name, val)| {
-            int_vars.get(name).map(|v| v._eq(&Int::from_i64(ctx, *val)).not())
-        }).collect();
-        if !clauses.is_empty() {
-            let refs: Vec<&Bool> = clauses.iter().collect();
-            solver.assert(&Bool::or(ctx, &refs));
+/// A new encoded value for slot `b`, kept within its bounds.
+fn mutate(b: &InputBound, v: i64, constants: &[i128], rng: &mut StdRng) -> i64 {
+    let ty = &b.var_type;
+    if ty.is_float() {
+        return random_value(b, Distribution::EdgeBiased, rng);
+    }
+    let (lo, hi) = (ty.decode(b.min), ty.decode(b.max));
+    let next = match rng.gen_range(0..4) {
+        // Either side of a branch boundary
+        0 if !constants.is_empty() => constants[rng.gen_range(0..constants.len())] + rng.gen_range(-1..=1),
+        1 => ty.decode(v) + rng.gen_range(-8..=8),
+        2 => ty.decode(random_value(b, Distribution::EdgeBiased, rng)),
+        _ => ty.decode(random_value(b, Distribution::Uniform, rng)),
+    };
+    ty.encode(next.clamp(lo, hi))
+}
+
+// ── Counterexample shrinking ──────────────────────────────────────────────────
+
+/// Runner pairs greedy shrinking may spend on one counterexample.
+const MAX_SHRINK_RUNS: u32 = 256;
+
+/// Shrink a divergence to the simplest input on which the runners still
+/// disagree: Z3 minimisation over the path pair the input follows, then
+/// greedy shrinking. Returns the counterexample, carrying the original when
+/// shrinking changed it, and the path pair the final input follows.
+fn shrink_divergence<'ctx>(
+    ctx:       &'ctx Context,
+    encoder:   &KleeEncoder<'ctx>,
+    config:    &AnalysisConfig,
+    ir_files:  &IrFiles,
+    summaries: &SymbolicSummaries,
+    found:     Divergence,
+    stats:     &mut CheckerStatistics,
+) -> (Counterexample, Option<(usize, usize)>) {
+    println!("\n  -- Shrinking Counterexample --");
+    let (c_paths, rust_paths) = (&summaries.c_summaries, &summaries.rust_summaries);
+    let original = counterexample(config, &found.0, &found.1, &found.2);
+    let mut best = found;
+
+    let pair = path_of(ctx, encoder, c_paths, &best.0, stats)
+        .zip(path_of(ctx, encoder, rust_paths, &best.0, stats));
+    if let Some((i, j)) = pair {
+        if let Some(input) = z3_shrink(ctx, encoder, config, &c_paths[i], &rust_paths[j], stats) {
+            println!("     Z3 on {} × {}: {:?}", c_paths[i].id, rust_paths[j].id, input);
+            if complexity(&input, config) < complexity(&best.0, config) {
+                if let Some((c_out, rust_out)) = diverges(config, ir_files, &input) {
+                    best = (input, c_out, rust_out);
+                }
+            }
         }
     }
 
-    if solver.check() != SatResult::Sat { return None; }
-    let model = solver.get_model()?;
+    let mut runs = 0u32;
+    best = greedy_shrink(config, ir_files, best, &mut runs);
 
-    let inputs: Vec<(String, i64)> = config.bounds.iter().filter_map(|b| {
-        let v = Int::new_const(ctx, b.name.clone());
-        model.eval(&v, true)?.as_i64().map(|val| (b.name.clone(), val))
-    }).collect();
+    if best.0 == original.inputs {
+        println!("     Already minimal ({} runner pair(s) tried)", runs);
+        return (original, pair);
+    }
+    println!("     ✓ {:?} → {:?} ({} runner pair(s) tried)", original.inputs, best.0, runs);
+    println!("       C={}  Rust={}", best.1.to_string_repr(), best.2.to_string_repr());
+    let pair = path_of(ctx, encoder, c_paths, &best.0, stats)
+        .zip(path_of(ctx, encoder, rust_paths, &best.0, stats));
+    let mut ce = counterexample(config, &best.0, &best.1, &best.2);
+    ce.original = Some(Box::new(original));
+    (ce, pair)
+}
 
-    if inputs.is_empty() { None } else { Some(inputs) }
+/// Index of the path whose constraints `input` satisfies. Paths with
+/// constraints the encoder could not translate are skipped.
+fn path_of<'ctx>(
+    ctx:     &'ctx Context,
+    encoder: &KleeEncoder<'ctx>,
+    paths:   &[PathSummary],
+    input:   &[(String, i64)],
+    stats:   &mut CheckerStatistics,
+) -> Option<usize> {
+    let pinned = encoder.equals(input)?;
+    paths.iter().position(|ps| {
+        let path = encoder.encode_path(&ps.constraints, None);
+        if !path.unsupported.is_empty() {
+            return false;
+        }
+        let solver = Solver::new(ctx);
+        for c in &path.constraints {
+            solver.assert(c);
+        }
+        solver.assert(&pinned);
+        timed_check(&solver, stats) == SatResult::Sat
+    })
 }
 
-// ── Witness distance ──────────────────────────────────────────────────────────
+/// Z3 half of shrinking: on one path pair, pin as many slots to zero as
+/// stay satisfiable, then binary-search the smallest bound on every slot's
+/// magnitude.
+fn z3_shrink<'ctx>(
+    ctx:     &'ctx Context,
+    encoder: &KleeEncoder<'ctx>,
+    config:  &AnalysisConfig,
+    c:       &PathSummary,
+    rust:    &PathSummary,
+    stats:   &mut CheckerStatistics,
+) -> Option<Vec<(String, i64)>> {
+    let cp = encoder.encode_path(&c.constraints, c.return_expr.as_deref());
+    let rp = encoder.encode_path(&rust.constraints, rust.return_expr.as_deref());
+    if !cp.unsupported.is_empty() || !rp.unsupported.is_empty() {
+        return None;
+    }
+    let solver = Solver::new(ctx);
+    let mut params = Params::new(ctx);
+    params.set_u32("timeout", config.timeout.saturating_mul(1000));
+    solver.set_params(&params);
+    for b in encoder.bounds().iter().chain(&cp.constraints).chain(&rp.constraints) {
+        solver.assert(b);
+    }
+    // Keep the results apart when both paths bind them
+    let single_int = config.result_type().is_some_and(|t| !t.is_float()) && config.outputs.len() <= 1;
+    if let (true, Some(c_ret), Some(rust_ret)) = (single_int, &cp.ret, &rp.ret) {
+        solver.assert(&encoder.returns_differ(c_ret, rust_ret));
+    }
 
-fn witness_dist(a: &[(String, i64)], b: &[(String, i64)]) -> i64 {
-    let am: HashMap<&str, i64> = a.iter().map(|(k, v)| (k.as_str(), *v)).collect();
-    let bm: HashMap<&str, i64> = b.iter().map(|(k, v)| (k.as_str(), *v)).collect();
-    am.iter().map(|(k, va)| bm.get(k).map(|vb| (va - vb).abs()).unwrap_or(1000)).sum()
-}
-
-// ── KLEE SMT parsers ──────────────────────────────────────────────────────────
-
-fn parse_klee_bool<'ctx>(
-    ctx:    &'ctx Context,
-    vars:   &HashMap<String, Int<'ctx>>,
-    labels: &HashMap<String, String>,
-    s:      &str,
-) -> Option<Bool<'ctx>> {
-    let s = s.trim();
-    if s == "true"  { return Some(Bool::from_bool(ctx, true));  }
-    if s == "false" { return Some(Bool::from_bool(ctx, false)); }
-
-    if s.starts_with("(Extract ") {
-        let inner = s["(Extract ".len()..s.len()-1].trim();
-        if let Some(rest) = inner.splitn(2, char::is_whitespace).nth(1) {
-            let rest = rest.trim();
-            let expr = if rest.starts_with("(ZExt ") || rest.starts_with("(SExt ") {
-                rest[6..rest.len()-1].trim()
-                    .splitn(2, char::is_whitespace).nth(1).unwrap_or("").trim()
-            } else { rest };
-            if !expr.is_empty() { return parse_klee_bool(ctx, vars, labels, expr); }
-        }
-    }
-    if s.starts_with("(Eq false ") {
-        return parse_klee_bool(ctx, vars, labels, s["(Eq false ".len()..s.len()-1].trim())
-            .map(|b| b.not());
-    }
-    if s.starts_with("(Eq true ") {
-        return parse_klee_bool(ctx, vars, labels, s["(Eq true ".len()..s.len()-1].trim());
-    }
-
-    macro_rules! cmp {
-        ($pfx:expr, $op:ident) => {
-            if s.starts_with($pfx) {
-                let args = s[$pfx.len()..s.len()-1].trim();
-                if let Some((l, r)) = split_two(args) {
-                    if let (Some(lv), Some(rv)) = (
-                        parse_klee_int(ctx, vars, labels, l),
-                        parse_klee_int(ctx, vars, labels, r),
-                    ) { return Some(lv.$op(&rv)); }
-                }
+    let model_input = |solver: &Solver<'ctx>| solver.get_model()
+        .and_then(|m| encoder.inputs(&m))
+        .map(|inp| truncate_strings(inp, config));
+    if timed_check(&solver, stats) != SatResult::Sat {
+        return None;
+    }
+    let mut best = model_input(&solver)?;
+
+    // Fewest nonzero slots: each pin stays asserted while satisfiable
+    for b in config.slots() {
+        if best.iter().any(|(n, v)| n == &b.name && *v == 0) {
+            continue;
+        }
+        let Some(zero) = encoder.is_zero(&b.name) else { continue };
+        solver.push();
+        solver.assert(&zero);
+        match timed_check(&solver, stats) {
+            SatResult::Sat => best = model_input(&solver).unwrap_or(best),
+            _              => solver.pop(1),
+        }
+    }
+
+    // Smallest magnitude bound over the remaining slots
+    let (mut lo, mut hi) = (0u64, magnitude(&best, config));
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        solver.push();
+        for c in encoder.magnitude_at_most(mid) {
+            solver.assert(&c);
+        }
+        match timed_check(&solver, stats) {
+            SatResult::Sat => {
+                best = model_input(&solver).unwrap_or(best);
+                hi = mid;
             }
-        };
+            _ => lo = mid + 1,
+        }
+        solver.pop(1);
     }
-    cmp!("(Slt ", lt); cmp!("(Sle ", le); cmp!("(Sgt ", gt); cmp!("(Sge ", ge);
-    cmp!("(Ult ", lt); cmp!("(Ule ", le); cmp!("(Ugt ", gt); cmp!("(Uge ", ge);
-
-    if s.starts_with("(Eq ") {
-        let args = s["(Eq ".len()..s.len()-1].trim();
-        if let Some((l, r)) = split_two(args) {
-            if let (Some(lv), Some(rv)) = (
-                parse_klee_int(ctx, vars, labels, l),
-                parse_klee_int(ctx, vars, labels, r),
-            ) { return Some(lv._eq(&rv)); }
-        }
-    }
-    if s.starts_with("(Not ") {
-        return parse_klee_bool(ctx, vars, labels, s["(Not ".len()..s.len()-1].trim())
-            .map(|b| b.not());
-    }
-    if s.starts_with("(And ") {
-        let args = s["(And ".len()..s.len()-1].trim();
-        if let Some((l, r)) = split_two(args) {
-            if let (Some(lv), Some(rv)) = (
-                parse_klee_bool(ctx, vars, labels, l),
-                parse_klee_bool(ctx, vars, labels, r),
-            ) { return Some(Bool::and(ctx, &[&lv, &rv])); }
-        }
-    }
-    if s.starts_with("(Or ") {
-        let args = s["(Or ".len()..s.len()-1].trim();
-        if let Some((l, r)) = split_two(args) {
-            if let (Some(lv), Some(rv)) = (
-                parse_klee_bool(ctx, vars, labels, l),
-                parse_klee_bool(ctx, vars, labels, r),
-            ) { return Some(Bool::or(ctx, &[&lv, &rv])); }
-        }
-    }
-    None
-}
-
-fn parse_klee_int<'ctx>(
-    ctx:    &'ctx Context,
-    vars:   &HashMap<String, Int<'ctx>>,
-    labels: &HashMap<String, String>,
-    s:      &str,
-) -> Option<Int<'ctx>> {
-    let s = s.trim();
-    if s.is_empty() { return None; }
-    if let Some(pos) = find_label_colon(s) {
-        return parse_klee_int(ctx, vars, labels, &s[pos + 1..]);
-    }
-    if is_bare_label(s) {
-        if let Some(vname) = labels.get(s) { return vars.get(vname).cloned(); }
-        if let Some(v)     = vars.get(s)   { return Some(v.clone()); }
-    }
-    if s.starts_with("(ReadLSB ") || s.starts_with("(ReadMSB ") {
-        let inner = s[9..s.len()-1].trim();
-        if let Some(&name) = inner.split_whitespace().collect::<Vec<_>>().last() {
-            if let Some(vname) = labels.get(name) { return vars.get(vname).cloned(); }
-            return vars.get(name).cloned();
-        }
-    }
-    if s.starts_with("(w32 ") || s.starts_with("(w64 ") {
-        let n = s[5..s.len()-1].trim();
-        return n.parse::<i64>().ok()
-            .or_else(|| n.parse::<u64>().ok().map(|v| v as i64))
-            .map(|v| Int::from_i64(ctx, v));
-    }
-    macro_rules! arith {
-        ($pfx:expr, $fn:path) => {
-            if s.starts_with($pfx) {
-                let args = s[$pfx.len()..s.len()-1].trim();
-                if let Some((l, r)) = split_two(args) {
-                    if let (Some(lv), Some(rv)) = (
-                        parse_klee_int(ctx, vars, labels, l),
-                        parse_klee_int(ctx, vars, labels, r),
-                    ) { return Some($fn(ctx, &[&lv, &rv])); }
+    Some(best)
+}
+
+/// Greedy half of shrinking: move one slot at a time toward zero (or the
+/// bound nearest zero), binary-searching the closest value on which the
+/// runners still disagree. Repeats until a pass changes nothing or the
+/// runner budget is spent.
+fn greedy_shrink(
+    config:   &AnalysisConfig,
+    ir_files: &IrFiles,
+    found:    Divergence,
+    runs:     &mut u32,
+) -> Divergence {
+    let mut best = found;
+    let try_input = |input: Vec<(String, i64)>, runs: &mut u32| -> Option<Divergence> {
+        if *runs >= MAX_SHRINK_RUNS || !strings_admissible(&input, config) {
+            return None;
+        }
+        *runs += 1;
+        let input = truncate_strings(input, config);
+        diverges(config, ir_files, &input).map(|(c_out, rust_out)| (input, c_out, rust_out))
+    };
+    let with = |input: &[(String, i64)], name: &str, v: i64| -> Vec<(String, i64)> {
+        input.iter().map(|(n, old)| (n.clone(), if n == name { v } else { *old })).collect()
+    };
+
+    let mut changed = true;
+    while changed && *runs < MAX_SHRINK_RUNS {
+        changed = false;
+        for b in config.slots() {
+            let Some(&(_, v)) = best.0.iter().find(|(n, _)| n == &b.name) else { continue };
+            let ty = &b.var_type;
+            if ty.is_float() {
+                let f = ty.decode_f64(v);
+                let origin = 0f64.clamp(ty.decode_f64(b.min), ty.decode_f64(b.max));
+                for target in [origin, f.trunc()] {
+                    let enc = ty.encode_f64(target);
+                    if enc == v || target == f { continue; }
+                    if let Some(next) = try_input(with(&best.0, &b.name, enc), runs) {
+                        best = next;
+                        changed = true;
+                        break;
+                    }
                 }
+                continue;
             }
-        };
-    }
-    arith!("(Add ", Int::add); arith!("(Sub ", Int::sub); arith!("(Mul ", Int::mul);
-    macro_rules! divrem {
-        ($pfx:expr, $m:ident) => {
-            if s.starts_with($pfx) {
-                let args = s[$pfx.len()..s.len()-1].trim();
-                if let Some((l, r)) = split_two(args) {
-                    if let (Some(lv), Some(rv)) = (
-                        parse_klee_int(ctx, vars, labels, l),
-                        parse_klee_int(ctx, vars, labels, r),
-                    ) { return Some(lv.$m(&rv)); }
+            let origin = 0i128.clamp(ty.decode(b.min), ty.decode(b.max));
+            let x = ty.decode(v);
+            if x == origin { continue; }
+            if let Some(next) = try_input(with(&best.0, &b.name, ty.encode(origin)), runs) {
+                best = next;
+                changed = true;
+                continue;
+            }
+            // `near` is known to agree, `far` to diverge
+            let (mut near, mut far) = (origin, x);
+            while (far - near).abs() > 1 {
+                let mid = near + (far - near) / 2;
+                match try_input(with(&best.0, &b.name, ty.encode(mid)), runs) {
+                    Some(next) => {
+                        best = next;
+                        far = mid;
+                        changed = true;
+                    }
+                    None if *runs >= MAX_SHRINK_RUNS => break,
+                    None => near = mid,
                 }
             }
-        };
+        }
     }
-    divrem!("(SDiv ", div); divrem!("(SRem ", rem);
-    divrem!("(UDiv ", div); divrem!("(URem ", rem);
-    if s.starts_with("(Select ") {
-        let inner = s["(Select ".len()..s.len()-1].trim();
-        if let Some((cs, rest)) = split_two(inner) {
-            if let Some((ts, fs)) = split_two(rest) {
-                if let (Some(c), Some(tv), Some(fv)) = (
-                    parse_klee_bool(ctx, vars, labels, cs),
-                    parse_klee_int(ctx, vars, labels, ts),
-                    parse_klee_int(ctx, vars, labels, fs),
-                ) { return Some(c.ite(&tv, &fv)); }
-            }
+    best
+}
+
+/// Both runners' outputs when they succeed and disagree on `input`.
+fn diverges(
+    config:   &AnalysisConfig,
+    ir_files: &IrFiles,
+    input:    &[(String, i64)],
+) -> Option<(BinaryOutput, BinaryOutput)> {
+    let c_out    = run_binary(&ir_files.c_runner_bin,    input, config);
+    let rust_out = run_binary(&ir_files.rust_runner_bin, input, config);
+    let differ = !matches!(c_out, BinaryOutput::Error)
+        && !matches!(rust_out, BinaryOutput::Error)
+        && c_out.differs_from(&rust_out, &config.float_compare);
+    differ.then_some((c_out, rust_out))
+}
+
+/// Largest distance from zero over the integer slots.
+fn magnitude(input: &[(String, i64)], config: &AnalysisConfig) -> u64 {
+    config.slots().iter()
+        .filter(|b| !b.var_type.is_float())
+        .filter_map(|b| input.iter().find(|(n, _)| n == &b.name)
+            .map(|(_, v)| b.var_type.decode(*v).unsigned_abs().min(u64::MAX as u128) as u64))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Shrinking order: fewer nonzero slots first, then smaller total magnitude.
+fn complexity(input: &[(String, i64)], config: &AnalysisConfig) -> (usize, u128) {
+    let sizes: Vec<u128> = config.slots().iter().filter_map(|b| {
+        let (_, v) = input.iter().find(|(n, _)| n == &b.name)?;
+        Some(if b.var_type.is_float() {
+            b.var_type.decode_f64(*v).abs().min(u128::MAX as f64) as u128
+                + (b.var_type.decode_f64(*v) != 0.0) as u128
+        } else {
+            b.var_type.decode(*v).unsigned_abs()
+        })
+    }).collect();
+    (sizes.iter().filter(|&&s| s != 0).count(), sizes.iter().sum())
+}
+
+// ── Divergence clusters ───────────────────────────────────────────────────────
+
+/// The (C path, Rust path) indices an input follows, when Z3 placed it.
+type PathPair = Option<(usize, usize)>;
+
+/// Group collect-all divergences by the (C path, Rust path) pair they
+/// follow, largest group first. Each cluster gets a shrunk representative
+/// and Z3's description of the region where the pair's results differ.
+fn cluster_divergences<'ctx>(
+    ctx:         &'ctx Context,
+    encoder:     &KleeEncoder<'ctx>,
+    config:      &AnalysisConfig,
+    ir_files:    &IrFiles,
+    summaries:   &SymbolicSummaries,
+    divergences: Vec<Divergence>,
+    stats:       &mut CheckerStatistics,
+) -> Vec<(PathPair, DivergenceCluster)> {
+    let (c_paths, rust_paths) = (&summaries.c_summaries, &summaries.rust_summaries);
+    let mut groups: Vec<(PathPair, Vec<Divergence>)> = Vec::new();
+    for d in divergences {
+        let pair = path_of(ctx, encoder, c_paths, &d.0, stats)
+            .zip(path_of(ctx, encoder, rust_paths, &d.0, stats));
+        match groups.iter_mut().find(|(p, _)| *p == pair) {
+            Some((_, members)) => members.push(d),
+            None               => groups.push((pair, vec![d])),
         }
     }
-    for pfx in &["(SExt ", "(ZExt ", "(Trunc "] {
-        if s.starts_with(pfx) {
-            let inner = s[pfx.len()..s.len()-1].trim();
-            let body = inner.splitn(2, char::is_whitespace).nth(1)
-                .unwrap_or(inner).trim();
-            return parse_klee_int(ctx, vars, labels, body);
+    groups.sort_by_key(|(_, members)| std::cmp::Reverse(members.len()));
+
+    let mut clusters = Vec::new();
+    for (pair, mut members) in groups {
+        let count = members.len() as u64;
+        let first = members.swap_remove(0);
+        let fallback = counterexample(config, &first.0, &first.1, &first.2);
+        // A representative shrunk out of its cluster no longer stands for it
+        let (shrunk, shrunk_pair) = shrink_divergence(ctx, encoder, config, ir_files, summaries, first, stats);
+        let representative = if pair.is_none() || shrunk_pair == pair { shrunk } else { fallback };
+        let region = pair.and_then(|(i, j)| divergence_region(encoder, config, &c_paths[i], &rust_paths[j]));
+        clusters.push((pair, DivergenceCluster {
+            c_path:    pair.map(|(i, _)| c_paths[i].id.clone()),
+            rust_path: pair.map(|(_, j)| rust_paths[j].id.clone()),
+            count,
+            representative,
+            region,
+        }));
+    }
+
+    println!("\n  -- Divergence Clusters --");
+    for (_, c) in &clusters {
+        println!("     ✗ {} × {}: {} input(s){}",
+            c.c_path.as_deref().unwrap_or("?"), c.rust_path.as_deref().unwrap_or("?"), c.count,
+            c.region.as_ref().map(|r| format!(" where {}", r)).unwrap_or_default());
+        println!("       e.g. {} → C={}  Rust={}", c.representative.format_inputs(),
+            c.representative.c_behavior.return_value, c.representative.rust_behavior.return_value);
+    }
+    clusters
+}
+
+/// Where the pair's results differ, described by Z3.
+fn divergence_region<'ctx>(
+    encoder: &KleeEncoder<'ctx>,
+    config:  &AnalysisConfig,
+    c:       &PathSummary,
+    rust:    &PathSummary,
+) -> Option<String> {
+    region_constraints(encoder, config, c, rust).map(|region| encoder.describe(&region))
+}
+
+/// `pc_C ∧ pc_Rust`, plus `ret_C ≠ ret_Rust` when both paths bind a single
+/// integer result. `None` when a path could not be encoded.
+fn region_constraints<'ctx>(
+    encoder: &KleeEncoder<'ctx>,
+    config:  &AnalysisConfig,
+    c:       &PathSummary,
+    rust:    &PathSummary,
+) -> Option<Vec<Bool<'ctx>>> {
+    let cp = encoder.encode_path(&c.constraints, c.return_expr.as_deref());
+    let rp = encoder.encode_path(&rust.constraints, rust.return_expr.as_deref());
+    if !cp.unsupported.is_empty() || !rp.unsupported.is_empty() {
+        return None;
+    }
+    let mut region: Vec<_> = cp.constraints.iter().chain(&rp.constraints).cloned().collect();
+    let single_int = config.result_type().is_some_and(|t| !t.is_float()) && config.outputs.len() <= 1;
+    if let (true, Some(c_ret), Some(rust_ret)) = (single_int, &cp.ret, &rp.ret) {
+        region.push(encoder.returns_differ(c_ret, rust_ret));
+    }
+    Some(region)
+}
+
+// ── Generalisation ────────────────────────────────────────────────────────────
+
+/// Every input that follows the counterexample's path pair and still
+/// differs, as per-slot intervals. `None` when the counterexample does not
+/// follow the pair (its paths were only the nearest witnesses), a path could
+/// not be encoded, or an input is a float.
+fn generalise<'ctx>(
+    encoder: &KleeEncoder<'ctx>,
+    config:  &AnalysisConfig,
+    input:   &[(String, i64)],
+    c:       &PathSummary,
+    rust:    &PathSummary,
+    stats:   &mut CheckerStatistics,
+) -> Option<InputRegion> {
+    let slots = config.slots();
+    if slots.iter().any(|b| b.var_type.is_float()) {
+        return None;
+    }
+    let mut region = region_constraints(encoder, config, c, rust)?;
+    region.extend(encoder.bounds());
+
+    let ctx = encoder.context();
+    let on_pair = Solver::new(ctx);
+    for r in &region {
+        on_pair.assert(r);
+    }
+    on_pair.assert(&encoder.equals(input)?);
+    if timed_check(&on_pair, stats) != SatResult::Sat {
+        return None;
+    }
+
+    println!("\n  -- Generalising Counterexample ({} × {}) --", c.id, rust.id);
+    let mut ranges = Vec::new();
+    for b in &slots {
+        let (lo, hi) = encoder.range_of(&region, &b.name)?;
+        ranges.push((b, lo, hi));
+    }
+    // The box is exact when no input inside it leaves the region
+    let boxed = Solver::new(ctx);
+    for (b, lo, hi) in &ranges {
+        if let Some(r) = encoder.in_range(&b.name, *lo, *hi) {
+            boxed.assert(&r);
+        }
+    }
+    let refs: Vec<&Bool> = region.iter().collect();
+    boxed.assert(&Bool::and(ctx, &refs).not());
+    let exact = timed_check(&boxed, stats) == SatResult::Unsat;
+
+    let intervals: Vec<(String, String, String)> = ranges.iter()
+        .filter(|(b, lo, hi)| (*lo, *hi) != (b.min, b.max))
+        .map(|(b, lo, hi)| (b.name.clone(), b.var_type.format_value(*lo), b.var_type.format_value(*hi)))
+        .collect();
+    for (name, lo, hi) in &intervals {
+        println!("     {} ∈ [{}, {}]", name, lo, hi);
+    }
+    println!("     {}", if exact {
+        "✓ Results differ on every input in this box"
+    } else {
+        "Box over-approximates the region: the path constraints narrow it further"
+    });
+    Some(InputRegion { intervals, exact })
+}
+
+// ── Path correspondence ───────────────────────────────────────────────────────
+
+/// Path pairs checked for overlap before the correspondence is left out.
+const MAX_OVERLAP_PAIRS: usize = 4096;
+
+/// Which C and Rust paths some input within bounds takes together, by Z3
+/// satisfiability of bounds ∧ pc_C ∧ pc_Rust, and why each path without a
+/// counterpart has none. A counterpart overlaps the path and ends the same
+/// way, returning or failing in KLEE, so a Rust panic path that shares its
+/// inputs only with returning C paths is still unmatched.
+fn path_correspondence(
+    encoder:   &KleeEncoder,
+    summaries: &SymbolicSummaries,
+    stats:     &mut CheckerStatistics,
+) -> Option<PathCorrespondence> {
+    let (c_paths, rust_paths) = (&summaries.c_summaries, &summaries.rust_summaries);
+    if summaries.miter.is_some() || c_paths.is_empty() || rust_paths.is_empty() {
+        return None;
+    }
+    if c_paths.len() * rust_paths.len() > MAX_OVERLAP_PAIRS {
+        println!("\n  ⚠ {} × {} paths: path correspondence skipped", c_paths.len(), rust_paths.len());
+        return None;
+    }
+
+    let encode = |paths: &[PathSummary]| -> Vec<EncodedPath> {
+        paths.iter().map(|ps| encoder.encode_path(&ps.constraints, None)).collect()
+    };
+    let (cs, rs) = (encode(c_paths), encode(rust_paths));
+    let bounds = encoder.bounds();
+    let mut check = |paths: &[&EncodedPath]| -> Option<bool> {
+        if paths.iter().any(|p| !p.unsupported.is_empty()) {
+            return None;
+        }
+        let solver = Solver::new(encoder.context());
+        for b in bounds.iter().chain(paths.iter().flat_map(|p| &p.constraints)) {
+            solver.assert(b);
+        }
+        match timed_check(&solver, stats) {
+            SatResult::Sat     => Some(true),
+            SatResult::Unsat   => Some(false),
+            SatResult::Unknown => None,
+        }
+    };
+
+    let overlap: Vec<Vec<Option<bool>>> = cs.iter()
+        .map(|cp| rs.iter().map(|rp| check(&[cp, rp])).collect())
+        .collect();
+    let feasible_c: Vec<Option<bool>>    = cs.iter().map(|p| check(&[p])).collect();
+    let feasible_rust: Vec<Option<bool>> = rs.iter().map(|p| check(&[p])).collect();
+
+    // Why `path` matches nothing among `others`, or None when it has a counterpart
+    let classify = |path: &PathSummary, feasible: Option<bool>, row: Vec<(Option<bool>, &PathSummary)>| {
+        let counterpart = |other: &PathSummary| other.error.is_some() == path.error.is_some();
+        if row.iter().any(|(o, other)| *o == Some(true) && counterpart(other)) {
+            return None;
+        }
+        Some(match feasible {
+            None        => UnmatchedKind::Undecided,
+            Some(false) => UnmatchedKind::OutsideBounds,
+            Some(true)  => match &path.error {
+                Some(kind) if row.iter().any(|(o, _)| *o == Some(true)) => UnmatchedKind::Check(kind.clone()),
+                _ if row.iter().any(|(o, _)| o.is_none())               => UnmatchedKind::Undecided,
+                _                                                        => UnmatchedKind::Uncovered,
+            },
+        })
+    };
+    let mut unmatched = Vec::new();
+    for (i, ps) in c_paths.iter().enumerate() {
+        let row = overlap[i].iter().copied().zip(rust_paths).collect();
+        if let Some(kind) = classify(ps, feasible_c[i], row) {
+            unmatched.push(UnmatchedPath { path: ps.id.clone(), program: ProgramKind::C, kind });
         }
     }
-    if s.starts_with("(Extract ") {
-        let inner = s["(Extract ".len()..s.len()-1].trim();
-        if let Some(body) = inner.splitn(2, char::is_whitespace).nth(1) {
-            return parse_klee_int(ctx, vars, labels, body.trim());
+    for (j, ps) in rust_paths.iter().enumerate() {
+        let column = overlap.iter().map(|row| row[j]).zip(c_paths).collect();
+        if let Some(kind) = classify(ps, feasible_rust[j], column) {
+            unmatched.push(UnmatchedPath { path: ps.id.clone(), program: ProgramKind::Rust, kind });
         }
     }
-    s.parse::<i64>().ok()
-        .or_else(|| s.parse::<u64>().ok().map(|v| v as i64))
-        .map(|v| Int::from_i64(ctx, v))
+
+    let corr = PathCorrespondence {
+        c_paths:    c_paths.iter().map(|p| p.id.clone()).collect(),
+        rust_paths: rust_paths.iter().map(|p| p.id.clone()).collect(),
+        overlap,
+        unmatched,
+    };
+    print_correspondence(&corr);
+    Some(corr)
+}
+
+fn print_correspondence(corr: &PathCorrespondence) {
+    println!("\n  -- Path Correspondence --");
+    for (i, c) in corr.c_paths.iter().enumerate() {
+        let overlapping = corr.rust_paths.iter().zip(&corr.overlap[i])
+            .filter_map(|(r, o)| match o {
+                Some(true)  => Some(r.clone()),
+                Some(false) => None,
+                None        => Some(format!("{}?", r)),
+            })
+            .collect::<Vec<_>>();
+        let shown = if overlapping.is_empty() { "—".to_string() } else { overlapping.join(", ") };
+        println!("     {} ↔ {}", c, shown);
+    }
+    for u in &corr.unmatched {
+        println!("       ⚠ {} unmatched: {} — {}", u.path, u.kind.label(), u.kind.meaning());
+    }
 }
 
-fn find_label_colon(s: &str) -> Option<usize> {
-    if let Some(pos) = s.find(":(") {
-        let label = &s[..pos];
-        if !label.is_empty() && label.chars().all(|c| c.is_alphanumeric() || c == '_') {
-            return Some(pos);
+// ── Partition table ───────────────────────────────────────────────────────────
+
+/// Each side's path summaries as a piecewise definition (condition →
+/// result, simplified by Z3), aligned so overlapping C and Rust partitions
+/// share a row. A row agrees when Z3 shows the results are equal wherever
+/// both conditions hold.
+fn partition_table<'ctx>(
+    encoder:   &KleeEncoder<'ctx>,
+    config:    &AnalysisConfig,
+    summaries: &SymbolicSummaries,
+    corr:      &PathCorrespondence,
+    stats:     &mut CheckerStatistics,
+) -> Vec<PartitionRow> {
+    let single_int = config.result_type().is_some_and(|t| !t.is_float()) && config.outputs.len() <= 1;
+    let encode = |paths: &[PathSummary]| -> Vec<(EncodedPath<'ctx>, Partition)> {
+        paths.iter().map(|ps| {
+            let path = encoder.encode_path(&ps.constraints, ps.return_expr.as_deref());
+            let condition = if path.unsupported.is_empty() {
+                encoder.describe(&path.constraints)
+            } else {
+                ps.constraints.iter().map(|c| crate::diff::smt_to_human(c)).collect::<Vec<_>>().join(" ∧ ")
+            };
+            let result = match (&path.ret, &ps.return_expr, &ps.error) {
+                (_, _, Some(kind))              => format!("✗ {}", UnmatchedKind::Check(kind.clone()).label()),
+                (Some(ret), _, _) if single_int => encoder.describe_value(ret),
+                (_, Some(expr), _)              => crate::diff::smt_to_human(expr),
+                _                               => "—".into(),
+            };
+            let part = Partition { path: ps.id.clone(), condition, result };
+            (path, part)
+        }).collect()
+    };
+    let (cs, rs) = (encode(&summaries.c_summaries), encode(&summaries.rust_summaries));
+
+    let bounds = encoder.bounds();
+    let mut rows = Vec::new();
+    let mut rust_placed = vec![false; rs.len()];
+    for (i, (cp, c_part)) in cs.iter().enumerate() {
+        let mut placed = false;
+        for (j, (rp, r_part)) in rs.iter().enumerate() {
+            if corr.overlap[i][j] != Some(true) {
+                continue;
+            }
+            let fails = |ps: &PathSummary| ps.error.is_some();
+            let agree = match (&cp.ret, &rp.ret) {
+                _ if fails(&summaries.c_summaries[i]) != fails(&summaries.rust_summaries[j]) => Some(false),
+                (Some(c_ret), Some(rust_ret)) if single_int => {
+                    let solver = Solver::new(encoder.context());
+                    for b in bounds.iter().chain(&cp.constraints).chain(&rp.constraints) {
+                        solver.assert(b);
+                    }
+                    solver.assert(&encoder.returns_differ(c_ret, rust_ret));
+                    match timed_check(&solver, stats) {
+                        SatResult::Unsat   => Some(true),
+                        SatResult::Sat     => Some(false),
+                        SatResult::Unknown => None,
+                    }
+                }
+                _ => None,
+            };
+            rows.push(PartitionRow { c: Some(c_part.clone()), rust: Some(r_part.clone()), agree });
+            rust_placed[j] = true;
+            placed = true;
+        }
+        if !placed {
+            rows.push(PartitionRow { c: Some(c_part.clone()), rust: None, agree: None });
         }
     }
-    None
+    for ((_, r_part), _) in rs.iter().zip(&rust_placed).filter(|(_, placed)| !**placed) {
+        rows.push(PartitionRow { c: None, rust: Some(r_part.clone()), agree: None });
+    }
+
+    print_partitions(&rows);
+    rows
 }
 
-fn is_bare_label(s: &str) -> bool {
-    !s.is_empty()
-        && !s.starts_with('(')
-        && s.chars().all(|c| c.is_alphanumeric() || c == '_')
-        && s.parse::<i64>().is_err()
-        && s.parse::<u64>().is_err()
+fn print_partitions(rows: &[PartitionRow]) {
+    let cell = |p: &Option<Partition>| p.as_ref()
+        .map_or_else(|| "—".to_string(), |p| format!("{} → {}", p.condition, p.result));
+    let width = rows.iter().map(|r| cell(&r.c).chars().count()).max().unwrap_or(0).max(1);
+    println!("\n  -- Input Partitions (C │ Rust) --");
+    for r in rows {
+        let mark = match r.agree {
+            Some(true)  => "✓",
+            Some(false) => "✗",
+            None        => "?",
+        };
+        let left = cell(&r.c);
+        let pad = width - left.chars().count();
+        println!("     {} {}{} │ {}", mark, left, " ".repeat(pad), cell(&r.rust));
+    }
 }
 
-fn split_two(s: &str) -> Option<(&str, &str)> {
-    let s = s.trim();
-    if s.is_empty() { return None; }
-    let end = if s.starts_with('(') {
-        let mut depth = 0usize;
-        let mut ep    = 0usize;
-        for (i, c) in s.char_indices() {
-            match c {
-                '(' => depth += 1,
-                ')' => { depth -= 1; if depth == 0 { ep = i; break; } }
-                _   => {}
+// ── Exhaustive enumeration ────────────────────────────────────────────────────
+
+/// Inputs whose runner processes are started together.
+const EXHAUSTIVE_BATCH: usize = 64;
+/// Divergent inputs listed in the report; the rest are only counted.
+const MAX_LISTED_DIVERGENCES: usize = 20;
+
+/// An input on which the runners disagree, with both outputs.
+type Divergence = (Vec<(String, i64)>, BinaryOutput, BinaryOutput);
+
+/// Run both runners on every input in the bounded domain, in batches.
+/// Returns the run summary and the first divergence, or every divergence
+/// in collect-all mode.
+fn enumerate_domain(
+    config:   &AnalysisConfig,
+    ir_files: &IrFiles,
+    domain:   u64,
+    stats:    &mut CheckerStatistics,
+) -> (ExhaustiveRun, Vec<Divergence>) {
+    println!("\n  -- Exhaustive Enumeration ({} inputs, batches of {}) --", domain, EXHAUSTIVE_BATCH);
+    let per_bound: Vec<Vec<Vec<(String, i64)>>> = config.bounds.iter().map(bound_values).collect();
+    let mut run = ExhaustiveRun { domain_size: domain, ..ExhaustiveRun::default() };
+    let mut found = Vec::new();
+    let mut next_report = domain / 10;
+
+    for start in (0..domain).step_by(EXHAUSTIVE_BATCH) {
+        let end = (start + EXHAUSTIVE_BATCH as u64).min(domain);
+        let batch: Vec<Vec<(String, i64)>> = (start..end).map(|i| nth_input(&per_bound, i)).collect();
+        for (input, (c_out, rust_out)) in batch.iter().zip(run_batch(ir_files, &batch, config)) {
+            if matches!(c_out, BinaryOutput::Error) || matches!(rust_out, BinaryOutput::Error) {
+                run.runner_errors += 1;
+                continue;
+            }
+            run.inputs_run += 1;
+            if c_out.differs_from(&rust_out, &config.float_compare) {
+                run.divergent_count += 1;
+                if run.divergent.len() < MAX_LISTED_DIVERGENCES {
+                    println!("       ✗ {:?}: C={}  Rust={}", input, c_out.to_string_repr(), rust_out.to_string_repr());
+                    run.divergent.push(format_input_strings(input, config));
+                }
+                if found.is_empty() || config.collect_all {
+                    found.push((input.clone(), c_out, rust_out));
+                }
             }
         }
-        ep + 1
-    } else {
-        s.find(char::is_whitespace)?
+        if end >= next_report && end < domain {
+            println!("     … {}/{} inputs", end, domain);
+            next_report = end + domain / 10;
+        }
+    }
+    stats.merged_pairs += run.inputs_run as usize;
+
+    println!("     {} inputs run, {} runner error(s), {} divergent",
+        run.inputs_run, run.runner_errors, run.divergent_count);
+    if run.divergent_count as usize > run.divergent.len() {
+        println!("       ({} more divergent input(s) not listed)", run.divergent_count as usize - run.divergent.len());
+    }
+    (run, found)
+}
+
+/// Start both runners for every input in the batch, then collect them.
+fn run_batch(
+    ir_files: &IrFiles,
+    batch:    &[Vec<(String, i64)>],
+    config:   &AnalysisConfig,
+) -> Vec<(BinaryOutput, BinaryOutput)> {
+    let spawn = |bin: &str, input: &[(String, i64)]| {
+        Command::new(bin)
+            .args(runner_args(input, config))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+    };
+    let finish = |child: std::io::Result<Child>| match child.and_then(Child::wait_with_output) {
+        Ok(out) => parse_runner_output(&out, config),
+        Err(_)  => BinaryOutput::Error,
+    };
+    let children: Vec<_> = batch.iter()
+        .map(|input| (spawn(&ir_files.c_runner_bin, input), spawn(&ir_files.rust_runner_bin, input)))
+        .collect();
+    children.into_iter().map(|(c, r)| (finish(c), finish(r))).collect()
+}
+
+/// Input number `i` of the domain: a mixed-radix index over each bound's values.
+fn nth_input(per_bound: &[Vec<Vec<(String, i64)>>], mut i: u64) -> Vec<(String, i64)> {
+    let mut parts: Vec<&Vec<(String, i64)>> = Vec::with_capacity(per_bound.len());
+    for values in per_bound.iter().rev() {
+        let n = values.len() as u64;
+        parts.push(&values[(i % n) as usize]);
+        i /= n;
+    }
+    parts.into_iter().rev().flatten().cloned().collect()
+}
+
+/// Every value one input takes within its bounds, as slot assignments.
+/// Counts match `InputBound::domain_size`.
+fn bound_values(b: &InputBound) -> Vec<Vec<(String, i64)>> {
+    let ty = &b.var_type;
+    let range = |lo: i64| -> Vec<i64> {
+        (ty.decode(lo)..=ty.decode(b.max)).map(|v| ty.encode(v)).collect()
+    };
+    let each = |name: &str, vals: &[i64]| -> Vec<Vec<(String, i64)>> {
+        vals.iter().map(|&v| vec![(name.to_string(), v)]).collect()
     };
-    let first  = s[..end].trim();
-    let second = s[end..].trim();
-    if first.is_empty() || second.is_empty() { None } else { Some((first, second)) }
+    match b.kind {
+        InputKind::Scalar => each(&b.name, &range(b.min)),
+        InputKind::Array { max_len } => {
+            let lens: Vec<i64> = (0..=max_len as i64).collect();
+            let elems = range(b.min);
+            let mut parts = vec![each(&b.len_name(), &lens)];
+            parts.extend((0..max_len).map(|i| each(&format!("{}[{}]", b.name, i), &elems)));
+            product(parts)
+        }
+        // One string per length; bytes after the terminator stay zero
+        InputKind::Str { max_len, .. } => {
+            let bytes = range(b.min.max(1));
+            (0..=max_len).flat_map(|len| {
+                product((0..max_len).map(|i| {
+                    let name = format!("{}[{}]", b.name, i);
+                    if i < len { each(&name, &bytes) } else { each(&name, &[0]) }
+                }).collect())
+            }).collect()
+        }
+        InputKind::Struct { ref fields, .. } => product(fields.iter().map(bound_values).collect()),
+    }
+}
+
+/// Cartesian product of per-part slot assignments, concatenated.
+fn product(parts: Vec<Vec<Vec<(String, i64)>>>) -> Vec<Vec<(String, i64)>> {
+    parts.into_iter().fold(vec![Vec::new()], |acc, part| {
+        acc.iter()
+            .flat_map(|prefix| part.iter().map(move |p| [prefix.as_slice(), p].concat()))
+            .collect()
+    })
+}
+
+// ── Z3 solver ─────────────────────────────────────────────────────────────────
+
+fn solve_single_input<'ctx>(
+    ctx:     &'ctx Context,
+    encoder: &KleeEncoder<'ctx>,
+    path:    &EncodedPath<'ctx>,
+    exclude: &[Vec<(String, i64)>],
+    stats:   &mut CheckerStatistics,
+) -> Option<Vec<(String, i64)>> {
+    let solver = Solver::new(ctx);
+    for b in encoder.bounds().iter().chain(&path.constraints) {
+        solver.assert(b);
+    }
+    for prev in exclude {
+        if let Some(fresh) = encoder.differs_from(prev) {
+            solver.assert(&fresh);
+        }
+    }
+
+    if timed_check(&solver, stats) != SatResult::Sat { return None; }
+    let model = solver.get_model()?;
+    encoder.inputs(&model).filter(|inputs| !inputs.is_empty())
+}
+
+// ── Witness distance ──────────────────────────────────────────────────────────
+
+/// The path pair an input follows, when Z3 placed it; otherwise the paths
+/// whose KLEE witnesses lie closest to it.
+fn closest_paths(
+    config:    &AnalysisConfig,
+    summaries: &SymbolicSummaries,
+    input:     &[(String, i64)],
+    pair:      Option<(usize, usize)>,
+) -> (Option<PathSummary>, Option<PathSummary>) {
+    let (c_paths, rust_paths) = (&summaries.c_summaries, &summaries.rust_summaries);
+    let closest = |paths: &[PathSummary]| paths.iter()
+        .min_by(|a, b| witness_dist(&a.witness, input, config).total_cmp(&witness_dist(&b.witness, input, config)))
+        .cloned();
+    match pair {
+        Some((i, j)) => (Some(c_paths[i].clone()), Some(rust_paths[j].clone())),
+        None => (closest(c_paths), closest(rust_paths)),
+    }
+}
+
+/// Summed distance between a witness and an input, slot by slot. Values
+/// are decoded in their slot's type: as raw bit patterns, a float -1.0 and
+/// 1.0 lie near the opposite ends of i64.
+fn witness_dist(witness: &[(String, i64)], input: &[(String, i64)], config: &AnalysisConfig) -> f64 {
+    config.slots().iter().map(|b| {
+        let value = |vals: &[(String, i64)]| vals.iter()
+            .find(|(n, _)| *n == b.name)
+            .map(|(_, v)| b.var_type.decode_f64(b.var_type.normalize_raw(*v)));
+        match (value(witness), value(input)) {
+            (Some(w), Some(i)) => (w - i).abs(),
+            _                  => 1000.0,
+        }
+    }).sum()
 }
 
 // ── Counterexample formatting ─────────────────────────────────────────────────
@@ -551,4 +1944,96 @@ impl Counterexample {
             .collect::<Vec<_>>()
             .join(", ")
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn f64_bits(v: f64) -> FloatBits {
+        FloatBits { bits: v.to_bits(), single: false }
+    }
+
+    fn f32_bits(v: f32) -> FloatBits {
+        FloatBits { bits: v.to_bits() as u64, single: true }
+    }
+
+    fn cmp(spec: &str) -> FloatCompare {
+        FloatCompare::parse(spec).unwrap()
+    }
+
+    #[test]
+    fn exact_compares_bit_patterns() {
+        let exact = cmp("exact");
+        assert!(f64_bits(0.1).matches(&f64_bits(0.1), &exact));
+        assert!(!f64_bits(0.1).matches(&f64_bits(0.1 + f64::EPSILON / 8.0), &exact));
+        assert!(!f64_bits(1.0).matches(&f64_bits(f64::from_bits(1.0f64.to_bits() + 1)), &exact));
+    }
+
+    #[test]
+    fn ulp_distance_counts_representable_values() {
+        let next = f64::from_bits(1.0f64.to_bits() + 1);
+        assert!(f64_bits(1.0).matches(&f64_bits(next), &cmp("ulp:1")));
+        assert!(!f64_bits(1.0).matches(&f64_bits(next), &cmp("ulp:0")));
+
+        let next32 = f32::from_bits(1.0f32.to_bits() + 2);
+        assert!(f32_bits(1.0).matches(&f32_bits(next32), &cmp("ulp:2")));
+        assert!(!f32_bits(1.0).matches(&f32_bits(next32), &cmp("ulp:1")));
+    }
+
+    #[test]
+    fn ulp_distance_crosses_zero() {
+        // The smallest subnormals either side of zero are two steps apart
+        let pos = FloatBits { bits: 1, single: false };
+        let neg = FloatBits { bits: (1 << 63) | 1, single: false };
+        assert!(pos.matches(&neg, &cmp("ulp:2")));
+        assert!(!pos.matches(&neg, &cmp("ulp:1")));
+        let pos32 = FloatBits { bits: 1, single: true };
+        let neg32 = FloatBits { bits: 0x8000_0001, single: true };
+        assert!(pos32.matches(&neg32, &cmp("ulp:2")));
+        assert!(!pos32.matches(&neg32, &cmp("ulp:1")));
+    }
+
+    #[test]
+    fn signed_zeros_follow_the_zero_policy() {
+        assert!(!f64_bits(0.0).matches(&f64_bits(-0.0), &cmp("ulp:10")));
+        assert!(f64_bits(0.0).matches(&f64_bits(-0.0), &cmp("exact,zero=equal")));
+        assert!(f64_bits(-0.0).matches(&f64_bits(-0.0), &cmp("exact")));
+    }
+
+    #[test]
+    fn nans_follow_the_nan_policy() {
+        let quiet   = f64_bits(f64::NAN);
+        let payload = FloatBits { bits: f64::NAN.to_bits() | 1, single: false };
+        assert!(quiet.matches(&payload, &cmp("exact")));
+        assert!(!quiet.matches(&payload, &cmp("exact,nan=payload")));
+        assert!(quiet.matches(&quiet, &cmp("exact,nan=payload")));
+        assert!(!quiet.matches(&quiet, &cmp("exact,nan=never")));
+        // A NaN never equals a number, whatever the tolerance
+        assert!(!quiet.matches(&f64_bits(1.0), &cmp("abs:1e300")));
+    }
+
+    #[test]
+    fn infinities_follow_the_inf_policy() {
+        let inf = f64_bits(f64::INFINITY);
+        assert!(inf.matches(&inf, &cmp("exact")));
+        assert!(!inf.matches(&f64_bits(f64::MAX), &cmp("exact")));
+        assert!(inf.matches(&f64_bits(f64::MAX), &cmp("exact,inf=saturate")));
+        assert!(!inf.matches(&f64_bits(-f64::MAX), &cmp("exact,inf=saturate")));
+        assert!(f32_bits(f32::NEG_INFINITY).matches(&f32_bits(f32::MIN), &cmp("exact,inf=saturate")));
+    }
+
+    #[test]
+    fn abs_and_rel_tolerances() {
+        assert!(f64_bits(1.0).matches(&f64_bits(1.0005), &cmp("abs:0.001")));
+        assert!(!f64_bits(1.0).matches(&f64_bits(1.002), &cmp("abs:0.001")));
+        assert!(f64_bits(1000.0).matches(&f64_bits(1000.5), &cmp("rel:0.001")));
+        assert!(!f64_bits(1.0).matches(&f64_bits(1.5), &cmp("rel:0.001")));
+    }
+
+    #[test]
+    fn float_results_display_exactly() {
+        assert_eq!(f64_bits(0.1).to_string(), "0.1 [0x1.999999999999ap-4]");
+        assert_eq!(f64_bits(-0.0).to_string(), "-0.0 [-0x0p+0]");
+        assert_eq!(f64_bits(f64::NEG_INFINITY).to_string(), "-inf");
+    }
+}
@@ -31,8 +31,8 @@ pub fn instrument(config: &AnalysisConfig, normalized: &NormalizedFiles) -> Resu
     println!("  Instrumentation disabled (return-only mode). Copying normalized IR...");
 
     
-    let c_inst = format!("/tmp/equivalence_checker/{}_c_instrumented.bc", config.function_name);
-    let rust_inst = format!("/tmp/equivalence_checker/{}_rust_instrumented.bc", config.function_name);
+    let c_inst = config.workspace.file("c_instrumented.bc");
+    let rust_inst = config.workspace.file("rust_instrumented.bc");
 
     println!("  Instrumenting C IR...");
     instrument_ir(&normalized.c_normalized_path, &c_inst)?;
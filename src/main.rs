@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use colored::*;
 use anyhow::Result;
 
@@ -12,16 +12,29 @@ mod equivalence;
 mod reporter;
 mod server;
 mod diff;
+mod smt;
+mod toolchain;
+mod workspace;
+mod cache;
+mod parallel;
 
 use types::{AnalysisConfig, Verdict};
 
 #[derive(Parser, Debug)]
 #[command(name = "equivalence_checker")]
 #[command(about = "LLVM-Based Cross-Language Semantic Equivalence Checker")]
+#[command(subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(long)]
     ui: bool,
 
+    /// Config file with tool paths (default: $EQCHECK_CONFIG, ./eqcheck.json, ~/.config/eqcheck/config.json)
+    #[arg(long, value_name = "FILE", global = true)]
+    config: Option<String>,
+
     #[arg(long, default_value = "8080")]
     port: u16,
 
@@ -34,20 +47,112 @@ struct Cli {
     #[arg(long, value_name = "NAME", required_unless_present = "ui")]
     function: Option<String>,
 
-    #[arg(long, value_name = "BOUNDS", default_value = "x:0:100")]
-    bounds: String,
+    /// name[:type]:min:max,... (arrays: name:type[N]:min:max, strings: name:str[N], struct fields: p.x:min:max) — omit to derive bounds from the C signature
+    #[arg(long, value_name = "BOUNDS")]
+    bounds: Option<String>,
+
+    /// MIN:MAX window for derived bounds instead of each type's full range
+    #[arg(long, value_name = "MIN:MAX")]
+    default_window: Option<String>,
+
+    /// Strings passed to a Rust &str: "ascii" bytes only, or any bytes "checked" as UTF-8
+    #[arg(long, value_name = "MODE", default_value = "ascii")]
+    utf8: String,
+
+    /// C pointer parameters the function writes, returned by Rust: name[=index|field],...
+    #[arg(long, value_name = "OUTPUTS")]
+    outputs: Option<String>,
+
+    /// C failure that Rust reports as None/Err: a sentinel (-1), <0 or errno
+    #[arg(long, value_name = "SIGNAL", allow_hyphen_values = true)]
+    error_map: Option<String>,
+
+    /// Float result comparison: exact, ulp:N, abs:EPS or rel:EPS, then ,nan=.. ,zero=.. ,inf=..
+    #[arg(long, value_name = "MODE", default_value = "exact")]
+    float: String,
+
+    /// Link C and Rust into one KLEE run that asserts both results are equal
+    #[arg(long)]
+    miter: bool,
+
+    /// Test every input when the bounded domain has at most this many (0 disables)
+    #[arg(long, value_name = "N", default_value = "65536")]
+    exhaustive_limit: u64,
+
+    /// Seeded random inputs to draw (0 disables)
+    #[arg(long, value_name = "N", default_value = "100")]
+    random: usize,
+
+    /// Distribution of random inputs: uniform, edge (near min/max/0) or log (per power of two)
+    #[arg(long, value_name = "DIST", default_value = "uniform")]
+    distribution: String,
+
+    /// Seed for random sampling and fuzzing (default: fresh, printed in the report)
+    #[arg(long, value_name = "SEED")]
+    seed: Option<u64>,
+
+    /// Seconds spent fuzzing near KLEE branch constants (0 disables)
+    #[arg(long, value_name = "SECS", default_value = "5")]
+    fuzz_time: u64,
+
+    /// Keep testing after the first divergence and report every divergence region
+    #[arg(long)]
+    collect_all: bool,
+
+    /// Keep the run's intermediate files (harnesses, bitcode, KLEE output) after it ends
+    #[arg(long)]
+    keep_workspace: bool,
+
+    /// Neither reuse nor record cached bitcode, path summaries and results
+    #[arg(long)]
+    no_cache: bool,
 
+    /// KLEE test limit per program (0: no limit)
     #[arg(long, default_value = "100")]
     max_paths: u32,
 
+    /// Seconds the C and Rust KLEE runs share
     #[arg(long, default_value = "60")]
     timeout: u32,
+
+    /// KLEE search heuristic: dfs, bfs, random-path, nurs:covnew or interleaved
+    /// (default: dfs, or the config file's "klee" section)
+    #[arg(long, value_name = "HEURISTIC")]
+    search: Option<String>,
+
+    /// Forks before KLEE stops branching (0: no limit; default 500)
+    #[arg(long, value_name = "N")]
+    max_forks: Option<u32>,
+
+    /// Symbolic branches along one path (0: no limit; default 500)
+    #[arg(long, value_name = "N")]
+    max_depth: Option<u32>,
+
+    /// KLEE memory cap in MB (0: no limit; default 1000)
+    #[arg(long, value_name = "MB")]
+    max_memory: Option<u32>,
+
+    /// Seconds one solver query may take (0: no limit; default 0)
+    #[arg(long, value_name = "SECS")]
+    solver_timeout: Option<u32>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check that KLEE, clang, the LLVM tools and rustc run and agree on LLVM versions
+    Doctor,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     env_logger::init();
     let cli = Cli::parse();
+    let toolchain = toolchain::init(cli.config.as_deref())?;
+
+    if let Some(Command::Doctor) = cli.command {
+        print_banner();
+        return toolchain::doctor(toolchain);
+    }
 
     if cli.ui {
         print_banner();
@@ -61,20 +166,55 @@ async fn main() -> Result<()> {
 
     print_banner();
 
-    let bounds = parse_bounds(&cli.bounds)?;
-    let config = AnalysisConfig {
+    let bounds = parse_bounds(cli.bounds.as_deref().unwrap_or(""))?;
+    let default_window = cli.default_window.as_deref().map(types::parse_window).transpose()?;
+    let utf8 = types::Utf8Mode::parse(&cli.utf8)?;
+    let outputs = parse_outputs(cli.outputs.as_deref().unwrap_or(""))?;
+    let error_map = cli.error_map.as_deref().map(types::ErrorMap::parse).transpose()?;
+    let float_compare = types::FloatCompare::parse(&cli.float)?;
+    let sampling = types::Sampling {
+        count:        cli.random,
+        distribution: types::Distribution::parse(&cli.distribution)?,
+        seed:         cli.seed,
+        fuzz_secs:    cli.fuzz_time,
+    };
+    let defaults = toolchain.klee_options;
+    let klee = types::KleeOptions {
+        search:         cli.search.as_deref().map(types::Search::parse).transpose()?.unwrap_or(defaults.search),
+        max_forks:      cli.max_forks.unwrap_or(defaults.max_forks),
+        max_depth:      cli.max_depth.unwrap_or(defaults.max_depth),
+        max_memory:     cli.max_memory.unwrap_or(defaults.max_memory),
+        solver_timeout: cli.solver_timeout.unwrap_or(defaults.solver_timeout),
+    };
+    let mut config = AnalysisConfig {
         c_file:        c_file.clone(),
         rust_file:     rust_file.clone(),
         function_name: function.clone(),
         bounds,
+        default_window,
+        utf8,
+        return_struct: None,
+        outputs,
+        error_map,
+        return_type: None,
+        float_compare,
+        miter:     cli.miter,
+        exhaustive_limit: cli.exhaustive_limit,
+        sampling,
+        collect_all: cli.collect_all,
         max_paths: cli.max_paths,
         timeout:   cli.timeout,
+        klee,
+        workspace: workspace::Workspace::create(&function, cli.keep_workspace)?,
+        cache:     !cli.no_cache,
     };
+    let _workspace = config.workspace.guard();
 
     println!("{}", "═".repeat(60).blue());
     println!("{} {}", "Analyzing function:".bold(), function.yellow());
     println!("{} {}", "C file:".bold(),    c_file.cyan());
     println!("{} {}", "Rust file:".bold(), rust_file.cyan());
+    println!("{} {}", "Run:".bold(), config.workspace.run_id.cyan());
     println!("{}", "═".repeat(60).blue());
 
     println!("\n{}", "[ Step 1/7 ] Input Validation...".bold().white());
@@ -83,54 +223,94 @@ async fn main() -> Result<()> {
         for err in &validation.errors { println!("  {} {}", "✗".red(), err); }
         return Err(anyhow::anyhow!("Validation failed"));
     }
+    validator::apply(&mut config, &validation)?;
     println!("  {} Validation passed", "✓".green());
 
-    println!("\n{}", "[ Step 2/7 ] Compiling to LLVM IR...".bold().white());
-    let ir_files = compiler::compile(&config)?;
-    println!("  {} C IR:    {}", "✓".green(), ir_files.c_ir_path.cyan());
-    println!("  {} Rust IR: {}", "✓".green(), ir_files.rust_ir_path.cyan());
-
-    println!("\n{}", "[ Step 3/7 ] Normalizing IR...".bold().white());
-    let normalized = normalizer::normalize(&config, &ir_files)?;
-    println!("  {} Normalization complete", "✓".green());
-
-    println!("\n{}", "[ Step 4/7 ] Instrumenting IR...".bold().white());
-    let instrumented = instrumentor::instrument(&config, &normalized)?;
-    println!("  {} Instrumentation complete", "✓".green());
-
-    println!("\n{}", "[ Step 5/7 ] Running Symbolic Execution (KLEE)...".bold().white());
-    let summaries = symbolic::execute(&config, &instrumented)?;
-    println!("  {} C paths:    {}", "✓".green(), summaries.c_summaries.len());
-    println!("  {} Rust paths: {}", "✓".green(), summaries.rust_summaries.len());
-
-    println!("\n{}", "[ Step 6/7 ] Checking Equivalence...".bold().white());
-    let result = equivalence::check(
-        &config,
-        &ir_files,
-        &summaries.c_summaries,
-        &summaries.rust_summaries,
-    )?;
+    let result = match cache::load_result(&config) {
+        Some(result) => {
+            println!("\n  {} Identical check found in cache (steps 2-6 skipped)", "✓".green());
+            result
+        }
+        None => {
+            let result = analyze(&config)?;
+            cache::store_result(&config, &result);
+            result
+        }
+    };
 
     println!("\n{}", "═".repeat(60).blue());
     match result.verdict {
-        Verdict::Equivalent => {
-            println!("  {} Programs are SEMANTICALLY EQUIVALENT", "✓".green().bold());
+        Verdict::ProvedEquivalent => {
+            println!("  {} Programs are PROVED EQUIVALENT within bounds", "✓".green().bold());
         }
+        Verdict::Equivalent => match result.evidence {
+            Some(e) if e.is_sampled() => println!("  {} {}", "≈".yellow().bold(), e.headline().bold()),
+            Some(e) => println!("  {} {}", "✓".green().bold(), e.headline().bold()),
+            None    => println!("  {} Programs are SEMANTICALLY EQUIVALENT", "✓".green().bold()),
+        },
         Verdict::NotEquivalent => {
             println!("  {} Programs are NOT EQUIVALENT", "✗".red().bold());
             if let Some(ce) = &result.counterexample {
                 println!("  {} Counterexample found:", "→".yellow());
-                for (name, val) in &ce.inputs {
+                for (name, val) in &ce.input_strings {
                     println!("      {} = {}", name.cyan(), val);
                 }
                 println!("      C returned:    {}", ce.c_behavior.return_value.red());
                 println!("      Rust returned: {}", ce.rust_behavior.return_value.green());
+                for d in &ce.differences {
+                    if let Some(label) = d.kind.label() {
+                        println!("      {} differs: C={} Rust={}", label.cyan(), d.c_value.red(), d.rust_value.green());
+                    }
+                }
+                if let Some(region) = &result.region {
+                    if let (Some(c), Some(r)) = (&result.c_path, &result.rust_path) {
+                        println!("  {} {}", "→".yellow(), diff::describe_region(region, c, r));
+                    }
+                }
+                if let Some(orig) = &ce.original {
+                    println!("  {} Shrunk from: {} (C={} Rust={})", "→".yellow(),
+                        orig.format_inputs(), orig.c_behavior.return_value, orig.rust_behavior.return_value);
+                }
+            }
+            if !result.clusters.is_empty() {
+                println!("  {} {} divergence region(s):", "→".yellow(), result.clusters.len());
+                for c in &result.clusters {
+                    println!("      {} × {}: {} input(s), e.g. {}",
+                        c.c_path.as_deref().unwrap_or("?").cyan(), c.rust_path.as_deref().unwrap_or("?").cyan(),
+                        c.count, c.representative.format_inputs().red());
+                    if let Some(region) = &c.region {
+                        println!("        where {}", region);
+                    }
+                }
             }
         }
         Verdict::Unknown => {
             println!("  {} Could not determine equivalence", "?".yellow().bold());
         }
     }
+    if let Some(e) = result.evidence {
+        println!("  {} Evidence: {} -- {}", "→".yellow(), e.label().cyan(), e.meaning());
+    }
+    if let Some(why) = &result.cut_short {
+        println!("  {} KLEE stopped early: {}", "→".yellow(), why);
+    }
+    if let Some(run) = &result.exhaustive {
+        println!("  {} Exhaustive: {} of {} inputs run, {} divergent",
+            "→".yellow(), run.inputs_run, run.domain_size, run.divergent_count);
+        for input in &run.divergent {
+            let shown: Vec<String> = input.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            println!("      {}", shown.join(", ").red());
+        }
+    }
+    if let Some(seed) = result.seed {
+        println!("  {} Seed: {} (repeat with --seed {})", "→".yellow(), seed.to_string().cyan(), seed);
+    }
+    if let Some(policy) = &result.float_compare {
+        println!("  {} Float comparison: {}", "→".yellow(), policy.cyan());
+    }
+    if let Some(exploration) = &result.exploration {
+        println!("  {} Explored with: {}", "→".yellow(), exploration.flags().cyan());
+    }
     println!("{}", "═".repeat(60).blue());
 
     println!("\n{}", "[ Step 7/7 ] Generating Report...".bold().white());
@@ -140,21 +320,46 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-fn parse_bounds(s: &str) -> Result<Vec<types::InputBound>> {
-    let mut out = Vec::new();
-    for part in s.split(',') {
-        let p: Vec<&str> = part.split(':').collect();
-        if p.len() != 3 {
-            return Err(anyhow::anyhow!("Invalid bounds '{}'. Use name:min:max", part));
+/// Steps 2-6: compile, normalise, instrument, explore and compare.
+fn analyze(config: &AnalysisConfig) -> Result<types::EquivalenceResult> {
+    println!("\n{}", "[ Step 2/7 ] Compiling to LLVM IR...".bold().white());
+    let ir_files = compiler::compile(config)?;
+    println!("  {} C IR:    {}", "✓".green(), ir_files.c_ir_path.cyan());
+    println!("  {} Rust IR: {}", "✓".green(), ir_files.rust_ir_path.cyan());
+
+    println!("\n{}", "[ Step 3/7 ] Normalizing IR...".bold().white());
+    let normalized = normalizer::normalize(config, &ir_files)?;
+    println!("  {} Normalization complete", "✓".green());
+
+    println!("\n{}", "[ Step 4/7 ] Instrumenting IR...".bold().white());
+    let instrumented = instrumentor::instrument(config, &normalized)?;
+    println!("  {} Instrumentation complete", "✓".green());
+
+    println!("\n{}", "[ Step 5/7 ] Running Symbolic Execution (KLEE)...".bold().white());
+    let summaries = match &ir_files.miter_bc {
+        Some(bc) => symbolic::execute_miter(config, bc)?,
+        None     => symbolic::execute(config, &instrumented)?,
+    };
+    match &summaries.miter {
+        Some(run) => println!("  {} Miter paths: {}", "✓".green(), run.paths.len()),
+        None => {
+            println!("  {} C paths:    {}", "✓".green(), summaries.c_summaries.len());
+            println!("  {} Rust paths: {}", "✓".green(), summaries.rust_summaries.len());
         }
-        out.push(types::InputBound {
-            name:       p[0].to_string(),
-            min:        p[1].parse::<i64>()?,
-            max:        p[2].parse::<i64>()?,
-            param_type: types::ParamType::Integer,
-        });
     }
-    Ok(out)
+
+    println!("\n{}", "[ Step 6/7 ] Checking Equivalence...".bold().white());
+    equivalence::check(config, &ir_files, &summaries)
+}
+
+fn parse_bounds(s: &str) -> Result<Vec<types::InputBound>> {
+    if s.trim().is_empty() { return Ok(Vec::new()); }
+    s.split(',').map(types::InputBound::parse).collect()
+}
+
+fn parse_outputs(s: &str) -> Result<Vec<types::OutputParam>> {
+    if s.trim().is_empty() { return Ok(Vec::new()); }
+    s.split(',').map(types::OutputParam::parse).collect()
 }
 
 fn print_banner() {
@@ -7,8 +7,8 @@
 
 use crate::types::AnalysisConfig;
 use crate::compiler::IrFiles;
+use crate::toolchain;
 use anyhow::Result;
-use std::process::Command;
 use std::fs;
 
 #[derive(Debug, Clone)]
@@ -20,14 +20,8 @@ pub struct NormalizedFiles {
 }
 
 pub fn normalize(config: &AnalysisConfig, ir_files: &IrFiles) -> Result<NormalizedFiles> {
-    let c_norm = format!(
-        "/tmp/equivalence_checker/{}_c_normalized.bc",
-        config.function_name
-    );
-    let rust_norm = format!(
-        "/tmp/equivalence_checker/{}_rust_normalized.bc",
-        config.function_name
-    );
+    let c_norm    = config.workspace.file("c_normalized.bc");
+    let rust_norm = config.workspace.file("rust_normalized.bc");
 
     // ── For KLEE: direct copy, no opt ─────────────────
     println!("  Copying C IR (no opt — preserves KLEE symbolic semantics)...");
@@ -39,33 +33,30 @@ pub fn normalize(config: &AnalysisConfig, ir_files: &IrFiles) -> Result<Normaliz
     println!("    → {}", rust_norm);
 
     // ── For display: produce optimized .ll for human reading ──
-    let ll_dir = "output/ir";
-    fs::create_dir_all(ll_dir)?;
-
-    let c_norm_ll = format!("{}/{}_c_normalized.ll", ll_dir, config.function_name);
-    let r_norm_ll = format!("{}/{}_rust_normalized.ll", ll_dir, config.function_name);
+    let c_norm_ll = config.workspace.output("ir/c_normalized.ll")?;
+    let r_norm_ll = config.workspace.output("ir/rust_normalized.ll")?;
 
     // We run opt on a throw-away copy so the KLEE bc is never touched
-    let c_opt_tmp  = format!("/tmp/equivalence_checker/{}_c_opt_display.bc", config.function_name);
-    let rs_opt_tmp = format!("/tmp/equivalence_checker/{}_rs_opt_display.bc", config.function_name);
+    let c_opt_tmp  = config.workspace.file("c_opt_display.bc");
+    let rs_opt_tmp = config.workspace.file("rs_opt_display.bc");
 
     if run_opt_passes(&ir_files.c_ir_path, &c_opt_tmp).is_ok() {
-        let _ = Command::new("llvm-dis-15")
+        let _ = toolchain::get().llvm_dis.command()
             .args([&c_opt_tmp, "-o", &c_norm_ll])
             .output();
     } else {
         // Fall back to disassembling the raw bc
-        let _ = Command::new("llvm-dis-15")
+        let _ = toolchain::get().llvm_dis.command()
             .args([&ir_files.c_ir_path, "-o", &c_norm_ll])
             .output();
     }
 
     if run_opt_passes(&ir_files.rust_ir_path, &rs_opt_tmp).is_ok() {
-        let _ = Command::new("llvm-dis-15")
+        let _ = toolchain::get().llvm_dis.command()
             .args([&rs_opt_tmp, "-o", &r_norm_ll])
             .output();
     } else {
-        let _ = Command::new("llvm-dis-15")
+        let _ = toolchain::get().llvm_dis.command()
             .args([&ir_files.rust_ir_path, "-o", &r_norm_ll])
             .output();
     }
@@ -79,8 +70,9 @@ pub fn normalize(config: &AnalysisConfig, ir_files: &IrFiles) -> Result<Normaliz
 /// Run standard normalization passes.
 /// Only used for generating human-readable .ll; never applied to KLEE bc.
 fn run_opt_passes(input: &str, output: &str) -> Result<()> {
+    let opt = &toolchain::get().opt;
     // Try new pass manager first (LLVM 13+)
-    let o = Command::new("opt-15")
+    let o = opt.command()
         .args(["-passes=mem2reg,dce", input, "-o", output])
         .output()?;
 
@@ -89,7 +81,7 @@ fn run_opt_passes(input: &str, output: &str) -> Result<()> {
     }
 
     // Fallback: legacy pass manager
-    let o = Command::new("opt-15")
+    let o = opt.command()
         .args(["-mem2reg", "-dce", input, "-o", output])
         .output()?;
 
@@ -0,0 +1,29 @@
+// src/parallel/mod.rs
+// ═══════════════════════════════════════════════════════
+// C and Rust side by side
+//
+// The compile and exploration stages do the same work for each language
+// independently, so both halves run on their own threads.
+// ═══════════════════════════════════════════════════════
+
+/// Run the C and Rust halves of a stage on their own threads and wait for
+/// both. A failure names the side it came from (both, if both failed).
+pub fn both_sides<C: Send, R: Send>(
+    c:    impl FnOnce() -> anyhow::Result<C> + Send,
+    rust: impl FnOnce() -> anyhow::Result<R> + Send,
+) -> anyhow::Result<(C, R)> {
+    fn panicked<T>(_: Box<dyn std::any::Any + Send>) -> anyhow::Result<T> {
+        Err(anyhow::anyhow!("thread panicked"))
+    }
+    let (c, rust) = std::thread::scope(|s| {
+        let c    = s.spawn(c);
+        let rust = s.spawn(rust);
+        (c.join().unwrap_or_else(panicked), rust.join().unwrap_or_else(panicked))
+    });
+    match (c, rust) {
+        (Ok(c), Ok(rust))   => Ok((c, rust)),
+        (Err(e), Ok(_))     => Err(anyhow::anyhow!("C side failed: {}", e)),
+        (Ok(_), Err(e))     => Err(anyhow::anyhow!("Rust side failed: {}", e)),
+        (Err(c), Err(rust)) => Err(anyhow::anyhow!("C side failed: {}\nRust side failed: {}", c, rust)),
+    }
+}
\ No newline at end of file
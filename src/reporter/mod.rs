@@ -1,14 +1,13 @@
 // src/reporter/mod.rs
-use crate::types::{AnalysisConfig, EquivalenceResult, Verdict};
+use crate::types::{AnalysisConfig, EquivalenceResult, Partition, Verdict};
 use crate::diff::find_semantic_divergence;
 use anyhow::Result;
 use std::fs;
 
 pub fn generate(config: &AnalysisConfig, result: &EquivalenceResult) -> Result<String> {
-    fs::create_dir_all("output")?;
-    let html_path = format!("output/{}_report.html", config.function_name);
+    let html_path = config.workspace.output(&format!("{}_report.html", config.function_name))?;
     fs::write(&html_path, generate_html_report(config, result))?;
-    let json_path = format!("output/{}_report.json", config.function_name);
+    let json_path = config.workspace.output(&format!("{}_report.json", config.function_name))?;
     fs::write(&json_path, serde_json::to_string_pretty(result)?)?;
     Ok(html_path)
 }
@@ -20,19 +19,44 @@ fn generate_html_report(config: &AnalysisConfig, result: &EquivalenceResult) ->
     let sem_diff = find_semantic_divergence(
         result.c_path.as_ref(),
         result.rust_path.as_ref(),
+        result.region.as_ref(),
         &c_src,
         &rust_src,
     );
 
     let diff_html  = build_diff_html(&c_src, &rust_src, &sem_diff);
     let ce_html    = generate_counterexample_html(result);
+    let exh_html   = generate_exhaustive_html(result);
+    let clus_html  = generate_clusters_html(result);
+    let part_html  = generate_partitions_html(result);
     let stats_html = generate_stats_html(result);
     let path_note  = generate_path_note_html(result);
+    let float_note = result.float_compare.as_ref()
+        .map(|p| format!(r#"<div class="verdict-sub">Floats compared with <code>{}</code></div>"#, html_escape(p)))
+        .unwrap_or_default();
 
-    let (vborder, vcolor, vicon, vtext) = match result.verdict {
-        Verdict::Equivalent    => ("rgba(5,150,105,.3)",  "#059669", "✓", "SEMANTICALLY EQUIVALENT"),
-        Verdict::NotEquivalent => ("rgba(220,38,38,.3)",  "#dc2626", "✗", "NOT EQUIVALENT"),
-        Verdict::Unknown       => ("rgba(217,119,6,.3)",  "#d97706", "?", "UNKNOWN"),
+    let evidence_note = result.evidence
+        .map(|e| format!(r#"<div class="verdict-sub">Evidence: <strong>{}</strong> — {}{}</div>"#, e.label(), e.meaning(),
+            result.cut_short.as_ref().map(|why| format!(" (KLEE stopped early: {})", html_escape(why))).unwrap_or_default()))
+        .unwrap_or_default();
+
+    let seed_note = result.seed
+        .map(|s| format!(r#"<div class="verdict-sub">Random inputs seeded with <code>{}</code> — rerun with <code>--seed {}</code> to reproduce</div>"#, s, s))
+        .unwrap_or_default();
+
+    let explore_note = result.exploration.as_ref()
+        .map(|e| format!(r#"<div class="verdict-sub">Paths explored with <code>{}</code></div>"#, html_escape(&e.flags())))
+        .unwrap_or_default();
+
+    // A sampled "equivalent" is amber: no difference found, nothing proved
+    let (vborder, vcolor, vicon, vtext) = match (&result.verdict, result.evidence) {
+        (Verdict::Equivalent, Some(e)) if e.is_sampled()
+                                       => ("rgba(217,119,6,.3)",  "#d97706", "≈", e.headline()),
+        (Verdict::Equivalent, Some(e)) => ("rgba(5,150,105,.3)",  "#059669", "✓", e.headline()),
+        (Verdict::ProvedEquivalent, _) => ("rgba(5,150,105,.3)",  "#059669", "✓", "PROVED EQUIVALENT WITHIN BOUNDS"),
+        (Verdict::Equivalent, None)    => ("rgba(5,150,105,.3)",  "#059669", "✓", "SEMANTICALLY EQUIVALENT"),
+        (Verdict::NotEquivalent, _)    => ("rgba(220,38,38,.3)",  "#dc2626", "✗", "NOT EQUIVALENT"),
+        (Verdict::Unknown, _)          => ("rgba(217,119,6,.3)",  "#d97706", "?", "UNKNOWN"),
     };
 
     format!(r#"<!DOCTYPE html>
@@ -75,6 +99,9 @@ body{{background:#0a0c0f;color:#c8d0dc;font-family:'Inter',sans-serif;font-size:
 table{{border-collapse:collapse;width:100%}}
 .hl{{background:rgba(255,77,109,.12);border-left:3px solid #ff4d6d}}
 .hl .dc{{color:#fff}}
+.part td{{padding:5px 10px;font-family:'JetBrains Mono',monospace;font-size:12px;color:#c8d0dc;border-bottom:1px solid #1e2229;vertical-align:top}}
+.part th{{padding:5px 10px;font-size:11px;color:#4a5568;text-align:left;border-bottom:1px solid #1e2229}}
+.part .res{{color:#ffd166}}
 .ln{{width:40px;padding:3px 8px;text-align:right;color:#2a3040;user-select:none;font-size:11px}}
 .dc{{padding:3px 12px;white-space:pre;color:#4a5568;font-size:12px;line-height:1.6}}
 .ar{{width:22px;text-align:center;color:#ff4d6d;font-size:12px}}
@@ -97,55 +124,208 @@ pre.suggestion{{background:#060809;border:1px solid rgba(0,229,255,.12);border-r
     <div class="verdict-icon">{vicon}</div>
     <div class="verdict-text">{vtext}</div>
     <div class="verdict-sub">{c_file} &nbsp;↔&nbsp; {rust_file}</div>
+    {evidence_note}
+    {float_note}
+    {seed_note}
+    {explore_note}
   </div>
   {stats_html}
   {path_note}
+  {exh_html}
+  {clus_html}
+  {part_html}
   {ce_html}
   {diff_html}
   <div class="footer">EQ·CHECK · C is the source of truth · Rust is the migration under verification</div>
 </div>
 </body>
 </html>"#,
-        fn_name    = config.function_name,
-        c_file     = html_escape(&config.c_file),
-        rust_file  = html_escape(&config.rust_file),
-        vborder    = vborder,
-        vcolor     = vcolor,
-        vicon      = vicon,
-        vtext      = vtext,
-        stats_html = stats_html,
-        path_note  = path_note,
-        ce_html    = ce_html,
-        diff_html  = diff_html,
+        fn_name       = config.function_name,
+        c_file        = html_escape(&config.c_file),
+        rust_file     = html_escape(&config.rust_file),
+        vborder       = vborder,
+        vcolor        = vcolor,
+        vicon         = vicon,
+        vtext         = vtext,
+        evidence_note = evidence_note,
+        float_note    = float_note,
+        seed_note     = seed_note,
+        explore_note  = explore_note,
+        stats_html    = stats_html,
+        path_note     = path_note,
+        exh_html      = exh_html,
+        clus_html     = clus_html,
+        part_html     = part_html,
+        ce_html       = ce_html,
+        diff_html     = diff_html,
     )
 }
 
 fn generate_stats_html(result: &EquivalenceResult) -> String {
+    // A single-miter run has one set of paths covering both programs
+    let paths = match result.statistics.miter_paths {
+        0 => format!(r#"<div class="stat-box"><div class="stat-val">{}</div><div class="stat-key">C Paths</div></div>
+      <div class="stat-box"><div class="stat-val">{}</div><div class="stat-key">Rust Paths</div></div>"#,
+            result.statistics.total_paths_c, result.statistics.total_paths_rust),
+        n => format!(r#"<div class="stat-box"><div class="stat-val">{}</div><div class="stat-key">Miter Paths</div></div>"#, n),
+    };
     format!(r#"<div class="stats-row">
-      <div class="stat-box"><div class="stat-val">{}</div><div class="stat-key">C Paths</div></div>
-      <div class="stat-box"><div class="stat-val">{}</div><div class="stat-key">Rust Paths</div></div>
+      {}
       <div class="stat-box"><div class="stat-val">{}</div><div class="stat-key">Inputs Tested</div></div>
       <div class="stat-box"><div class="stat-val">{:.2}s</div><div class="stat-key">Time</div></div>
     </div>"#,
-        result.statistics.total_paths_c,
-        result.statistics.total_paths_rust,
+        paths,
         result.statistics.merged_pairs,
         result.time_taken,
     )
 }
 
 fn generate_path_note_html(result: &EquivalenceResult) -> String {
-    let extra = result.statistics.total_paths_rust
-        .saturating_sub(result.statistics.total_paths_c);
-    if extra == 0 { return String::new(); }
-    format!(r#"<div class="path-note">⚠ Rust has {} more path(s) than C. This is expected — Rust inserts implicit safety checks that create extra branches absent in C.</div>"#, extra)
+    let corr = match &result.correspondence { Some(c) => c, None => return String::new() };
+    let header = corr.rust_paths.iter()
+        .map(|r| format!("<th>{}</th>", html_escape(r)))
+        .collect::<String>();
+    let rows = corr.c_paths.iter().zip(&corr.overlap).map(|(c, row)| {
+        let cells = row.iter().map(|o| match o {
+            Some(true)  => r#"<td style="color:#00e096">●</td>"#,
+            Some(false) => r#"<td style="color:#2a3040">·</td>"#,
+            None        => r#"<td style="color:#d97706">?</td>"#,
+        }).collect::<String>();
+        format!("<tr><th>{}</th>{}</tr>", html_escape(c), cells)
+    }).collect::<String>();
+    let unmatched = corr.unmatched.iter().map(|u| format!(
+        r#"<div style="margin-top:5px">⚠ <strong>{}</strong> ({:?}) — {}: {}</div>"#,
+        html_escape(&u.path), u.program, html_escape(&u.kind.label()), u.kind.meaning(),
+    )).collect::<String>();
+    let note = if corr.unmatched.is_empty() {
+        "Every path overlaps a path on the other side that ends the same way.".to_string()
+    } else {
+        format!(r#"<div class="path-note" style="margin:10px 0 0">{} path(s) without a counterpart{}</div>"#, corr.unmatched.len(), unmatched)
+    };
+    format!(r#"<div class="section">
+      <div class="section-title">⇄ Path Correspondence</div>
+      <div>● some input within bounds takes both paths (Z3), · none does, ? undecided</div>
+      <table class="part" style="margin-top:12px;width:auto">
+        <tr><th></th>{}</tr>{}
+      </table>
+      <div style="margin-top:10px">{}</div>
+    </div>"#,
+        header,
+        rows,
+        note,
+    )
+}
+
+fn generate_exhaustive_html(result: &EquivalenceResult) -> String {
+    let run = match &result.exhaustive { Some(r) => r, None => return String::new() };
+    let listed = run.divergent.iter().map(|input| {
+        let shown = input.iter()
+            .map(|(k, v)| format!("{} = {}", html_escape(k), html_escape(v)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(r#"<div style="margin-top:5px"><span class="ce-val in-val">{}</span></div>"#, shown)
+    }).collect::<String>();
+    let unlisted = run.divergent_count as usize - run.divergent.len();
+    let more = if unlisted > 0 {
+        format!(r#"<div class="verdict-sub">… and {} more divergent input(s)</div>"#, unlisted)
+    } else {
+        String::new()
+    };
+    format!(r#"<div class="section">
+      <div class="section-title">∀ Exhaustive Enumeration</div>
+      <div>{} of {} inputs run · {} runner error(s) · <strong>{}</strong> divergent</div>{}{}
+    </div>"#,
+        run.inputs_run,
+        run.domain_size,
+        run.runner_errors,
+        run.divergent_count,
+        listed,
+        more,
+    )
+}
+
+fn generate_clusters_html(result: &EquivalenceResult) -> String {
+    if result.clusters.is_empty() { return String::new(); }
+    let rows = result.clusters.iter().map(|c| {
+        let region = c.region.as_ref()
+            .map(|r| format!(r#"<div class="verdict-sub">where <code>{}</code></div>"#, html_escape(r)))
+            .unwrap_or_default();
+        format!(r#"<div style="margin-top:12px">
+        <div><strong>{}</strong> × <strong>{}</strong> · {} input(s)</div>{}
+        <div style="margin-top:5px"><span class="ce-val in-val">{}</span> → <span class="ce-val c-val">{}</span> ≠ <span class="ce-val r-val">{}</span></div>
+      </div>"#,
+            html_escape(c.c_path.as_deref().unwrap_or("?")),
+            html_escape(c.rust_path.as_deref().unwrap_or("?")),
+            c.count,
+            region,
+            html_escape(&c.representative.format_inputs()),
+            html_escape(&c.representative.c_behavior.return_value),
+            html_escape(&c.representative.rust_behavior.return_value),
+        )
+    }).collect::<String>();
+    let total: u64 = result.clusters.iter().map(|c| c.count).sum();
+    format!(r#"<div class="section">
+      <div class="section-title">⚑ Divergence Regions</div>
+      <div>{} divergent input(s) in {} region(s), grouped by (C path, Rust path)</div>{}
+    </div>"#,
+        total,
+        result.clusters.len(),
+        rows,
+    )
+}
+
+fn generate_partitions_html(result: &EquivalenceResult) -> String {
+    if result.partitions.is_empty() { return String::new(); }
+    let cells = |p: Option<&Partition>| p.map_or_else(
+        || r#"<td>—</td><td></td>"#.to_string(),
+        |p| format!(r#"<td title="{}">{}</td><td class="res">→ {}</td>"#,
+            html_escape(&p.path), html_escape(&p.condition), html_escape(&p.result)),
+    );
+    // Consecutive rows for the same C path show its cells once
+    let mut prev_c: Option<&str> = None;
+    let rows = result.partitions.iter().map(|r| {
+        let c_id = r.c.as_ref().map(|p| p.path.as_str());
+        let c_cells = if c_id.is_some() && c_id == prev_c {
+            r#"<td></td><td></td>"#.to_string()
+        } else {
+            cells(r.c.as_ref())
+        };
+        prev_c = c_id;
+        let (class, mark) = match r.agree {
+            Some(true)  => ("", r#"<span style="color:#00e096">✓</span>"#),
+            Some(false) => (r#" class="hl""#, r#"<span style="color:#ff4d6d">✗</span>"#),
+            None        => ("", r#"<span style="color:#4a5568">?</span>"#),
+        };
+        format!("<tr{}><td>{}</td>{}{}</tr>", class, mark, c_cells, cells(r.rust.as_ref()))
+    }).collect::<String>();
+    let differing = result.partitions.iter().filter(|r| r.agree == Some(false)).count();
+    format!(r#"<div class="section">
+      <div class="section-title">▦ Input Partitions</div>
+      <div>Each path as condition → result, simplified by Z3; overlapping C and Rust partitions share a row ({} differ)</div>
+      <table class="part" style="margin-top:12px">
+        <tr><th></th><th>C condition</th><th>C result</th><th>Rust condition</th><th>Rust result</th></tr>{}
+      </table>
+    </div>"#,
+        differing,
+        rows,
+    )
 }
 
 fn generate_counterexample_html(result: &EquivalenceResult) -> String {
     let ce = match &result.counterexample { Some(c) => c, None => return String::new() };
-    let inputs = ce.inputs.iter().map(|(k, v)|
-        format!(r#"<div style="margin-bottom:5px"><span style="color:#4a5568;font-family:'JetBrains Mono',monospace;font-size:12px;display:inline-block;width:50px">{}</span><span class="ce-val in-val"> = {}</span></div>"#, k, v)
+    let inputs = ce.input_strings.iter().map(|(k, v)|
+        format!(r#"<div style="margin-bottom:5px"><span style="color:#4a5568;font-family:'JetBrains Mono',monospace;font-size:12px;display:inline-block;width:50px">{}</span><span class="ce-val in-val"> = {}</span></div>"#, html_escape(k), html_escape(v))
     ).collect::<String>();
+    let fields = ce.differences.iter().filter_map(|d| d.kind.label().map(|label| format!(
+        r#"<div style="margin-top:5px"><span class="ce-box-title">{}</span> <span class="ce-val c-val">{}</span> ≠ <span class="ce-val r-val">{}</span></div>"#,
+        html_escape(&label), html_escape(&d.c_value), html_escape(&d.rust_value)
+    ))).collect::<String>();
+    let shrunk = ce.original.as_ref().map(|orig| format!(
+        r#"<div class="verdict-sub" style="margin-top:10px">Shrunk from <code>{}</code> (C returned <code>{}</code>, Rust <code>{}</code>)</div>"#,
+        html_escape(&orig.format_inputs()),
+        html_escape(&orig.c_behavior.return_value),
+        html_escape(&orig.rust_behavior.return_value),
+    )).unwrap_or_default();
 
     format!(r#"<div class="section">
       <div class="section-title">⚡ Counterexample</div>
@@ -155,11 +335,13 @@ fn generate_counterexample_html(result: &EquivalenceResult) -> String {
           <div><div class="ce-box-title">C Returns</div><div class="ce-val c-val">{}</div></div>
           <div><div class="ce-box-title">Rust Returns</div><div class="ce-val r-val">{}</div></div>
         </div>
-      </div>
+      </div>{}{}
     </div>"#,
         inputs,
         html_escape(&ce.c_behavior.return_value),
         html_escape(&ce.rust_behavior.return_value),
+        fields,
+        shrunk,
     )
 }
 
@@ -178,10 +360,13 @@ fn build_diff_html(
         format!(r#"<div style="margin-bottom:14px;background:rgba(255,255,255,.02);border:1px solid #2a3040;border-radius:8px;padding:14px">
           <div style="font-family:'JetBrains Mono',monospace;font-size:11px;color:#4a5568;text-transform:uppercase;letter-spacing:.08em;margin-bottom:10px">Divergent Branch Condition</div>
           <div class="smt-row"><span class="smt-label">C:</span><span class="smt-val diverge">{}</span></div>
-          <div class="smt-row"><span class="smt-label">Rust:</span><span class="smt-val diverge">{}</span></div>
+          <div class="smt-row"><span class="smt-label">Rust:</span><span class="smt-val diverge">{}</span></div>{}
         </div>"#,
             html_escape(&sd.human_c),
             html_escape(&sd.human_rust),
+            sd.region.as_ref()
+                .map(|r| format!(r#"<div class="smt-row"><span class="smt-label">Region:</span><span class="smt-val">{}</span></div>"#, html_escape(r)))
+                .unwrap_or_default(),
         )
     } else { String::new() };
 
@@ -10,7 +10,7 @@ use serde::Serialize;
 use std::net::SocketAddr;
 use anyhow::Result;
 
-use crate::types::{AnalysisConfig, Verdict};
+use crate::types::AnalysisConfig;
 
 pub async fn launch(port: u16) -> Result<()> {
     let app = Router::new()
@@ -54,15 +54,86 @@ pub enum Msg {
         counterexample: Option<CeMsg>,
         diff:           Option<SemanticDiffMsg>,
         time_taken:     f64,
+        /// Float comparison settings, when a compared result is a float
+        float_compare:  Option<String>,
+        /// What an equivalent verdict rests on
+        evidence:       Option<EvidenceMsg>,
+        /// Set when every input in the bounded domain was run
+        exhaustive:     Option<crate::types::ExhaustiveRun>,
+        /// Seed behind random sampling and fuzzing, for reproduction
+        seed:           Option<u64>,
+        /// Every divergence region, in collect-all mode
+        clusters:       Vec<ClusterMsg>,
+        /// Both sides' input partitions, aligned by overlap
+        partitions:     Vec<crate::types::PartitionRow>,
+        /// Which C and Rust paths overlap, and why the rest match nothing
+        correspondence: Option<CorrespondenceMsg>,
+        /// Checker flags that repeat the KLEE exploration
+        exploration:    Option<String>,
     },
     Error  { text: String },
 }
 
+#[derive(Serialize, Clone)]
+pub struct EvidenceMsg {
+    pub kind:     crate::types::Evidence,
+    pub label:    String,
+    pub headline: String,
+    pub meaning:  String,
+    /// No difference found, but only a sample of the domain was compared
+    pub sampled:  bool,
+    /// What stopped KLEE before it explored every path
+    pub cut_short: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ClusterMsg {
+    pub c_path:         Option<String>,
+    pub rust_path:      Option<String>,
+    pub count:          u64,
+    pub representative: CeMsg,
+    pub region:         Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct CorrespondenceMsg {
+    pub c_paths:    Vec<String>,
+    pub rust_paths: Vec<String>,
+    pub overlap:    Vec<Vec<Option<bool>>>,
+    pub unmatched:  Vec<UnmatchedMsg>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct UnmatchedMsg {
+    pub path:    String,
+    pub side:    String,
+    pub label:   String,
+    pub meaning: String,
+}
+
 #[derive(Serialize, Clone)]
 pub struct CeMsg {
-    pub inputs:   Vec<(String, i64)>,
+    pub inputs:   Vec<(String, String)>,
     pub c_return: String,
     pub r_return: String,
+    /// Differing struct fields (".x") and outputs ("*rem") as (label, C value, Rust value)
+    pub fields:   Vec<(String, String, String)>,
+    /// The counterexample as first found, before shrinking
+    pub original: Option<Box<CeMsg>>,
+}
+
+impl CeMsg {
+    fn from(c: &crate::types::Counterexample) -> CeMsg {
+        CeMsg {
+            inputs:   c.input_strings.clone(),
+            c_return: c.c_behavior.return_value.clone(),
+            r_return: c.rust_behavior.return_value.clone(),
+            fields:   c.differences.iter()
+                .filter_map(|d| d.kind.label().map(|l| (l, d.c_value.clone(), d.rust_value.clone())))
+                .collect(),
+            original: c.original.as_deref().map(|o| Box::new(CeMsg::from(o))),
+        }
+    }
 }
 
 #[derive(Serialize, Clone)]
@@ -74,7 +145,8 @@ pub struct SemanticDiffMsg {
     pub c_lines:           Vec<DiffLine>,
     pub rust_lines:        Vec<DiffLine>,
     pub suggestion:        String,
-    pub extra_rust_paths:  usize,
+    /// Every input on the counterexample's path pair whose results differ
+    pub region:            Option<String>,
 }
 
 #[derive(Serialize, Clone)]
@@ -90,9 +162,28 @@ async fn run_check(mut multipart: Multipart) -> impl IntoResponse {
     let mut c_bytes   = Vec::<u8>::new();
     let mut r_bytes   = Vec::<u8>::new();
     let mut function  = String::new();
-    let mut bounds    = String::from("x:0:100");
+    let mut bounds    = String::new();
+    let mut window    = String::new();
+    let mut utf8      = String::new();
+    let mut outputs   = String::new();
+    let mut error_map = String::new();
+    let mut float     = String::new();
+    let mut miter     = false;
+    let mut exhaustive_limit = 65536u64;
+    let mut random    = 100usize;
+    let mut distribution = String::new();
+    let mut seed      = String::new();
+    let mut fuzz_time = 5u64;
+    let mut collect_all = false;
+    let mut keep_workspace = false;
+    let mut no_cache  = false;
     let mut timeout   = 60u32;
     let mut max_paths = 100u32;
+    let mut search    = String::new();
+    let mut max_forks = String::new();
+    let mut max_depth = String::new();
+    let mut max_memory = String::new();
+    let mut solver_timeout = String::new();
 
     while let Ok(Some(field)) = multipart.next_field().await {
         let name = field.name().unwrap_or("").to_string();
@@ -103,10 +194,36 @@ async fn run_check(mut multipart: Multipart) -> impl IntoResponse {
                              r_bytes = field.bytes().await.unwrap_or_default().to_vec(); }
             "function"  => { function  = field.text().await.unwrap_or_default(); }
             "bounds"    => { bounds    = field.text().await.unwrap_or(bounds); }
+            "default_window" => { window = field.text().await.unwrap_or_default(); }
+            "utf8"      => { utf8      = field.text().await.unwrap_or_default(); }
+            "outputs"   => { outputs   = field.text().await.unwrap_or_default(); }
+            "error_map" => { error_map = field.text().await.unwrap_or_default(); }
+            "float"     => { float     = field.text().await.unwrap_or_default(); }
+            "miter"     => { let v = field.text().await.unwrap_or_default();
+                             miter     = matches!(v.trim(), "1" | "true" | "on"); }
+            "exhaustive_limit" => { let v = field.text().await.unwrap_or_default();
+                             exhaustive_limit = v.trim().parse().unwrap_or(65536); }
+            "random"    => { let v = field.text().await.unwrap_or_default();
+                             random    = v.trim().parse().unwrap_or(100); }
+            "distribution" => { distribution = field.text().await.unwrap_or_default(); }
+            "seed"      => { seed      = field.text().await.unwrap_or_default(); }
+            "fuzz_time" => { let v = field.text().await.unwrap_or_default();
+                             fuzz_time = v.trim().parse().unwrap_or(5); }
+            "collect_all" => { let v = field.text().await.unwrap_or_default();
+                             collect_all = matches!(v.trim(), "1" | "true" | "on"); }
+            "keep_workspace" => { let v = field.text().await.unwrap_or_default();
+                             keep_workspace = matches!(v.trim(), "1" | "true" | "on"); }
+            "no_cache"  => { let v = field.text().await.unwrap_or_default();
+                             no_cache  = matches!(v.trim(), "1" | "true" | "on"); }
             "timeout"   => { let v = field.text().await.unwrap_or_default();
                              timeout   = v.parse().unwrap_or(60); }
             "max_paths" => { let v = field.text().await.unwrap_or_default();
                              max_paths = v.parse().unwrap_or(100); }
+            "search"    => { search    = field.text().await.unwrap_or_default(); }
+            "max_forks" => { max_forks = field.text().await.unwrap_or_default(); }
+            "max_depth" => { max_depth = field.text().await.unwrap_or_default(); }
+            "max_memory" => { max_memory = field.text().await.unwrap_or_default(); }
+            "solver_timeout" => { solver_timeout = field.text().await.unwrap_or_default(); }
             _           => { let _ = field.text().await; }
         }
     }
@@ -124,21 +241,91 @@ async fn run_check(mut multipart: Multipart) -> impl IntoResponse {
     std::fs::write(&c_path, &c_bytes).unwrap();
     std::fs::write(&r_path, &r_bytes).unwrap();
 
-    let parsed_bounds = match parse_bounds(&bounds) {
-        Ok(b)  => b,
+    let parse_options = || -> anyhow::Result<_> {
+        let default_window = if window.trim().is_empty() {
+            None
+        } else {
+            Some(crate::types::parse_window(&window)?)
+        };
+        let error_map = if error_map.trim().is_empty() {
+            None
+        } else {
+            Some(crate::types::ErrorMap::parse(&error_map)?)
+        };
+        let seed = if seed.trim().is_empty() {
+            None
+        } else {
+            Some(seed.trim().parse::<u64>()
+                .map_err(|_| anyhow::anyhow!("Invalid seed '{}': expected an unsigned integer", seed.trim()))?)
+        };
+        let sampling = crate::types::Sampling {
+            count:        random,
+            distribution: crate::types::Distribution::parse(&distribution)?,
+            seed,
+            fuzz_secs:    fuzz_time,
+        };
+        // Blank fields keep the config file's (or the built-in) defaults
+        let defaults = crate::toolchain::get().klee_options;
+        let limit = |name: &str, v: &str, default: u32| -> anyhow::Result<u32> {
+            if v.trim().is_empty() { return Ok(default); }
+            v.trim().parse().map_err(|_| anyhow::anyhow!("Invalid {} '{}': expected an unsigned integer", name, v.trim()))
+        };
+        let klee = crate::types::KleeOptions {
+            search: if search.trim().is_empty() { defaults.search } else { crate::types::Search::parse(&search)? },
+            max_forks:      limit("max forks", &max_forks, defaults.max_forks)?,
+            max_depth:      limit("max depth", &max_depth, defaults.max_depth)?,
+            max_memory:     limit("max memory", &max_memory, defaults.max_memory)?,
+            solver_timeout: limit("solver timeout", &solver_timeout, defaults.solver_timeout)?,
+        };
+        Ok((
+            parse_bounds(&bounds)?,
+            default_window,
+            crate::types::Utf8Mode::parse(&utf8)?,
+            parse_outputs(&outputs)?,
+            error_map,
+            crate::types::FloatCompare::parse(&float)?,
+            sampling,
+            klee,
+        ))
+    };
+    let (parsed_bounds, default_window, utf8, outputs, error_map, float_compare, sampling, klee) = match parse_options() {
+        Ok(parsed) => parsed,
         Err(e) => {
             let body = serde_json::to_string(&Msg::Error { text: e.to_string() }).unwrap_or_default();
             return (StatusCode::BAD_REQUEST, [("content-type", "application/x-ndjson")], body);
         }
     };
 
+    let workspace = match crate::workspace::Workspace::create(&function, keep_workspace) {
+        Ok(ws) => ws,
+        Err(e) => {
+            let body = serde_json::to_string(&Msg::Error { text: format!("Cannot create run workspace: {}", e) }).unwrap_or_default();
+            return (StatusCode::INTERNAL_SERVER_ERROR, [("content-type", "application/x-ndjson")], body);
+        }
+    };
+    let _workspace = workspace.guard();
+
     let config = AnalysisConfig {
         c_file:        c_path.to_string_lossy().to_string(),
         rust_file:     r_path.to_string_lossy().to_string(),
         function_name: function.clone(),
         bounds:        parsed_bounds,
+        default_window,
+        utf8,
+        return_struct: None,
+        outputs,
+        error_map,
+        return_type: None,
+        float_compare,
+        miter,
+        exhaustive_limit,
+        sampling,
+        collect_all,
         max_paths,
         timeout,
+        klee,
+        workspace,
+        cache: !no_cache,
     };
 
     let msgs = tokio::task::spawn_blocking(move || run_pipeline(config))
@@ -154,7 +341,7 @@ async fn run_check(mut multipart: Multipart) -> impl IntoResponse {
     (StatusCode::OK, [("content-type", "application/x-ndjson")], body)
 }
 
-fn run_pipeline(config: AnalysisConfig) -> Vec<Msg> {
+fn run_pipeline(mut config: AnalysisConfig) -> Vec<Msg> {
     let mut msgs: Vec<Msg> = Vec::new();
 
     macro_rules! log {
@@ -163,6 +350,7 @@ fn run_pipeline(config: AnalysisConfig) -> Vec<Msg> {
         };
     }
 
+    log!("muted", format!("  · Run {}", config.workspace.run_id));
     log!("step", "[ Step 1/7 ] Input Validation...");
     let validation = match crate::validator::validate(&config) {
         Ok(v)  => v,
@@ -173,47 +361,32 @@ fn run_pipeline(config: AnalysisConfig) -> Vec<Msg> {
         msgs.push(Msg::Error { text: "Validation failed".into() });
         return msgs;
     }
+    if let Err(e) = crate::validator::apply(&mut config, &validation) {
+        msgs.push(Msg::Error { text: e.to_string() });
+        return msgs;
+    }
+    let shown = config.bounds.iter().flat_map(|b| match &b.kind {
+        crate::types::InputKind::Struct { fields, .. } => fields.iter().collect::<Vec<_>>(),
+        _ => vec![b],
+    });
+    for b in shown {
+        log!("muted", format!("  · {}: {} in [{}, {}]", b.name, b.var_type.rust_type(),
+            b.var_type.format_value(b.min), b.var_type.format_value(b.max)));
+    }
     log!("ok", "  ✓ Validation passed");
 
-    log!("step", "[ Step 2/7 ] Compiling to LLVM IR...");
-    let ir_files = match crate::compiler::compile(&config) {
-        Ok(f)  => f,
-        Err(e) => { msgs.push(Msg::Error { text: e.to_string() }); return msgs; }
-    };
-    log!("ok", format!("  ✓ C IR:    {}", ir_files.c_ir_path));
-    log!("ok", format!("  ✓ Rust IR: {}", ir_files.rust_ir_path));
-
-    log!("step", "[ Step 3/7 ] Normalizing IR...");
-    let normalized = match crate::normalizer::normalize(&config, &ir_files) {
-        Ok(n)  => n,
-        Err(e) => { msgs.push(Msg::Error { text: e.to_string() }); return msgs; }
-    };
-    log!("ok", "  ✓ Normalization complete");
-
-    log!("step", "[ Step 4/7 ] Instrumenting IR...");
-    let instrumented = match crate::instrumentor::instrument(&config, &normalized) {
-        Ok(i)  => i,
-        Err(e) => { msgs.push(Msg::Error { text: e.to_string() }); return msgs; }
-    };
-    log!("ok", "  ✓ Instrumentation complete");
-
-    log!("step", "[ Step 5/7 ] Running Symbolic Execution (KLEE)...");
-    let summaries = match crate::symbolic::execute(&config, &instrumented) {
-        Ok(s)  => s,
-        Err(e) => { msgs.push(Msg::Error { text: e.to_string() }); return msgs; }
-    };
-    log!("ok", format!("  ✓ C paths:    {}", summaries.c_summaries.len()));
-    log!("ok", format!("  ✓ Rust paths: {}", summaries.rust_summaries.len()));
-
-    log!("step", "[ Step 6/7 ] Checking Equivalence...");
-    let result = match crate::equivalence::check(
-        &config,
-        &ir_files,
-        &summaries.c_summaries,
-        &summaries.rust_summaries,
-    ) {
-        Ok(r)  => r,
-        Err(e) => { msgs.push(Msg::Error { text: e.to_string() }); return msgs; }
+    let result = match crate::cache::load_result(&config) {
+        Some(result) => {
+            log!("ok", "  ✓ Identical check found in cache (steps 2-6 skipped)");
+            result
+        }
+        None => match analyze(&config, &mut msgs) {
+            Ok(result) => {
+                crate::cache::store_result(&config, &result);
+                result
+            }
+            Err(e) => { msgs.push(Msg::Error { text: e.to_string() }); return msgs; }
+        },
     };
 
     log!("step", "[ Step 7/7 ] Generating Report...");
@@ -222,44 +395,107 @@ fn run_pipeline(config: AnalysisConfig) -> Vec<Msg> {
         Err(e)   => { log!("warn", format!("  ⚠ Report failed: {}", e)); }
     }
 
-    let ce = result.counterexample.as_ref().map(|c| CeMsg {
-        inputs:   c.inputs.clone(),
-        c_return: c.c_behavior.return_value.clone(),
-        r_return: c.rust_behavior.return_value.clone(),
-    });
+    let ce = result.counterexample.as_ref().map(CeMsg::from);
 
-    let diff: Option<SemanticDiffMsg> = if result.verdict != Verdict::Equivalent {
+    let diff: Option<SemanticDiffMsg> = if !result.verdict.is_equivalent() {
         let c_src    = std::fs::read_to_string(&config.c_file).unwrap_or_default();
         let rust_src = std::fs::read_to_string(&config.rust_file).unwrap_or_default();
         let sem = crate::diff::find_semantic_divergence(
             result.c_path.as_ref(),
             result.rust_path.as_ref(),
+            result.region.as_ref(),
             &c_src,
             &rust_src,
         );
-        let extra = result.statistics.total_paths_rust
-            .saturating_sub(result.statistics.total_paths_c);
-        Some(build_semantic_diff_msg(sem, &c_src, &rust_src, extra))
+        Some(build_semantic_diff_msg(sem, &c_src, &rust_src))
     } else { None };
 
     msgs.push(Msg::Result {
-        equivalent:     result.verdict == Verdict::Equivalent,
+        equivalent:     result.verdict.is_equivalent(),
         paths_c:        result.statistics.total_paths_c,
         paths_rust:     result.statistics.total_paths_rust,
         inputs_tested:  result.statistics.merged_pairs,
         counterexample: ce,
         diff,
         time_taken:     result.time_taken,
+        float_compare:  result.float_compare.clone(),
+        evidence:       result.evidence.map(|e| EvidenceMsg {
+            kind:     e,
+            label:    e.label().into(),
+            headline: e.headline().into(),
+            meaning:  e.meaning().into(),
+            sampled:  e.is_sampled(),
+            cut_short: result.cut_short.clone(),
+        }),
+        exhaustive:     result.exhaustive.clone(),
+        seed:           result.seed,
+        clusters:       result.clusters.iter().map(|c| ClusterMsg {
+            c_path:         c.c_path.clone(),
+            rust_path:      c.rust_path.clone(),
+            count:          c.count,
+            representative: CeMsg::from(&c.representative),
+            region:         c.region.clone(),
+        }).collect(),
+        partitions:     result.partitions.clone(),
+        correspondence: result.correspondence.as_ref().map(|c| CorrespondenceMsg {
+            c_paths:    c.c_paths.clone(),
+            rust_paths: c.rust_paths.clone(),
+            overlap:    c.overlap.clone(),
+            unmatched:  c.unmatched.iter().map(|u| UnmatchedMsg {
+                path:    u.path.clone(),
+                side:    format!("{:?}", u.program),
+                label:   u.kind.label(),
+                meaning: u.kind.meaning().into(),
+            }).collect(),
+        }),
+        exploration:    result.exploration.as_ref().map(|e| e.flags()),
     });
 
     msgs
 }
 
+/// Steps 2-6 of `run_pipeline`, logging into `msgs`.
+fn analyze(config: &AnalysisConfig, msgs: &mut Vec<Msg>) -> anyhow::Result<crate::types::EquivalenceResult> {
+    macro_rules! log {
+        ($level:expr, $text:expr) => {
+            msgs.push(Msg::Log { level: $level.to_string(), text: $text.to_string() });
+        };
+    }
+
+    log!("step", "[ Step 2/7 ] Compiling to LLVM IR...");
+    let ir_files = crate::compiler::compile(config)?;
+    log!("ok", format!("  ✓ C IR:    {}", ir_files.c_ir_path));
+    log!("ok", format!("  ✓ Rust IR: {}", ir_files.rust_ir_path));
+
+    log!("step", "[ Step 3/7 ] Normalizing IR...");
+    let normalized = crate::normalizer::normalize(config, &ir_files)?;
+    log!("ok", "  ✓ Normalization complete");
+
+    log!("step", "[ Step 4/7 ] Instrumenting IR...");
+    let instrumented = crate::instrumentor::instrument(config, &normalized)?;
+    log!("ok", "  ✓ Instrumentation complete");
+
+    log!("step", "[ Step 5/7 ] Running Symbolic Execution (KLEE)...");
+    let summaries = match &ir_files.miter_bc {
+        Some(bc) => crate::symbolic::execute_miter(config, bc)?,
+        None     => crate::symbolic::execute(config, &instrumented)?,
+    };
+    match &summaries.miter {
+        Some(run) => { log!("ok", format!("  ✓ Miter paths: {}", run.paths.len())); }
+        None => {
+            log!("ok", format!("  ✓ C paths:    {}", summaries.c_summaries.len()));
+            log!("ok", format!("  ✓ Rust paths: {}", summaries.rust_summaries.len()));
+        }
+    }
+
+    log!("step", "[ Step 6/7 ] Checking Equivalence...");
+    crate::equivalence::check(config, &ir_files, &summaries)
+}
+
 fn build_semantic_diff_msg(
-    sem:              Option<crate::diff::SemanticDiff>,
-    c_src:            &str,
-    rust_src:         &str,
-    extra_rust_paths: usize,
+    sem:      Option<crate::diff::SemanticDiff>,
+    c_src:    &str,
+    rust_src: &str,
 ) -> SemanticDiffMsg {
     let c_lines_raw:    Vec<&str> = c_src.lines().collect();
     let rust_lines_raw: Vec<&str> = rust_src.lines().collect();
@@ -276,7 +512,7 @@ fn build_semantic_diff_msg(
                 c_lines:    make_window(&c_lines_raw,    div_c.map(|n| n - 1)),
                 rust_lines: make_window(&rust_lines_raw, div_rust.map(|n| n - 1)),
                 suggestion: sd.suggestion.clone(),
-                extra_rust_paths,
+                region:     sd.region.clone(),
             }
         }
         None => SemanticDiffMsg {
@@ -290,7 +526,7 @@ fn build_semantic_diff_msg(
                          condition automatically.\n\
                          Review the counterexample inputs above and compare the source files \
                          side-by-side below.".into(),
-            extra_rust_paths,
+            region:            None,
         },
     }
 }
@@ -309,18 +545,11 @@ fn make_window(lines: &[&str], highlight: Option<usize>) -> Vec<DiffLine> {
 }
 
 fn parse_bounds(s: &str) -> anyhow::Result<Vec<crate::types::InputBound>> {
-    let mut out = Vec::new();
-    for part in s.split(',') {
-        let p: Vec<&str> = part.trim().split(':').collect();
-        if p.len() != 3 {
-            return Err(anyhow::anyhow!("Invalid bounds '{}'. Use name:min:max", part));
-        }
-        out.push(crate::types::InputBound {
-            name:       p[0].to_string(),
-            min:        p[1].parse::<i64>()?,
-            max:        p[2].parse::<i64>()?,
-            param_type: crate::types::ParamType::Integer,
-        });
-    }
-    Ok(out)
-}
\ No newline at end of file
+    if s.trim().is_empty() { return Ok(Vec::new()); }
+    s.split(',').map(crate::types::InputBound::parse).collect()
+}
+
+fn parse_outputs(s: &str) -> anyhow::Result<Vec<crate::types::OutputParam>> {
+    if s.trim().is_empty() { return Ok(Vec::new()); }
+    s.split(',').map(crate::types::OutputParam::parse).collect()
+}
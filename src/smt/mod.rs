@@ -0,0 +1,965 @@
+// src/smt/mod.rs
+// ═══════════════════════════════════════════════════════
+// Bit-precise Z3 encoding of KLEE path constraints
+//
+// KLEE .kquery expressions are fixed-width bit-vector terms over byte
+// arrays, one array per klee_make_symbolic object:
+//   (Slt N0:(ReadLSB w32 0 a) (w32 100))
+//   (Eq false (Ult (Add w32 N0 4294967295) 10))
+//   (Eq 0 (And w32 (ZExt w32 (Read w8 3 s)) 128))
+//
+// Every input slot (see `AnalysisConfig::slots`) becomes one Z3 bit-vector
+// of the slot's byte width, and each Read picks bytes out of it. Widths
+// come from the wN tokens, so wraparound, unsigned compares, extensions,
+// truncation and masking mean exactly what they mean to KLEE.
+//
+// A path's return value is the right-hand side of the harness's binding of
+// the symbolic `result` object, already split off by the symbolic stage.
+//
+// Labels (N0:expr) are scoped to one path: `encode_path` collects every
+// definition in the path's constraints and return expression first, so a
+// label may be used before the constraint that defines it.
+//
+// Anything outside the kquery subset below is reported as unsupported
+// rather than translated loosely.
+// ═══════════════════════════════════════════════════════
+
+use crate::types::{AnalysisConfig, InputBound, InputKind, VarType};
+use std::collections::HashMap;
+use z3::{Context, DeclKind, Goal, Model, SatResult, Solver, Tactic, ast::{Ast, Bool, Dynamic, BV}};
+
+/// One encoded path: its constraints and, when known, its return value.
+pub struct EncodedPath<'ctx> {
+    pub constraints: Vec<Bool<'ctx>>,
+    pub ret:         Option<BV<'ctx>>,
+    /// Why each constraint (or the return value) that could not be encoded
+    /// was left out
+    pub unsupported: Vec<String>,
+}
+
+struct SlotVar<'ctx> {
+    bound: InputBound,
+    bv:    BV<'ctx>,
+}
+
+/// Encodes the KLEE constraints of one configuration over shared input
+/// variables, so C and Rust paths can be asserted in the same solver.
+pub struct KleeEncoder<'ctx> {
+    ctx:           &'ctx Context,
+    slots:         Vec<SlotVar<'ctx>>,
+    /// Array/string objects whose elements are separate slots: name → element bytes
+    arrays:        HashMap<String, usize>,
+    /// Width of the symbolic `result` object, for return values KLEE prints as bare literals
+    result_bits:   Option<u32>,
+    result_signed: bool,
+}
+
+impl<'ctx> KleeEncoder<'ctx> {
+    pub fn new(ctx: &'ctx Context, config: &AnalysisConfig) -> Self {
+        let slots = config.slots().into_iter().map(|b| SlotVar {
+            bv:    BV::new_const(ctx, b.name.clone(), bits(&b.var_type)),
+            bound: b,
+        }).collect();
+        let arrays = config.bounds.iter()
+            .filter(|b| matches!(b.kind, InputKind::Array { .. } | InputKind::Str { .. }))
+            .map(|b| (b.name.clone(), b.var_type.byte_size()))
+            .collect();
+        let result_bits = config.result_type()
+            .map(|ty| bits(&crate::compiler::result_storage(ty)));
+        let result_signed = config.result_type().is_some_and(|ty| is_signed(&ty));
+        KleeEncoder { ctx, slots, arrays, result_bits, result_signed }
+    }
+
+    pub fn context(&self) -> &'ctx Context {
+        self.ctx
+    }
+
+    /// Each slot within its declared bounds. Floats are not modelled by the
+    /// bit-vector encoding; they stay pinned to their lower bound.
+    pub fn bounds(&self) -> Vec<Bool<'ctx>> {
+        let mut out = Vec::new();
+        for s in &self.slots {
+            let ty = &s.bound.var_type;
+            let w  = bits(ty);
+            if ty.is_float() {
+                out.push(s.bv._eq(&BV::from_u64(self.ctx, s.bound.min as u64, w)));
+            } else if is_signed(ty) {
+                out.push(s.bv.bvsge(&BV::from_i64(self.ctx, s.bound.min, w)));
+                out.push(s.bv.bvsle(&BV::from_i64(self.ctx, s.bound.max, w)));
+            } else {
+                out.push(s.bv.bvuge(&BV::from_u64(self.ctx, s.bound.min as u64, w)));
+                out.push(s.bv.bvule(&BV::from_u64(self.ctx, s.bound.max as u64, w)));
+            }
+        }
+        out
+    }
+
+    /// "The inputs are not exactly `input`" — used to ask for a fresh model.
+    pub fn differs_from(&self, input: &[(String, i64)]) -> Option<Bool<'ctx>> {
+        let clauses: Vec<Bool> = input.iter().filter_map(|(name, val)| {
+            let s = self.slots.iter().find(|s| &s.bound.name == name)?;
+            Some(s.bv._eq(&BV::from_i64(self.ctx, *val, bits(&s.bound.var_type))).not())
+        }).collect();
+        if clauses.is_empty() { return None; }
+        let refs: Vec<&Bool> = clauses.iter().collect();
+        Some(Bool::or(self.ctx, &refs))
+    }
+
+    /// "The inputs are exactly `input`" — pins a concrete input to test
+    /// which path it follows.
+    pub fn equals(&self, input: &[(String, i64)]) -> Option<Bool<'ctx>> {
+        self.differs_from(input).map(|d| d.not())
+    }
+
+    /// "Slot `name` is zero".
+    pub fn is_zero(&self, name: &str) -> Option<Bool<'ctx>> {
+        let s = self.slots.iter().find(|s| s.bound.name == name)?;
+        Some(s.bv._eq(&BV::from_u64(self.ctx, 0, bits(&s.bound.var_type))))
+    }
+
+    /// "Every integer slot lies within `limit` of zero". Slots whose type
+    /// cannot exceed the limit get no clause.
+    pub fn magnitude_at_most(&self, limit: u64) -> Vec<Bool<'ctx>> {
+        let mut out = Vec::new();
+        for s in self.slots.iter().filter(|s| !s.bound.var_type.is_float()) {
+            let w = bits(&s.bound.var_type);
+            if is_signed(&s.bound.var_type) {
+                if w < 64 && limit >= 1u64 << (w - 1) { continue; }
+                let limit = limit.min(i64::MAX as u64) as i64;
+                out.push(s.bv.bvsge(&BV::from_i64(self.ctx, -limit, w)));
+                out.push(s.bv.bvsle(&BV::from_i64(self.ctx, limit, w)));
+            } else {
+                if w < 64 && limit >= (1u64 << w) - 1 { continue; }
+                out.push(s.bv.bvule(&BV::from_u64(self.ctx, limit, w)));
+            }
+        }
+        out
+    }
+
+    /// "Slot `name` lies in [lo, hi]" (encoded values, compared in the
+    /// slot's signedness).
+    pub fn in_range(&self, name: &str, lo: i64, hi: i64) -> Option<Bool<'ctx>> {
+        let s = self.slots.iter().find(|s| s.bound.name == name)?;
+        let w = bits(&s.bound.var_type);
+        let (lo, hi) = (BV::from_i64(self.ctx, lo, w), BV::from_i64(self.ctx, hi, w));
+        Some(if is_signed(&s.bound.var_type) {
+            Bool::and(self.ctx, &[&s.bv.bvsge(&lo), &s.bv.bvsle(&hi)])
+        } else {
+            Bool::and(self.ctx, &[&s.bv.bvuge(&lo), &s.bv.bvule(&hi)])
+        })
+    }
+
+    /// Smallest and largest value integer slot `name` takes under
+    /// `constraints`, by binary search within its bounds. `None` when the
+    /// constraints are unsatisfiable or Z3 gives up.
+    pub fn range_of(&self, constraints: &[Bool<'ctx>], name: &str) -> Option<(i64, i64)> {
+        let b = &self.slots.iter().find(|s| s.bound.name == name)?.bound;
+        let ty = &b.var_type;
+        let solver = Solver::new(self.ctx);
+        for c in constraints {
+            solver.assert(c);
+        }
+        // Is some value in [lo, hi] feasible? (decoded bounds)
+        let feasible = |lo: i128, hi: i128| -> Option<bool> {
+            solver.push();
+            solver.assert(&self.in_range(name, ty.encode(lo), ty.encode(hi))?);
+            let r = solver.check();
+            solver.pop(1);
+            match r {
+                SatResult::Sat     => Some(true),
+                SatResult::Unsat   => Some(false),
+                SatResult::Unknown => None,
+            }
+        };
+        let (min, max) = (ty.decode(b.min), ty.decode(b.max));
+        if !feasible(min, max)? {
+            return None;
+        }
+        // Least value: the smallest `hi` with a feasible [min, hi]
+        let (mut lo, mut hi) = (min, max);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if feasible(min, mid)? { hi = mid } else { lo = mid + 1 }
+        }
+        let least = lo;
+        // Greatest value: the largest `lo` with a feasible [lo, max]
+        let (mut lo, mut hi) = (least, max);
+        while lo < hi {
+            let mid = lo + (hi - lo + 1) / 2;
+            if feasible(mid, max)? { lo = mid } else { hi = mid - 1 }
+        }
+        Some((ty.encode(least), ty.encode(lo)))
+    }
+
+    /// "The two return values differ", widening the narrower one (KLEE may
+    /// bind a C bool as w8 and the Rust one as w1).
+    pub fn returns_differ(&self, c: &BV<'ctx>, rust: &BV<'ctx>) -> Bool<'ctx> {
+        let w = c.get_size().max(rust.get_size());
+        let widen = |v: &BV<'ctx>| match w - v.get_size() {
+            0                         => v.clone(),
+            n if self.result_signed   => v.sign_ext(n),
+            n                         => v.zero_ext(n),
+        };
+        widen(c)._eq(&widen(rust)).not()
+    }
+
+    /// The model's value for every slot, encoded in the slot's type.
+    pub fn inputs(&self, model: &Model<'ctx>) -> Option<Vec<(String, i64)>> {
+        self.slots.iter().map(|s| {
+            let raw = model.eval(&s.bv, true)?.as_u64()?;
+            Some((s.bound.name.clone(), s.bound.var_type.normalize_raw(raw as i64)))
+        }).collect()
+    }
+
+    /// Encode one path's constraints and return expression in a single label scope.
+    pub fn encode_path(&self, constraints: &[String], return_expr: Option<&str>) -> EncodedPath<'ctx> {
+        let mut labels = HashMap::new();
+        let mut parsed = Vec::new();
+        let mut unsupported = Vec::new();
+        for c in constraints {
+            match parse_sexp(c, &mut labels) {
+                Ok(e)  => parsed.push((c, e)),
+                Err(e) => unsupported.push(format!("{} in {}", e, c)),
+            }
+        }
+        let ret = return_expr.and_then(|r| match parse_sexp(r, &mut labels) {
+            Ok(e)  => Some((r, e)),
+            Err(e) => {
+                unsupported.push(format!("return value: {} in {}", e, r));
+                None
+            }
+        });
+
+        let scope = Scope { enc: self, labels: &labels };
+        let mut encoded = Vec::new();
+        for (text, e) in &parsed {
+            match scope.term(e, None).and_then(|t| scope.to_bool(t)) {
+                Ok(b)  => encoded.push(b),
+                Err(e) => unsupported.push(format!("{} in {}", e, text)),
+            }
+        }
+        let ret = ret.and_then(|(text, e)| match scope.term(&e, self.result_bits) {
+            Ok(t)  => Some(scope.to_bv(t)),
+            Err(e) => {
+                unsupported.push(format!("return value: {} in {}", e, text));
+                None
+            }
+        });
+        EncodedPath { constraints: encoded, ret, unsupported }
+    }
+
+    /// Byte `offset` of the KLEE object `array`, as an 8-bit term.
+    fn byte(&self, array: &str, offset: u32) -> Result<BV<'ctx>, String> {
+        let (slot, byte) = match self.arrays.get(array) {
+            Some(&elem) => (format!("{}[{}]", array, offset as usize / elem), offset as usize % elem),
+            None        => (array.to_string(), offset as usize),
+        };
+        let s = self.slots.iter().find(|s| s.bound.name == slot)
+            .ok_or_else(|| format!("read of unknown object '{}'", array))?;
+        if byte >= s.bound.var_type.byte_size() {
+            return Err(format!("read past the end of '{}'", array));
+        }
+        let lo = byte as u32 * 8;
+        Ok(s.bv.extract(lo + 7, lo))
+    }
+}
+
+fn bits(ty: &VarType) -> u32 {
+    ty.byte_size() as u32 * 8
+}
+
+fn is_signed(ty: &VarType) -> bool {
+    matches!(ty, VarType::I8 | VarType::I16 | VarType::I32 | VarType::I64 | VarType::Char)
+}
+
+// ── S-expressions ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+enum Sexp {
+    Atom(String),
+    List(Vec<Sexp>),
+}
+
+/// Parse one kquery expression, recording label definitions (N0:expr) in `labels`.
+fn parse_sexp(s: &str, labels: &mut HashMap<String, Sexp>) -> Result<Sexp, String> {
+    let tokens = tokenize(s);
+    let mut pos = 0;
+    let e = parse_tokens(&tokens, &mut pos, labels)?;
+    if pos != tokens.len() {
+        return Err("trailing tokens".into());
+    }
+    Ok(e)
+}
+
+fn tokenize(s: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut cur = String::new();
+    for c in s.chars() {
+        match c {
+            '(' | ')' | '[' | ']' => {
+                if !cur.is_empty() { tokens.push(std::mem::take(&mut cur)); }
+                tokens.push(c.to_string());
+            }
+            c if c.is_whitespace() => {
+                if !cur.is_empty() { tokens.push(std::mem::take(&mut cur)); }
+            }
+            c => cur.push(c),
+        }
+    }
+    if !cur.is_empty() { tokens.push(cur); }
+    tokens
+}
+
+fn parse_tokens(
+    tokens: &[String],
+    pos:    &mut usize,
+    labels: &mut HashMap<String, Sexp>,
+) -> Result<Sexp, String> {
+    let tok = tokens.get(*pos).ok_or("unexpected end of expression")?;
+    *pos += 1;
+    match tok.as_str() {
+        "(" => {
+            let mut items = Vec::new();
+            while tokens.get(*pos).map(String::as_str) != Some(")") {
+                if *pos >= tokens.len() { return Err("unbalanced parentheses".into()); }
+                items.push(parse_tokens(tokens, pos, labels)?);
+            }
+            *pos += 1;
+            Ok(Sexp::List(items))
+        }
+        "[" | "]" => Err("array update lists are unsupported".into()),
+        ")"       => Err("unbalanced parentheses".into()),
+        t if t.ends_with(':') => {
+            let e = parse_tokens(tokens, pos, labels)?;
+            labels.insert(t.trim_end_matches(':').to_string(), e.clone());
+            Ok(e)
+        }
+        t if t.contains('@') => Err("array update lists are unsupported".into()),
+        t => Ok(Sexp::Atom(t.to_string())),
+    }
+}
+
+/// "w32" → 32
+fn width_token(e: &Sexp) -> Option<u32> {
+    match e {
+        Sexp::Atom(a) => a.strip_prefix('w')?.parse().ok(),
+        _             => None,
+    }
+}
+
+fn is_literal(e: &Sexp) -> bool {
+    matches!(e, Sexp::Atom(a) if parse_number(a).is_some())
+}
+
+fn parse_number(s: &str) -> Option<i128> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u128::from_str_radix(hex, 16).ok().map(|v| v as i128),
+        None      => s.parse::<i128>().ok()
+            .or_else(|| s.parse::<u128>().ok().map(|v| v as i128)),
+    }
+}
+
+// ── Branch constants ──────────────────────────────────────────────────────────
+
+/// Integer constants that KLEE constraints compare inputs against or add to
+/// them, for fuzzing near branch boundaries. Bare literals carry no width, so
+/// a value with its top bit set at 8/16/32/64 bits is also read as negative.
+pub fn branch_constants(constraints: &[String]) -> Vec<i128> {
+    let mut out = Vec::new();
+    for c in constraints {
+        let mut labels = HashMap::new();
+        if let Ok(e) = parse_sexp(c, &mut labels) {
+            collect_constants(&e, &mut out);
+        }
+    }
+    out.sort();
+    out.dedup();
+    out
+}
+
+fn collect_constants(e: &Sexp, out: &mut Vec<i128>) {
+    let Sexp::List(items) = e else { return };
+    let op = match items.first() {
+        Some(Sexp::Atom(op)) => op.as_str(),
+        _                    => return,
+    };
+    // Offsets and widths are positions, not values
+    if matches!(op, "Read" | "ReadLSB" | "ReadMSB" | "Extract") {
+        return;
+    }
+    let compares = matches!(op,
+        "Eq" | "Ne" | "Ult" | "Ule" | "Ugt" | "Uge" | "Slt" | "Sle" | "Sgt" | "Sge" | "Add" | "Sub");
+    for arg in &items[1..] {
+        if compares {
+            match arg {
+                Sexp::Atom(a) => if let Some(v) = parse_number(a) {
+                    out.push(v);
+                    for w in [8u32, 16, 32, 64] {
+                        if v >= 1i128 << (w - 1) && v < 1i128 << w {
+                            out.push(v - (1i128 << w));
+                        }
+                    }
+                },
+                // (w32 5)
+                Sexp::List(l) => if let (Some(w), Some(Sexp::Atom(a))) = (l.first().and_then(width_token), l.get(1)) {
+                    if let Some(v) = parse_number(a).filter(|_| (1..=64).contains(&w)) {
+                        let sign = 1i128 << (w - 1);
+                        out.push(if v & sign != 0 { v - (sign << 1) } else { v });
+                    }
+                },
+            }
+        }
+        collect_constants(arg, out);
+    }
+}
+
+// ── Region description ───────────────────────────────────────────────────────
+
+impl<'ctx> KleeEncoder<'ctx> {
+    /// The conjunction of `constraints`, simplified by Z3 and printed as
+    /// source-level conditions (`b == 0 ∧ a > 0`). Conjuncts already implied
+    /// by the input bounds are left out; "true" means every input in bounds.
+    pub fn describe(&self, constraints: &[Bool<'ctx>]) -> String {
+        let goal = Goal::new(self.ctx, false, false, false);
+        for c in constraints {
+            goal.assert(c);
+        }
+        let tactic = Tactic::new(self.ctx, "simplify")
+            .and_then(&Tactic::new(self.ctx, "ctx-solver-simplify"))
+            .and_then(&Tactic::new(self.ctx, "simplify"));
+        let formulas: Vec<Bool<'ctx>> = match tactic.apply(&goal, None) {
+            Ok(result) => result.list_subgoals().flat_map(|g| g.get_formulas::<Bool>()).collect(),
+            Err(_)     => constraints.to_vec(),
+        };
+
+        let bounds = self.bounds();
+        let shown: Vec<String> = formulas.iter()
+            .filter(|f| {
+                let solver = Solver::new(self.ctx);
+                for b in &bounds {
+                    solver.assert(b);
+                }
+                solver.assert(&f.not());
+                solver.check() != SatResult::Unsat
+            })
+            .flat_map(|f| conjuncts(f))
+            .map(|f| human(&Dynamic::from_ast(&f), true))
+            .collect();
+        if shown.is_empty() { "true".into() } else { shown.join(" ∧ ") }
+    }
+}
+
+impl<'ctx> KleeEncoder<'ctx> {
+    /// A bit-vector term such as a path's return value, simplified by Z3 and
+    /// printed like `describe` prints conditions (`(x * 2)`).
+    pub fn describe_value(&self, v: &BV<'ctx>) -> String {
+        human(&Dynamic::from_ast(&v.simplify()), self.result_signed)
+    }
+}
+
+fn conjuncts<'ctx>(f: &Bool<'ctx>) -> Vec<Bool<'ctx>> {
+    if f.decl().kind() == DeclKind::AND {
+        f.children().iter().filter_map(|c| c.as_bool()).flat_map(|c| conjuncts(&c)).collect()
+    } else {
+        vec![f.clone()]
+    }
+}
+
+/// A Z3 term over the input slots as a C-like expression. Numerals print
+/// signed unless an unsigned comparison reads them.
+fn human(e: &Dynamic, signed: bool) -> String {
+    let args = e.children();
+    let arg = |i: usize, signed: bool| args.get(i).map_or_else(|| "?".into(), |a| human(a, signed));
+    let infix = |op: &str, signed: bool| {
+        let parts: Vec<String> = (0..args.len()).map(|i| arg(i, signed)).collect();
+        format!("({})", parts.join(&format!(" {} ", op)))
+    };
+    let Ok(decl) = e.safe_decl() else { return e.to_string() };
+    match decl.kind() {
+        DeclKind::TRUE  => "true".into(),
+        DeclKind::FALSE => "false".into(),
+        DeclKind::BNUM  => {
+            let bv = e.as_bv();
+            let (w, raw) = (bv.as_ref().map_or(64, |v| v.get_size()), bv.and_then(|v| v.as_u64()));
+            match raw {
+                Some(raw) if signed && w <= 64 && raw >> (w - 1) & 1 == 1 => {
+                    ((raw as i128) - (1i128 << w)).to_string()
+                }
+                Some(raw) => raw.to_string(),
+                None      => e.to_string(),
+            }
+        }
+        DeclKind::UNINTERPRETED if args.is_empty() => decl.name(),
+        DeclKind::NOT => match args.first().and_then(|a| a.safe_decl().ok().map(|d| d.kind())) {
+            Some(k) if negated_compare(k).is_some() => {
+                let inner = &args[0];
+                let op = negated_compare(k).unwrap_or("?");
+                compare(inner, op, is_signed_compare(k))
+            }
+            _ => format!("!{}", arg(0, signed)),
+        },
+        DeclKind::AND  => (0..args.len()).map(|i| arg(i, signed)).collect::<Vec<_>>().join(" ∧ "),
+        DeclKind::OR   => infix("∨", signed),
+        DeclKind::EQ   => compare(e, "==", signed),
+        DeclKind::DISTINCT => compare(e, "!=", signed),
+        k @ (DeclKind::SLEQ | DeclKind::SLT | DeclKind::SGEQ | DeclKind::SGT
+            | DeclKind::ULEQ | DeclKind::ULT | DeclKind::UGEQ | DeclKind::UGT) => {
+            compare(e, compare_op(k), is_signed_compare(k))
+        }
+        DeclKind::BADD => infix("+", signed),
+        DeclKind::BSUB => infix("-", signed),
+        DeclKind::BMUL => infix("*", signed),
+        DeclKind::BSDIV | DeclKind::BUDIV => infix("/", signed),
+        DeclKind::BSREM | DeclKind::BUREM | DeclKind::BSMOD => infix("%", signed),
+        DeclKind::BAND => infix("&", false),
+        DeclKind::BOR  => infix("|", false),
+        DeclKind::BXOR => infix("^", false),
+        DeclKind::BSHL => infix("<<", false),
+        DeclKind::BLSHR | DeclKind::BASHR => infix(">>", false),
+        DeclKind::BNEG => format!("-{}", arg(0, signed)),
+        DeclKind::BNOT => format!("~{}", arg(0, false)),
+        // Widening and narrowing do not change the value a reader cares about
+        DeclKind::SIGN_EXT => arg(0, true),
+        DeclKind::ZERO_EXT => arg(0, false),
+        DeclKind::EXTRACT  => arg(0, signed),
+        DeclKind::ITE => format!("({} ? {} : {})", arg(0, signed), arg(1, signed), arg(2, signed)),
+        _ => e.to_string(),
+    }
+}
+
+/// `lhs op rhs`, with a constant moved to the right-hand side.
+fn compare(e: &Dynamic, op: &str, signed: bool) -> String {
+    let args = e.children();
+    let (Some(l), Some(r)) = (args.first(), args.get(1)) else { return e.to_string() };
+    let is_num = |a: &Dynamic| a.safe_decl().is_ok_and(|d| d.kind() == DeclKind::BNUM);
+    if is_num(l) && !is_num(r) {
+        format!("{} {} {}", human(r, signed), flip_compare(op), human(l, signed))
+    } else {
+        format!("{} {} {}", human(l, signed), op, human(r, signed))
+    }
+}
+
+fn compare_op(k: DeclKind) -> &'static str {
+    match k {
+        DeclKind::SLEQ | DeclKind::ULEQ => "<=",
+        DeclKind::SLT  | DeclKind::ULT  => "<",
+        DeclKind::SGEQ | DeclKind::UGEQ => ">=",
+        DeclKind::SGT  | DeclKind::UGT  => ">",
+        DeclKind::EQ                    => "==",
+        _                               => "?",
+    }
+}
+
+/// The operator of `!(a op b)` for a comparison kind.
+fn negated_compare(k: DeclKind) -> Option<&'static str> {
+    Some(match k {
+        DeclKind::SLEQ | DeclKind::ULEQ => ">",
+        DeclKind::SLT  | DeclKind::ULT  => ">=",
+        DeclKind::SGEQ | DeclKind::UGEQ => "<",
+        DeclKind::SGT  | DeclKind::UGT  => "<=",
+        DeclKind::EQ                    => "!=",
+        _                               => return None,
+    })
+}
+
+fn is_signed_compare(k: DeclKind) -> bool {
+    !matches!(k, DeclKind::ULEQ | DeclKind::ULT | DeclKind::UGEQ | DeclKind::UGT)
+}
+
+fn flip_compare(op: &str) -> &str {
+    match op {
+        "<=" => ">=",
+        "<"  => ">",
+        ">=" => "<=",
+        ">"  => "<",
+        other => other,
+    }
+}
+
+// ── Translation ───────────────────────────────────────────────────────────────
+
+/// A KLEE term: w1 conditions are Z3 booleans, everything else a bit-vector.
+enum Term<'ctx> {
+    B(Bool<'ctx>),
+    V(BV<'ctx>),
+}
+
+struct Scope<'a, 'ctx> {
+    enc:    &'a KleeEncoder<'ctx>,
+    labels: &'a HashMap<String, Sexp>,
+}
+
+impl<'a, 'ctx> Scope<'a, 'ctx> {
+    fn to_bool(&self, t: Term<'ctx>) -> Result<Bool<'ctx>, String> {
+        match t {
+            Term::B(b) => Ok(b),
+            Term::V(v) if v.get_size() == 1 => Ok(v._eq(&BV::from_u64(self.enc.ctx, 1, 1))),
+            Term::V(v) => Err(format!("w{} value used as a condition", v.get_size())),
+        }
+    }
+
+    fn to_bv(&self, t: Term<'ctx>) -> BV<'ctx> {
+        match t {
+            Term::V(v) => v,
+            Term::B(b) => b.ite(&BV::from_u64(self.enc.ctx, 1, 1), &BV::from_u64(self.enc.ctx, 0, 1)),
+        }
+    }
+
+    fn bv(&self, e: &Sexp, width: Option<u32>) -> Result<BV<'ctx>, String> {
+        self.term(e, width).map(|t| self.to_bv(t))
+    }
+
+    fn constant(&self, v: i128, width: u32) -> Result<BV<'ctx>, String> {
+        if width == 0 { return Err("zero-width constant".into()); }
+        Ok(if v < 0 || v <= i64::MAX as i128 {
+            BV::from_i64(self.enc.ctx, v as i64, width)
+        } else if v <= u64::MAX as i128 {
+            BV::from_u64(self.enc.ctx, v as u64, width)
+        } else {
+            BV::from_str(self.enc.ctx, width, &v.to_string()).ok_or("constant out of range")?
+        })
+    }
+
+    fn term(&self, e: &Sexp, width: Option<u32>) -> Result<Term<'ctx>, String> {
+        let items = match e {
+            Sexp::Atom(a) => return self.atom(a, width),
+            Sexp::List(items) => items,
+        };
+        let op = match items.first() {
+            Some(Sexp::Atom(op)) => op.as_str(),
+            _                    => return Err("malformed expression".into()),
+        };
+        // Most operators spell their result width right after the name
+        let w    = items.get(1).and_then(width_token);
+        let args = if w.is_some() { &items[2..] } else { &items[1..] };
+
+        if let Some(cw) = width_token(&items[0]) {
+            // (w32 5)
+            let v = match items.get(1) {
+                Some(Sexp::Atom(a)) => parse_number(a).ok_or("malformed constant")?,
+                _                   => return Err("malformed constant".into()),
+            };
+            return self.constant(v, cw).map(Term::V);
+        }
+
+        match (op, args) {
+            ("Read" | "ReadLSB" | "ReadMSB", [Sexp::Atom(off), Sexp::Atom(array)]) => {
+                let w   = w.ok_or("read without a width")?;
+                let off = off.parse::<u32>().map_err(|_| "symbolic read index is unsupported".to_string())?;
+                let n   = w.div_ceil(8);
+                // Most significant byte first, as concat expects
+                let order: Vec<u32> = if op == "ReadMSB" { (0..n).collect() } else { (0..n).rev().collect() };
+                let mut acc: Option<BV> = None;
+                for i in order {
+                    let b = self.enc.byte(array, off + i)?;
+                    acc = Some(match acc { Some(hi) => hi.concat(&b), None => b });
+                }
+                let v = acc.ok_or("empty read")?;
+                Ok(Term::V(if w % 8 == 0 { v } else { v.extract(w - 1, 0) }))
+            }
+            ("Concat", [hi, lo]) => Ok(Term::V(self.bv(hi, None)?.concat(&self.bv(lo, None)?))),
+            ("Extract", [Sexp::Atom(off), x]) => {
+                let w   = w.ok_or("extract without a width")?;
+                let off = off.parse::<u32>().map_err(|_| "malformed extract offset".to_string())?;
+                Ok(Term::V(self.bv(x, None)?.extract(off + w - 1, off)))
+            }
+            ("ZExt" | "SExt" | "Trunc", [x]) => {
+                let w = w.ok_or("cast without a width")?;
+                let v = self.bv(x, None)?;
+                let from = v.get_size();
+                Ok(Term::V(match op {
+                    _ if w == from      => v,
+                    "Trunc"             => v.extract(w - 1, 0),
+                    _ if w < from       => v.extract(w - 1, 0),
+                    "ZExt"              => v.zero_ext(w - from),
+                    _                   => v.sign_ext(w - from),
+                }))
+            }
+            ("Not", [x]) => Ok(match self.term(x, w)? {
+                Term::B(b) => Term::B(b.not()),
+                Term::V(v) => Term::V(v.bvnot()),
+            }),
+            ("And" | "Or" | "Xor", [a, b]) => {
+                match self.operands(a, b, w)? {
+                    (Term::B(x), Term::B(y)) => Ok(Term::B(match op {
+                        "And" => Bool::and(self.enc.ctx, &[&x, &y]),
+                        "Or"  => Bool::or(self.enc.ctx, &[&x, &y]),
+                        _     => x.xor(&y),
+                    })),
+                    (x, y) => {
+                        let (x, y) = (self.to_bv(x), self.to_bv(y));
+                        Ok(Term::V(match op {
+                            "And" => x.bvand(&y),
+                            "Or"  => x.bvor(&y),
+                            _     => x.bvxor(&y),
+                        }))
+                    }
+                }
+            }
+            ("Add" | "Sub" | "Mul" | "UDiv" | "SDiv" | "URem" | "SRem" | "Shl" | "LShr" | "AShr", [a, b]) => {
+                let (x, y) = self.operands(a, b, w)?;
+                let (x, y) = (self.to_bv(x), self.to_bv(y));
+                Ok(Term::V(match op {
+                    "Add"  => x.bvadd(&y),
+                    "Sub"  => x.bvsub(&y),
+                    "Mul"  => x.bvmul(&y),
+                    "UDiv" => x.bvudiv(&y),
+                    "SDiv" => x.bvsdiv(&y),
+                    "URem" => x.bvurem(&y),
+                    "SRem" => x.bvsrem(&y),
+                    "Shl"  => x.bvshl(&y),
+                    "LShr" => x.bvlshr(&y),
+                    _      => x.bvashr(&y),
+                }))
+            }
+            ("Select", [c, t, f]) => {
+                let c = self.term(c, Some(1)).and_then(|c| self.to_bool(c))?;
+                match self.operands(t, f, w)? {
+                    (Term::B(x), Term::B(y)) => Ok(Term::B(c.ite(&x, &y))),
+                    (x, y) => Ok(Term::V(c.ite(&self.to_bv(x), &self.to_bv(y)))),
+                }
+            }
+            ("Eq" | "Ne", [a, b]) => {
+                let eq = match self.operands(a, b, None)? {
+                    (Term::B(x), Term::B(y)) => x._eq(&y),
+                    (x, y)                   => self.to_bv(x)._eq(&self.to_bv(y)),
+                };
+                Ok(Term::B(if op == "Ne" { eq.not() } else { eq }))
+            }
+            ("Ult" | "Ule" | "Ugt" | "Uge" | "Slt" | "Sle" | "Sgt" | "Sge", [a, b]) => {
+                let (x, y) = self.operands(a, b, None)?;
+                let (x, y) = (self.to_bv(x), self.to_bv(y));
+                Ok(Term::B(match op {
+                    "Ult" => x.bvult(&y), "Ule" => x.bvule(&y),
+                    "Ugt" => x.bvugt(&y), "Uge" => x.bvuge(&y),
+                    "Slt" => x.bvslt(&y), "Sle" => x.bvsle(&y),
+                    "Sgt" => x.bvsgt(&y), _     => x.bvsge(&y),
+                }))
+            }
+            _ => Err(format!("unsupported KLEE operator '{}'", op)),
+        }
+    }
+
+    /// Both operands of a binary operator; a bare literal takes the width
+    /// of the other side when the operator does not spell one.
+    fn operands(&self, a: &Sexp, b: &Sexp, width: Option<u32>) -> Result<(Term<'ctx>, Term<'ctx>), String> {
+        if width.is_none() && is_literal(a) {
+            let y = self.term(b, None)?;
+            let x = self.term(a, Some(self.width_of(&y)))?;
+            return Ok((x, y));
+        }
+        let x = self.term(a, width)?;
+        let y = self.term(b, width.or(Some(self.width_of(&x))))?;
+        Ok((x, y))
+    }
+
+    fn width_of(&self, t: &Term<'ctx>) -> u32 {
+        match t {
+            Term::B(_) => 1,
+            Term::V(v) => v.get_size(),
+        }
+    }
+
+    fn atom(&self, a: &str, width: Option<u32>) -> Result<Term<'ctx>, String> {
+        match a {
+            "true"  => return Ok(Term::B(Bool::from_bool(self.enc.ctx, true))),
+            "false" => return Ok(Term::B(Bool::from_bool(self.enc.ctx, false))),
+            _ => {}
+        }
+        if let Some(v) = parse_number(a) {
+            let w = width.ok_or_else(|| format!("constant {} without a width", a))?;
+            return self.constant(v, w).map(Term::V);
+        }
+        match self.labels.get(a) {
+            Some(e) => self.term(e, width),
+            None    => Err(format!("undefined label '{}'", a)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FloatCompare, KleeOptions, Sampling, Utf8Mode};
+    use crate::workspace::Workspace;
+    use z3::Config;
+
+    fn config(bounds: &[&str], return_type: Option<VarType>) -> AnalysisConfig {
+        AnalysisConfig {
+            c_file:           String::new(),
+            rust_file:        String::new(),
+            function_name:    "f".into(),
+            bounds:           bounds.iter().map(|b| InputBound::parse(b).unwrap()).collect(),
+            default_window:   None,
+            utf8:             Utf8Mode::Ascii,
+            return_struct:    None,
+            outputs:          Vec::new(),
+            error_map:        None,
+            return_type,
+            float_compare:    FloatCompare::default(),
+            miter:            false,
+            exhaustive_limit: 0,
+            sampling:         Sampling::default(),
+            collect_all:      false,
+            max_paths:        0,
+            timeout:          10,
+            klee:             KleeOptions::default(),
+            workspace:        Workspace {
+                run_id: "test".into(), dir: "".into(), output_dir: "".into(), keep: true, sources: Default::default(),
+            },
+            cache:            false,
+        }
+    }
+
+    /// A model of the bounds and `constraints`, or `None` when unsatisfiable.
+    fn solve(enc: &KleeEncoder, constraints: &[&str]) -> Option<Vec<(String, i64)>> {
+        let constraints: Vec<String> = constraints.iter().map(|c| c.to_string()).collect();
+        let path = enc.encode_path(&constraints, None);
+        assert!(path.unsupported.is_empty(), "{:?}", path.unsupported);
+        let solver = Solver::new(enc.context());
+        for c in enc.bounds().iter().chain(&path.constraints) {
+            solver.assert(c);
+        }
+        (solver.check() == SatResult::Sat).then(|| enc.inputs(&solver.get_model().unwrap()).unwrap())
+    }
+
+    fn value(input: &[(String, i64)], name: &str) -> i64 {
+        input.iter().find(|(n, _)| n == name).unwrap().1
+    }
+
+    #[test]
+    fn parse_sexp_records_labels() {
+        let mut labels = HashMap::new();
+        let e = parse_sexp("(Slt N0:(ReadLSB w32 0 x) (w32 100))", &mut labels).unwrap();
+        assert!(matches!(e, Sexp::List(ref items) if items.len() == 3));
+        assert!(matches!(labels.get("N0"), Some(Sexp::List(items)) if items.len() == 4));
+        assert!(parse_sexp("(Eq 1 (ReadLSB w32 0 x)", &mut labels).is_err());
+        assert!(parse_sexp("(Eq 1 x) 2", &mut labels).is_err());
+        assert!(parse_sexp("(Read w8 0 [1=2]@x)", &mut labels).is_err());
+    }
+
+    #[test]
+    fn labels_may_be_used_before_their_definition() {
+        let cfg = config(&["x:-100:100"], None);
+        let ctx = Context::new(&Config::new());
+        let enc = KleeEncoder::new(&ctx, &cfg);
+        let constraints = vec![
+            "(Sle N0 (w32 10))".to_string(),
+            "(Eq false (Slt N0:(ReadLSB w32 0 x) (w32 0)))".to_string(),
+        ];
+        let path = enc.encode_path(&constraints, Some("(Mul w32 (w32 2) N0)"));
+        assert!(path.unsupported.is_empty(), "{:?}", path.unsupported);
+        assert_eq!(path.constraints.len(), 2);
+        assert!(path.ret.is_some());
+        assert_eq!(enc.range_of(&path.constraints, "x"), Some((0, 10)));
+    }
+
+    #[test]
+    fn read_lsb_and_msb_differ_in_byte_order() {
+        let cfg = config(&["x:u32:0:4294967295"], None);
+        let ctx = Context::new(&Config::new());
+        let enc = KleeEncoder::new(&ctx, &cfg);
+        let lsb = solve(&enc, &["(Eq (w32 1) (ReadLSB w32 0 x))"]).unwrap();
+        assert_eq!(value(&lsb, "x"), 1);
+        let msb = solve(&enc, &["(Eq (w32 1) (ReadMSB w32 0 x))"]).unwrap();
+        assert_eq!(value(&msb, "x"), 0x0100_0000);
+        // A single byte read picks out the low byte
+        let byte = solve(&enc, &["(Eq (w32 258) (ReadLSB w32 0 x))", "(Eq (w8 2) (Read w8 0 x))"]);
+        assert!(byte.is_some());
+        assert!(solve(&enc, &["(Eq (w32 258) (ReadLSB w32 0 x))", "(Eq (w8 1) (Read w8 0 x))"]).is_none());
+    }
+
+    #[test]
+    fn array_reads_map_to_element_slots() {
+        let cfg = config(&["arr:i32[2]:-10:10"], None);
+        let ctx = Context::new(&Config::new());
+        let enc = KleeEncoder::new(&ctx, &cfg);
+        let input = solve(&enc, &["(Eq (w32 7) (ReadLSB w32 4 arr))"]).unwrap();
+        assert_eq!(value(&input, "arr[1]"), 7);
+        let path = enc.encode_path(&["(Eq (w32 7) (ReadLSB w32 8 arr))".to_string()], None);
+        assert_eq!(path.unsupported.len(), 1);
+        let path = enc.encode_path(&["(Eq (w32 7) (ReadLSB w32 0 y))".to_string()], None);
+        assert!(path.unsupported[0].contains("unknown object 'y'"), "{:?}", path.unsupported);
+    }
+
+    #[test]
+    fn bare_literals_take_the_other_operand_width() {
+        let cfg = config(&["x:i32:-100:100"], None);
+        let ctx = Context::new(&Config::new());
+        let enc = KleeEncoder::new(&ctx, &cfg);
+        let input = solve(&enc, &["(Eq 5 (ReadLSB w32 0 x))"]).unwrap();
+        assert_eq!(value(&input, "x"), 5);
+        let input = solve(&enc, &["(Eq (ReadLSB w32 0 x) 4294967295)"]).unwrap();
+        assert_eq!(value(&input, "x"), -1);
+        // x + (-1) < 10 unsigned: only x in 1..=10 (x = 0 wraps around)
+        let path = enc.encode_path(&["(Ult (Add w32 4294967295 N0:(ReadLSB w32 0 x)) 10)".to_string()], None);
+        assert!(path.unsupported.is_empty(), "{:?}", path.unsupported);
+        assert_eq!(enc.range_of(&path.constraints, "x"), Some((1, 10)));
+    }
+
+    #[test]
+    fn range_of_respects_signedness() {
+        let cfg = config(&["x:-100:100", "u:u8:0:255"], None);
+        let ctx = Context::new(&Config::new());
+        let enc = KleeEncoder::new(&ctx, &cfg);
+        let path = enc.encode_path(&[
+            "(Slt (ReadLSB w32 0 x) (w32 10))".to_string(),
+            "(Eq false (Slt (ReadLSB w32 0 x) (w32 4294967291)))".to_string(),
+            "(Ult (w8 200) (Read w8 0 u))".to_string(),
+        ], None);
+        assert!(path.unsupported.is_empty(), "{:?}", path.unsupported);
+        assert_eq!(enc.range_of(&path.constraints, "x"), Some((-5, 9)));
+        assert_eq!(enc.range_of(&path.constraints, "u"), Some((201, 255)));
+        let none = enc.encode_path(&["(Slt (ReadLSB w32 0 x) (w32 4294967196))".to_string()], None);
+        assert_eq!(enc.range_of(&none.constraints, "x"), None);
+    }
+
+    #[test]
+    fn branch_constants_read_both_signs() {
+        let constraints = vec![
+            "(Slt (ReadLSB w32 0 x) (w32 4294967291))".to_string(),
+            "(Eq 255 (Read w8 0 c))".to_string(),
+            "(Eq (w32 7) (ReadLSB w32 4 arr))".to_string(),
+        ];
+        let found = branch_constants(&constraints);
+        // Width-tagged constants are read signed at their width
+        assert!(found.contains(&-5));
+        assert!(!found.contains(&4294967291));
+        // Bare literals are ambiguous: both readings
+        assert!(found.contains(&255) && found.contains(&-1));
+        assert!(found.contains(&7));
+        // Read offsets are positions, not values
+        assert!(!found.contains(&4));
+    }
+
+    #[test]
+    fn returns_differ_widens_by_result_signedness() {
+        let ctx = Context::new(&Config::new());
+        let differs = |ty: VarType| {
+            let cfg = config(&["x:0:1"], Some(ty));
+            let enc = KleeEncoder::new(&ctx, &cfg);
+            let solver = Solver::new(&ctx);
+            solver.assert(&enc.returns_differ(&BV::from_i64(&ctx, -1, 8), &BV::from_i64(&ctx, -1, 32)));
+            solver.check() == SatResult::Sat
+        };
+        // 0xff sign-extends to -1 for a signed result, zero-extends to 255 otherwise
+        assert!(!differs(VarType::I32));
+        assert!(differs(VarType::U32));
+    }
+
+    #[test]
+    fn unencodable_return_values_are_reported() {
+        let cfg = config(&["x:-10:10"], Some(VarType::I32));
+        let ctx = Context::new(&Config::new());
+        let enc = KleeEncoder::new(&ctx, &cfg);
+        let path = enc.encode_path(&[], Some("(FAdd w32 (ReadLSB w32 0 x) (w32 1))"));
+        assert!(path.ret.is_none());
+        assert!(path.unsupported[0].contains("unsupported KLEE operator 'FAdd'"), "{:?}", path.unsupported);
+        let path = enc.encode_path(&[], Some("(Add w32 (ReadLSB w32 0 x)"));
+        assert!(path.ret.is_none());
+        assert!(path.unsupported[0].starts_with("return value:"), "{:?}", path.unsupported);
+        // A bare literal return takes the result's width
+        let path = enc.encode_path(&[], Some("42"));
+        assert_eq!(path.ret.map(|r| r.get_size()), Some(32));
+    }
+}
@@ -3,74 +3,189 @@
 // Module 5: Symbolic Execution using KLEE
 // ═══════════════════════════════════════════════════════
 
+use crate::cache;
+use crate::toolchain;
+use crate::workspace::Workspace;
 use crate::types::{
     AnalysisConfig, PathSummary, ProgramKind, CheckerError, ObservableEffects,
+    InputBound, InputKind, KleeOptions,
 };
+use crate::parallel::both_sides;
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::io::Read;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SymbolicSummaries {
     pub c_summaries:    Vec<PathSummary>,
     pub rust_summaries: Vec<PathSummary>,
+    /// Why KLEE stopped short of every C path (time, memory, fork, depth or
+    /// test limit); `None` when it explored them all
+    pub c_cut_short:    Option<String>,
+    pub rust_cut_short: Option<String>,
+    /// Set instead of the per-language summaries in single-miter mode
+    pub miter:          Option<MiterRun>,
+}
+
+impl SymbolicSummaries {
+    /// Every feasible path of both programs is in the summaries.
+    pub fn coverage_complete(&self) -> bool {
+        self.cut_short().is_none()
+    }
+
+    /// What stopped KLEE early, per side, when coverage is incomplete.
+    pub fn cut_short(&self) -> Option<String> {
+        if let Some(run) = &self.miter {
+            return run.cut_short.as_ref().map(|r| format!("miter: {}", r));
+        }
+        let sides: Vec<String> = [("C", &self.c_cut_short), ("Rust", &self.rust_cut_short)].iter()
+            .filter_map(|(side, reason)| reason.as_ref().map(|r| format!("{}: {}", side, r)))
+            .collect();
+        (!sides.is_empty()).then(|| sides.join("; "))
+    }
+}
+
+/// One KLEE run over the linked C + Rust module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MiterRun {
+    pub paths:    Vec<PathSummary>,
+    /// Paths on which `klee_assert(__c == __rust)` failed
+    pub failures: Vec<PathSummary>,
+    /// Other KLEE errors as (path id, kind): either side may have crashed
+    pub errors:   Vec<(String, String)>,
+    /// Why KLEE stopped short of every miter path, if it did
+    pub cut_short: Option<String>,
 }
 
 pub fn execute(
     config: &AnalysisConfig,
     files:  &crate::instrumentor::InstrumentedFiles,
 ) -> Result<SymbolicSummaries> {
-    println!("  Running symbolic execution on C IR...");
-    let c_summaries = run_symbolic_pipeline(
-        &files.c_instrumented_path,
-        &config.function_name,
-        config.max_paths,
-        config.timeout,
-        ProgramKind::C,
-    )?;
-    println!("    → Found {} C paths", c_summaries.len());
-
-    println!("  Running symbolic execution on Rust IR...");
-    let rust_summaries = run_symbolic_pipeline(
-        &files.rust_instrumented_path,
-        &config.function_name,
-        config.max_paths,
-        config.timeout,
-        ProgramKind::Rust,
+    if let Some(summaries) = cache::load_summaries(config) {
+        println!("  ✓ Reusing cached path summaries ({} C, {} Rust)",
+            summaries.c_summaries.len(), summaries.rust_summaries.len());
+        return Ok(summaries);
+    }
+
+    // Both KLEE runs share one deadline, so --timeout bounds the whole stage
+    println!("  Running symbolic execution on C and Rust IR in parallel...");
+    let deadline = Instant::now() + Duration::from_secs(config.timeout as u64);
+    let side = |ir_path: &str, kind: ProgramKind| run_symbolic_pipeline(ir_path, config, deadline, kind);
+    let ((c_summaries, c_cut_short), (rust_summaries, rust_cut_short)) = both_sides(
+        || side(&files.c_instrumented_path, ProgramKind::C),
+        || side(&files.rust_instrumented_path, ProgramKind::Rust),
     )?;
-    println!("    → Found {} Rust paths", rust_summaries.len());
+    println!("    → Found {} C paths, {} Rust paths", c_summaries.len(), rust_summaries.len());
 
     if c_summaries.len() <= 1 || rust_summaries.len() <= 1 {
         println!("    ⚠ Warning: KLEE may have missed some paths.");
         println!("      C paths: {}, Rust paths: {}", c_summaries.len(), rust_summaries.len());
     }
 
-    Ok(SymbolicSummaries { c_summaries, rust_summaries })
+    let summaries = SymbolicSummaries { c_summaries, rust_summaries, c_cut_short, rust_cut_short, miter: None };
+    cache::store_summaries(config, &summaries);
+    Ok(summaries)
+}
+
+/// Single-miter mode: one KLEE run whose assertion failures are divergences.
+pub fn execute_miter(config: &AnalysisConfig, miter_bc: &str) -> Result<SymbolicSummaries> {
+    if let Some(summaries) = cache::load_summaries(config) {
+        println!("  ✓ Reusing cached miter paths");
+        return Ok(summaries);
+    }
+
+    println!("  Running symbolic execution on the C + Rust miter...");
+    let kind = ProgramKind::Miter;
+    let deadline = Instant::now() + Duration::from_secs(config.timeout as u64);
+    let klee_out_dir = stage_051(miter_bc, &config.workspace, &kind)?;
+    let (test_numbers, cut_short) = stage_052(
+        miter_bc, &config.function_name, &config.klee, config.max_paths, deadline, &kind, &klee_out_dir,
+    )?;
+    let errors    = error_tests(&klee_out_dir);
+    let raw_paths = stage_053(&klee_out_dir, &test_numbers, &config.bounds, &kind)?;
+    let paths     = stage_054(raw_paths, &kind);
+
+    let failed: Vec<String> = errors.iter()
+        .filter(|(_, k)| k == "assert")
+        .map(|(n, _)| path_id(&kind, *n))
+        .collect();
+    let failures: Vec<PathSummary> = paths.iter()
+        .filter(|p| failed.contains(&p.id))
+        .cloned()
+        .collect();
+    let errors: Vec<(String, String)> = errors.into_iter()
+        .filter(|(_, k)| k != "assert")
+        .map(|(n, k)| (path_id(&kind, n), k))
+        .collect();
+    println!("    → {} miter paths, {} assertion failure(s), {} other error(s)",
+        test_numbers.len(), failures.len(), errors.len());
+
+    let summaries = SymbolicSummaries {
+        c_summaries:    Vec::new(),
+        rust_summaries: Vec::new(),
+        c_cut_short:    None,
+        rust_cut_short: None,
+        miter:          Some(MiterRun { paths, failures, errors, cut_short }),
+    };
+    cache::store_summaries(config, &summaries);
+    Ok(summaries)
+}
+
+/// Tests KLEE ended in an error, as (test number, kind) from `testN.<kind>.err`.
+fn error_tests(klee_dir: &str) -> Vec<(usize, String)> {
+    let mut errors: Vec<(usize, String)> = fs::read_dir(klee_dir).into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let rest = name.strip_prefix("test")?.strip_suffix(".err")?;
+            let (num, kind) = rest.split_once('.')?;
+            Some((num.parse().ok()?, kind.to_string()))
+        })
+        .collect();
+    errors.sort();
+    errors
+}
+
+fn path_id(program_kind: &ProgramKind, test_num: usize) -> String {
+    format!("{:?}-{}", program_kind, test_num)
 }
 
 fn run_symbolic_pipeline(
-    ir_path:       &str,
-    function_name: &str,
-    max_paths:     u32,
-    timeout:       u32,
-    program_kind:  ProgramKind,
-) -> Result<Vec<PathSummary>> {
-    let klee_out_dir = stage_051(ir_path, function_name, &program_kind)?;
-    let test_numbers = stage_052(ir_path, function_name, max_paths, timeout, &program_kind, &klee_out_dir)?;
-    let raw_paths    = stage_053(&klee_out_dir, &test_numbers)?;
-    Ok(stage_054(raw_paths, &program_kind))
+    ir_path:      &str,
+    config:       &AnalysisConfig,
+    deadline:     Instant,
+    program_kind: ProgramKind,
+) -> Result<(Vec<PathSummary>, Option<String>)> {
+    let klee_out_dir = stage_051(ir_path, &config.workspace, &program_kind)?;
+    let (test_numbers, cut_short) = stage_052(
+        ir_path, &config.function_name, &config.klee, config.max_paths, deadline, &program_kind, &klee_out_dir,
+    )?;
+    let raw_paths    = stage_053(&klee_out_dir, &test_numbers, &config.bounds, &program_kind)?;
+    Ok((stage_054(raw_paths, &program_kind), cut_short))
+}
+
+fn program_label(program_kind: &ProgramKind) -> &'static str {
+    match program_kind {
+        ProgramKind::C     => "C",
+        ProgramKind::Rust  => "Rust",
+        ProgramKind::Miter => "Miter",
+    }
 }
 
 // ═══════════════════════════════════════════════════════
 // 0.5.1
 // ═══════════════════════════════════════════════════════
-fn stage_051(_ir_path: &str, function_name: &str, program_kind: &ProgramKind) -> Result<String> {
-    let kind_str = match program_kind { ProgramKind::C => "C", ProgramKind::Rust => "Rust" };
-    let dir = format!("/tmp/equivalence_checker/klee_{}_{}", function_name, kind_str);
+fn stage_051(_ir_path: &str, workspace: &Workspace, program_kind: &ProgramKind) -> Result<String> {
+    let kind_str = program_label(program_kind);
+    let dir = workspace.file(&format!("klee_{}", kind_str));
     if Path::new(&dir).exists() { let _ = fs::remove_dir_all(&dir); }
-    println!("    [0.5.1] Symbolic input generation → {}", dir);
+    println!("    [{}] [0.5.1] Symbolic input generation → {}", kind_str, dir);
     Ok(dir)
 }
 
@@ -78,37 +193,50 @@ fn stage_051(_ir_path: &str, function_name: &str, program_kind: &ProgramKind) ->
 // 0.5.2
 // ═══════════════════════════════════════════════════════
 fn stage_052(
-    ir_path: &str, function_name: &str, _max_paths: u32, timeout: u32,
+    ir_path: &str, function_name: &str, options: &KleeOptions, max_paths: u32, deadline: Instant,
     program_kind: &ProgramKind, klee_out_dir: &str,
-) -> Result<Vec<usize>> {
-    println!("    [0.5.2] Path exploration (KLEE, up to {}s)…", timeout);
+) -> Result<(Vec<usize>, Option<String>)> {
+    let tag = program_label(program_kind);
+    let timeout = (deadline.saturating_duration_since(Instant::now()).as_secs_f64().ceil() as u64).max(1);
+    println!("    [{}] [0.5.2] Path exploration (KLEE {}, up to {}s)…", tag, options.search, timeout);
 
-    let mut cmd = Command::new("/home/fathima/klee/build/bin/klee");
+    let klee = &toolchain::get().klee;
+    let mut cmd = klee.command();
     cmd.arg("--output-dir").arg(klee_out_dir)
        .arg("--optimize=false")
-       .arg("--search=dfs")
-       .arg("--max-forks=500")
-       .arg("--max-depth=500")
-       .arg("--max-tests=200")
        .arg(format!("--max-time={}", timeout))
        .arg("--simplify-sym-indices")
        .arg("--write-test-info")
        .arg("--write-paths")
        .arg("--write-kqueries")
-       .arg("--write-smt2s")
-       .arg("--max-memory=1000");
+       .arg("--write-smt2s");
+    for name in options.search.klee_names() {
+        cmd.arg(format!("--search={}", name));
+    }
+    // 0 leaves KLEE unlimited (its own --max-forks=0 would forbid forking)
+    let limits = [
+        ("--max-tests",       max_paths),
+        ("--max-forks",       options.max_forks),
+        ("--max-depth",       options.max_depth),
+        ("--max-memory",      options.max_memory),
+        ("--max-solver-time", options.solver_timeout),
+    ];
+    for (flag, value) in limits.iter().filter(|(_, v)| *v > 0) {
+        cmd.arg(format!("{}={}", flag, value));
+    }
 
+    // The miter's entry point is the C harness main()
     match program_kind {
-        ProgramKind::C    => cmd.arg("--entry-point=main"),
-        ProgramKind::Rust => cmd.arg("--entry-point=klee_harness"),
+        ProgramKind::C | ProgramKind::Miter => cmd.arg("--entry-point=main"),
+        ProgramKind::Rust                   => cmd.arg("--entry-point=klee_harness"),
     };
     cmd.arg(ir_path);
 
-    let output = cmd.output()?;
-    for line in String::from_utf8_lossy(&output.stderr).lines() {
+    let (stderr, killed) = run_until(cmd, deadline).map_err(|e| klee.spawn_error(e))?;
+    for line in stderr.lines() {
         if (line.contains("ERROR") || line.contains("WARNING") || line.contains("KLEE:"))
    && !line.contains("provably false") {
-            println!("    [KLEE] {}", line);
+            println!("    [{}] [KLEE] {}", tag, line);
         }
     }
 
@@ -116,8 +244,8 @@ fn stage_052(
         return Err(CheckerError::SymbolicExecutionError(format!(
             "KLEE failed for {} ({})\n{}",
             function_name,
-            match program_kind { ProgramKind::C => "C", ProgramKind::Rust => "Rust" },
-            String::from_utf8_lossy(&output.stderr)
+            tag,
+            stderr
         )).into());
     }
 
@@ -134,8 +262,69 @@ fn stage_052(
         }
     }
     test_numbers.sort();
-    println!("    [0.5.2] Found {} feasible execution paths", test_numbers.len());
-    Ok(test_numbers)
+    println!("    [{}] [0.5.2] Found {} feasible execution paths", tag, test_numbers.len());
+    let cut_short = if killed {
+        Some("stopped at the overall deadline".to_string())
+    } else {
+        exploration_cut_short(&stderr, test_numbers.len(), max_paths as usize)
+    };
+    if let Some(reason) = &cut_short {
+        println!("    [{}] [0.5.2] ⚠ Path coverage incomplete: {}", tag, reason);
+    }
+    Ok((test_numbers, cut_short))
+}
+
+/// How long KLEE may overrun the deadline before it is killed: it only
+/// checks --max-time between instructions, and a solver query can block it.
+const KILL_GRACE: Duration = Duration::from_secs(10);
+
+/// Run `cmd` to completion or until `deadline` + `KILL_GRACE`, returning its
+/// stderr and whether it had to be killed.
+fn run_until(mut cmd: Command, deadline: Instant) -> std::io::Result<(String, bool)> {
+    let mut child = cmd.stdout(Stdio::null()).stderr(Stdio::piped()).spawn()?;
+    let mut pipe  = child.stderr.take();
+    let reader = std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(p) = pipe.as_mut() { let _ = p.read_to_end(&mut bytes); }
+        String::from_utf8_lossy(&bytes).to_string()
+    });
+    let mut killed = false;
+    while child.try_wait()?.is_none() {
+        if Instant::now() > deadline + KILL_GRACE {
+            let _ = child.kill();
+            let _ = child.wait();
+            killed = true;
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Ok((reader.join().unwrap_or_default(), killed))
+}
+
+/// Why KLEE stopped before exploring every path, judging by its log.
+fn exploration_cut_short(stderr: &str, tests: usize, max_tests: usize) -> Option<String> {
+    let markers = [
+        ("HaltTimer invoked",       "time limit reached"),
+        ("halting execution",       "execution halted early"),
+        ("max-forks reached",       "fork limit reached"),
+        ("max-depth exceeded",      "depth limit reached"),
+        ("over memory cap",         "memory limit reached"),
+        ("skipping fork",           "forks were skipped"),
+    ];
+    if let Some((_, reason)) = markers.iter().find(|(m, _)| stderr.contains(m)) {
+        return Some(reason.to_string());
+    }
+    let partial = stderr.lines()
+        .find_map(|l| l.split("partially completed paths = ").nth(1))
+        .and_then(|n| n.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    if partial > 0 {
+        return Some(format!("{} partially completed path(s)", partial));
+    }
+    if max_tests > 0 && tests >= max_tests {
+        return Some(format!("test limit of {} reached", max_tests));
+    }
+    None
 }
 
 // ═══════════════════════════════════════════════════════
@@ -147,12 +336,17 @@ struct RawPathData {
     return_expr: Option<String>,
     witness:     Vec<(String, i64)>,
     label_map:   HashMap<String, String>,
+    error:       Option<String>,
 }
 
-fn stage_053(klee_dir: &str, test_numbers: &[usize]) -> Result<Vec<RawPathData>> {
-    println!("    [0.5.3] Extracting path constraints & symbolic observables…");
+fn stage_053(
+    klee_dir: &str, test_numbers: &[usize], bounds: &[InputBound], program_kind: &ProgramKind,
+) -> Result<Vec<RawPathData>> {
+    let tag = program_label(program_kind);
+    println!("    [{}] [0.5.3] Extracting path constraints & symbolic observables…", tag);
 
     let mut raw_paths = Vec::new();
+    let errors: HashMap<usize, String> = error_tests(klee_dir).into_iter().collect();
 
     for &num in test_numbers {
         let kquery_path = format!("{}/test{:06}.kquery", klee_dir, num);
@@ -165,9 +359,9 @@ fn stage_053(klee_dir: &str, test_numbers: &[usize]) -> Result<Vec<RawPathData>>
         };
 
         let witness = if Path::new(&ktest_path).exists() {
-            let w = parse_ktest_binary(Path::new(&ktest_path))?;
+            let w = parse_ktest_binary(Path::new(&ktest_path), bounds)?;
             if w.is_empty() {
-                parse_ktest_via_tool(Path::new(&ktest_path)).unwrap_or_default()
+                parse_ktest_via_tool(Path::new(&ktest_path), bounds).unwrap_or_default()
             } else {
                 w
             }
@@ -176,7 +370,8 @@ fn stage_053(klee_dir: &str, test_numbers: &[usize]) -> Result<Vec<RawPathData>>
         };
 
         println!(
-            "      test {:06}: {} constraints, ret_expr={:?}, labels={:?}, witness={:?}",
+            "    [{}]   test {:06}: {} constraints, ret_expr={:?}, labels={:?}, witness={:?}",
+            tag,
             num,
             constraints.len(),
             return_expr.as_deref().map(|s| &s[..s.len().min(60)]),
@@ -184,7 +379,8 @@ fn stage_053(klee_dir: &str, test_numbers: &[usize]) -> Result<Vec<RawPathData>>
             witness,
         );
 
-        raw_paths.push(RawPathData { test_num: num, constraints, return_expr, witness, label_map });
+        let error = errors.get(&num).cloned();
+        raw_paths.push(RawPathData { test_num: num, constraints, return_expr, witness, label_map, error });
     }
 
     Ok(raw_paths)
@@ -194,18 +390,20 @@ fn stage_053(klee_dir: &str, test_numbers: &[usize]) -> Result<Vec<RawPathData>>
 // 0.5.4
 // ═══════════════════════════════════════════════════════
 fn stage_054(raw_paths: Vec<RawPathData>, program_kind: &ProgramKind) -> Vec<PathSummary> {
-    println!("    [0.5.4] Constructing path summaries…");
+    let tag = program_label(program_kind);
+    println!("    [{}] [0.5.4] Constructing path summaries…", tag);
 
     let mut summaries: Vec<PathSummary> = raw_paths
         .into_iter()
         .map(|raw| PathSummary {
-            id:          format!("{:?}-{}", program_kind, raw.test_num),
+            id:          path_id(program_kind, raw.test_num),
             program:     program_kind.clone(),
             constraints: raw.constraints,
             return_expr: raw.return_expr,
             witness:     raw.witness,
             observables: ObservableEffects::default(),
             label_map:   raw.label_map,
+            error:       raw.error,
         })
         .collect();
 
@@ -218,10 +416,11 @@ fn stage_054(raw_paths: Vec<RawPathData>, program_kind: &ProgramKind) -> Vec<Pat
             witness:     vec![],
             observables: ObservableEffects::default(),
             label_map:   HashMap::new(),
+            error:       None,
         });
     }
 
-    println!("    [0.5.4] {} symbolic path summaries constructed", summaries.len());
+    println!("    [{}] [0.5.4] {} symbolic path summaries constructed", tag, summaries.len());
     summaries
 }
 
@@ -241,6 +440,9 @@ fn stage_054(raw_paths: Vec<RawPathData>, program_kind: &ProgramKind) -> Vec<Pat
 //   constraints = all non-result constraints
 //   return_expr = RHS of the result binding (e.g. "N0")
 //   label_map   = {"N0": "a", "N1": "b", ...}
+//
+// Reads at a non-zero offset are array elements:
+//   N2:(ReadLSB w32 8 arr)  →  {"N2": "arr[2]"}
 // ═══════════════════════════════════════════════════════
 
 fn parse_kquery(path: &Path) -> Result<(Vec<String>, Option<String>, HashMap<String, String>)> {
@@ -277,11 +479,15 @@ fn parse_kquery(path: &Path) -> Result<(Vec<String>, Option<String>, HashMap<Str
 
 /// Build a map from KLEE inline labels to variable names.
 /// Scans for patterns like: N0:(ReadLSB w32 0 varname)
-/// and produces {"N0" -> "varname"}.
+/// and produces {"N0" -> "varname"}. Single-byte reads of strings are
+/// spelled N1:(Read w8 3 s).
 fn extract_label_map(section: &str) -> HashMap<String, String> {
     let mut map = HashMap::new();
     let mut s = section;
-    while let Some(colon_pos) = s.find(":(ReadLSB ") {
+    let next_read = |s: &str| [":(ReadLSB ", ":(ReadMSB ", ":(Read "].iter()
+        .filter_map(|p| s.find(p))
+        .min();
+    while let Some(colon_pos) = next_read(s) {
         // The label is the identifier immediately before the colon
         let before = &s[..colon_pos];
         let label: String = before
@@ -310,9 +516,9 @@ fn extract_label_map(section: &str) -> HashMap<String, String> {
         if end > 0 {
             let inner = after[1..end].trim(); // strip outer parens
             // inner = "w32 0 varname" — varname is last token
-            if let Some(varname) = inner.split_whitespace().last() {
+            if let Some(varname) = read_target(inner) {
                 if !label.is_empty() {
-                    map.insert(label, varname.to_string());
+                    map.insert(label, varname);
                 }
             }
             s = &after[end + 1..];
@@ -323,6 +529,24 @@ fn extract_label_map(section: &str) -> HashMap<String, String> {
     map
 }
 
+/// Variable read by a "[ReadLSB] w32 OFFSET name" read body: `name` at
+/// offset 0, otherwise the array element `name[OFFSET / width]`.
+pub fn read_target(inner: &str) -> Option<String> {
+    let toks: Vec<&str> = inner.split_whitespace()
+        .skip_while(|t| t.starts_with("Read"))
+        .collect();
+    let name = *toks.last()?;
+    let width = toks.first()
+        .and_then(|w| w.strip_prefix('w'))
+        .and_then(|w| w.parse::<usize>().ok())
+        .map(|w| (w / 8).max(1));
+    let offset = toks.get(1).and_then(|o| o.parse::<usize>().ok());
+    match (width, offset) {
+        (Some(w), Some(off)) if toks.len() == 3 && off > 0 => Some(format!("{}[{}]", name, off / w)),
+        _ => Some(name.to_string()),
+    }
+}
+
 /// Find the constraint that binds the result variable and extract its RHS.
 /// Looks for: (Eq (ReadLSB w32 0 result) EXPR) or (Eq EXPR (ReadLSB w32 0 result))
 /// Returns EXPR as the return expression string.
@@ -485,7 +709,7 @@ fn get_full_expr(s: &str) -> String {
 
 // ── .ktest binary parser ──────────────────────────────
 
-fn parse_ktest_binary(path: &Path) -> Result<Vec<(String, i64)>> {
+fn parse_ktest_binary(path: &Path, bounds: &[InputBound]) -> Result<Vec<(String, i64)>> {
     let bytes = fs::read(path)?;
     let mut vals = Vec::new();
 
@@ -525,37 +749,47 @@ fn parse_ktest_binary(path: &Path) -> Result<Vec<(String, i64)>> {
         let data = &bytes[pos..pos + data_len];
         pos += data_len;
 
-        let val: Option<i64> = match data_len {
-            1 => Some(data[0] as i8 as i64),
-            2 => Some(i16::from_le_bytes([data[0], data[1]]) as i64),
-            4 => Some(i32::from_le_bytes([data[0], data[1], data[2], data[3]]) as i64),
-            8 => Some(i64::from_le_bytes([
-                data[0], data[1], data[2], data[3],
-                data[4], data[5], data[6], data[7],
-            ])),
-            _ => None,
-        };
-        if let Some(v) = val { vals.push((name, v)); }
+        push_object(&mut vals, name, data, bounds);
     }
     Ok(vals)
 }
 
-// ── ktest-tool fallback ───────────────────────────────
+/// Decode one ktest object. Array and string inputs are split into `name[i]` elements of
+/// the bound's element size; anything else must be a 1/2/4/8-byte scalar.
+fn push_object(vals: &mut Vec<(String, i64)>, name: String, data: &[u8], bounds: &[InputBound]) {
+    let elem_size = bounds.iter()
+        .find(|b| b.name == name && !matches!(b.kind, InputKind::Scalar))
+        .map(|b| b.var_type.byte_size());
+    match elem_size {
+        Some(size) => {
+            for (i, chunk) in data.chunks_exact(size).enumerate() {
+                if let Some(v) = le_value(chunk) { vals.push((format!("{}[{}]", name, i), v)); }
+            }
+        }
+        None => {
+            if let Some(v) = le_value(data) { vals.push((name, v)); }
+        }
+    }
+}
 
-fn parse_ktest_via_tool(path: &Path) -> Result<Vec<(String, i64)>> {
-    let ktest_tool_paths = [
-        "/home/fathima/klee/build/bin/ktest-tool",
-        "/usr/local/bin/ktest-tool",
-        "/usr/bin/ktest-tool",
-        "ktest-tool",
-    ];
-    let tool = ktest_tool_paths
-        .iter()
-        .find(|p| Path::new(p).exists() || **p == "ktest-tool")
-        .copied()
-        .unwrap_or("ktest-tool");
+/// Little-endian scalar, sign-extended from its width.
+fn le_value(data: &[u8]) -> Option<i64> {
+    match data.len() {
+        1 => Some(data[0] as i8 as i64),
+        2 => Some(i16::from_le_bytes([data[0], data[1]]) as i64),
+        4 => Some(i32::from_le_bytes([data[0], data[1], data[2], data[3]]) as i64),
+        8 => Some(i64::from_le_bytes([
+            data[0], data[1], data[2], data[3],
+            data[4], data[5], data[6], data[7],
+        ])),
+        _ => None,
+    }
+}
+
+// ── ktest-tool fallback ───────────────────────────────
 
-    let out = match Command::new(tool).arg(path).output() {
+fn parse_ktest_via_tool(path: &Path, bounds: &[InputBound]) -> Result<Vec<(String, i64)>> {
+    let out = match toolchain::get().ktest_tool.command().arg(path).output() {
         Ok(o)  => o,
         Err(e) => { println!("      [ktest-tool] not available: {}", e); return Ok(vec![]); }
     };
@@ -589,17 +823,9 @@ fn parse_ktest_via_tool(path: &Path) -> Result<Vec<(String, i64)>> {
                     .filter(|s| s.len() >= 2)
                     .filter_map(|s| u8::from_str_radix(&s[..2], 16).ok())
                     .collect();
-                let val: Option<i64> = match hex_bytes.len() {
-                    1 => Some(hex_bytes[0] as i8 as i64),
-                    2 => Some(i16::from_le_bytes([hex_bytes[0], hex_bytes[1]]) as i64),
-                    4 => Some(i32::from_le_bytes([hex_bytes[0], hex_bytes[1], hex_bytes[2], hex_bytes[3]]) as i64),
-                    8 => Some(i64::from_le_bytes([
-                        hex_bytes[0], hex_bytes[1], hex_bytes[2], hex_bytes[3],
-                        hex_bytes[4], hex_bytes[5], hex_bytes[6], hex_bytes[7],
-                    ])),
-                    _ => None,
-                };
-                if let (Some(name), Some(v)) = (current_name.take(), val) { vals.push((name, v)); }
+                if let Some(name) = current_name.take() {
+                    push_object(&mut vals, name, &hex_bytes, bounds);
+                }
             }
         }
     }
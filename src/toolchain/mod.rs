@@ -0,0 +1,381 @@
+// src/toolchain/mod.rs
+// ═══════════════════════════════════════════════════════
+// Toolchain discovery: KLEE, LLVM and rustc
+//
+// Every external tool is resolved once, shared by all stages, from
+// the first of:
+//   1. an environment variable        EQCHECK_KLEE=/opt/klee/bin/klee
+//   2. the config file's "toolchain"  {"toolchain": {"klee": "/opt/klee/bin/klee"}}
+//   3. probing PATH (and a few usual install locations)
+//
+// The config file is --config FILE, else $EQCHECK_CONFIG, else
+// ./eqcheck.json, else ~/.config/eqcheck/config.json. Its "klee" section
+// sets default exploration options ({"klee": {"search": "bfs"}}).
+//
+// LLVM tools are probed with KLEE's LLVM major version first (clang-15,
+// /usr/lib/llvm-15/bin/clang) so the bitcode they emit is one KLEE reads.
+// ═══════════════════════════════════════════════════════
+
+use crate::types::{CheckerError, KleeOptions};
+use anyhow::Result;
+use regex::Regex;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// The parts of the config file this module reads.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    pub toolchain: ToolchainFile,
+    pub klee:      KleeOptions,
+}
+
+/// `"toolchain"` section: a path (or name on PATH) for each tool.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolchainFile {
+    pub klee:           Option<String>,
+    pub ktest_tool:     Option<String>,
+    /// Directory holding `klee/klee.h`
+    pub klee_include:   Option<String>,
+    pub clang:          Option<String>,
+    pub opt:            Option<String>,
+    pub llvm_dis:       Option<String>,
+    pub llvm_link:      Option<String>,
+    pub rustc:          Option<String>,
+    /// Run rustc as `rustup run <toolchain> rustc` (e.g. "1.69.0")
+    pub rust_toolchain: Option<String>,
+    /// LLVM major version to probe for (`clang-15`), instead of KLEE's
+    pub llvm_version:   Option<u32>,
+}
+
+/// Where a tool's command came from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Env(&'static str),
+    ConfigFile,
+    Probed,
+    /// Nothing found: the bare name is used and will fail to start
+    Missing,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tool {
+    pub name:    &'static str,
+    /// Program followed by any fixed leading arguments (`rustup run 1.69.0 rustc`)
+    pub command: Vec<String>,
+    pub source:  Source,
+}
+
+impl Tool {
+    pub fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.command[0]);
+        cmd.args(&self.command[1..]);
+        cmd
+    }
+
+    /// Turn a failure to start the tool into an error that says how to fix it.
+    pub fn spawn_error(&self, e: std::io::Error) -> anyhow::Error {
+        CheckerError::ToolchainError(format!(
+            "could not run {} ({}): {} — set {} or the config file's \"toolchain\" section, \
+             then check with `equivalence_checker doctor`",
+            self.name, self.display(), e, env_var(self.name),
+        )).into()
+    }
+
+    pub fn display(&self) -> String {
+        self.command.join(" ")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toolchain {
+    pub klee:         Tool,
+    pub ktest_tool:   Tool,
+    pub clang:        Tool,
+    pub opt:          Tool,
+    pub llvm_dis:     Tool,
+    pub llvm_link:    Tool,
+    pub rustc:        Tool,
+    /// `-I` directory for `klee/klee.h`, when found
+    pub klee_include: Option<String>,
+    /// Config file the toolchain was read from
+    pub config_file:  Option<String>,
+    /// Exploration defaults from the config file's "klee" section
+    pub klee_options: KleeOptions,
+}
+
+impl Toolchain {
+    pub fn tools(&self) -> [&Tool; 7] {
+        [&self.klee, &self.ktest_tool, &self.clang, &self.opt, &self.llvm_dis, &self.llvm_link, &self.rustc]
+    }
+
+    /// `-I<dir>` for the KLEE headers, or nothing when they were not found.
+    pub fn klee_include_flag(&self) -> Vec<String> {
+        self.klee_include.iter().map(|d| format!("-I{}", d)).collect()
+    }
+}
+
+static TOOLCHAIN: OnceLock<Toolchain> = OnceLock::new();
+
+/// Resolve the toolchain from `config_path` (or the default config file
+/// locations). Later calls keep the first result.
+pub fn init(config_path: Option<&str>) -> Result<&'static Toolchain> {
+    if let Some(tc) = TOOLCHAIN.get() {
+        return Ok(tc);
+    }
+    let tc = load(config_path)?;
+    Ok(TOOLCHAIN.get_or_init(|| tc))
+}
+
+/// The shared toolchain, resolved from the default locations on first use.
+pub fn get() -> &'static Toolchain {
+    TOOLCHAIN.get_or_init(|| load(None).unwrap_or_else(|e| {
+        println!("  ⚠ {} — probing PATH only", e);
+        discover(ConfigFile::default(), None)
+    }))
+}
+
+fn load(config_path: Option<&str>) -> Result<Toolchain> {
+    let path = match config_path {
+        Some(p) => Some(PathBuf::from(p)),
+        None    => default_config_path(),
+    };
+    let config = match &path {
+        Some(p) => {
+            let text = std::fs::read_to_string(p).map_err(|e| CheckerError::ToolchainError(
+                format!("cannot read config file {}: {}", p.display(), e)))?;
+            serde_json::from_str(&text).map_err(|e| CheckerError::ToolchainError(
+                format!("invalid config file {}: {}", p.display(), e)))?
+        }
+        None => ConfigFile::default(),
+    };
+    Ok(discover(config, path.map(|p| p.display().to_string())))
+}
+
+fn default_config_path() -> Option<PathBuf> {
+    if let Ok(p) = std::env::var("EQCHECK_CONFIG") {
+        return Some(PathBuf::from(p));
+    }
+    let local = PathBuf::from("eqcheck.json");
+    if local.is_file() {
+        return Some(local);
+    }
+    let home = PathBuf::from(std::env::var("HOME").ok()?).join(".config/eqcheck/config.json");
+    home.is_file().then_some(home)
+}
+
+// ── Discovery ─────────────────────────────────────────
+
+fn discover(config: ConfigFile, config_file: Option<String>) -> Toolchain {
+    let file = &config.toolchain;
+    let home = std::env::var("HOME").unwrap_or_default();
+
+    let klee = resolve("klee", file.klee.as_deref(), &[
+        "klee".into(),
+        format!("{}/klee/build/bin/klee", home),
+    ]);
+    let klee_dir = located(&klee).and_then(|p| p.parent().map(Path::to_path_buf));
+    let mut ktest_candidates: Vec<String> = klee_dir.iter()
+        .map(|d| d.join("ktest-tool").display().to_string())
+        .collect();
+    ktest_candidates.push("ktest-tool".into());
+    let ktest_tool = resolve("ktest-tool", file.ktest_tool.as_deref(), &ktest_candidates);
+
+    // Match LLVM tools to the LLVM KLEE was built against
+    let llvm = env_value("llvm_version").and_then(|v| v.parse().ok())
+        .or(file.llvm_version)
+        .or_else(|| llvm_major_of(&klee));
+    let llvm_tool = |name: &'static str, configured: Option<&str>| {
+        let mut candidates = Vec::new();
+        if let Some(v) = llvm {
+            candidates.push(format!("{}-{}", name, v));
+            candidates.push(format!("/usr/lib/llvm-{}/bin/{}", v, name));
+        }
+        candidates.push(name.to_string());
+        resolve(name, configured, &candidates)
+    };
+    let clang     = llvm_tool("clang",     file.clang.as_deref());
+    let opt       = llvm_tool("opt",       file.opt.as_deref());
+    let llvm_dis  = llvm_tool("llvm-dis",  file.llvm_dis.as_deref());
+    let llvm_link = llvm_tool("llvm-link", file.llvm_link.as_deref());
+
+    // A rustc path beats a rustup toolchain from the same source; env beats the file
+    let rustup = |name: String, source| Tool {
+        name:    "rustc",
+        command: vec!["rustup".into(), "run".into(), name, "rustc".into()],
+        source,
+    };
+    let rustc = match (env_value("rustc"), env_value("rust_toolchain"), &file.rustc, &file.rust_toolchain) {
+        (None, Some(name), _, _)       => rustup(name, Source::Env(env_var("rust_toolchain"))),
+        (None, None, None, Some(name)) => rustup(name.clone(), Source::ConfigFile),
+        _                              => resolve("rustc", file.rustc.as_deref(), &["rustc".into()]),
+    };
+
+    let klee_include = env_value("klee_include")
+        .or_else(|| file.klee_include.clone())
+        .or_else(|| find_klee_include(klee_dir.as_deref()));
+
+    Toolchain {
+        klee, ktest_tool, clang, opt, llvm_dis, llvm_link, rustc, klee_include, config_file,
+        klee_options: config.klee,
+    }
+}
+
+/// Environment variable overriding a tool: `llvm-dis` → `EQCHECK_LLVM_DIS`.
+fn env_var(name: &str) -> &'static str {
+    match name {
+        "klee"           => "EQCHECK_KLEE",
+        "ktest-tool"     => "EQCHECK_KTEST_TOOL",
+        "klee_include"   => "EQCHECK_KLEE_INCLUDE",
+        "clang"          => "EQCHECK_CLANG",
+        "opt"            => "EQCHECK_OPT",
+        "llvm-dis"       => "EQCHECK_LLVM_DIS",
+        "llvm-link"      => "EQCHECK_LLVM_LINK",
+        "rustc"          => "EQCHECK_RUSTC",
+        "rust_toolchain" => "EQCHECK_RUST_TOOLCHAIN",
+        "llvm_version"   => "EQCHECK_LLVM_VERSION",
+        _                => "EQCHECK_CONFIG",
+    }
+}
+
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(env_var(name)).ok().filter(|v| !v.is_empty())
+}
+
+fn resolve(name: &'static str, configured: Option<&str>, candidates: &[String]) -> Tool {
+    let (command, source) = if let Some(v) = env_value(name) {
+        (v, Source::Env(env_var(name)))
+    } else if let Some(v) = configured {
+        (v.to_string(), Source::ConfigFile)
+    } else if let Some(found) = candidates.iter().find_map(|c| find_program(c)) {
+        (found.display().to_string(), Source::Probed)
+    } else {
+        (name.to_string(), Source::Missing)
+    };
+    Tool { name, command: vec![command], source }
+}
+
+/// Full path of `program`: itself when it names an existing file, else its
+/// first match on PATH.
+fn find_program(program: &str) -> Option<PathBuf> {
+    if program.contains('/') {
+        let p = PathBuf::from(program);
+        return p.is_file().then_some(p);
+    }
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(program))
+        .find(|p| p.is_file())
+}
+
+/// Where a tool's program lives, when it can be found.
+fn located(tool: &Tool) -> Option<PathBuf> {
+    find_program(&tool.command[0])
+}
+
+/// `klee/klee.h` next to a KLEE build or install, or in the system include dirs.
+fn find_klee_include(klee_dir: Option<&Path>) -> Option<String> {
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if let Some(bin) = klee_dir {
+        candidates.push(bin.join("../include"));       // install prefix
+        candidates.push(bin.join("../../include"));    // build/bin inside a source tree
+    }
+    candidates.push("/usr/local/include".into());
+    candidates.push("/usr/include".into());
+    candidates.into_iter()
+        .find(|d| d.join("klee/klee.h").is_file())
+        .map(|d| d.canonicalize().unwrap_or(d).display().to_string())
+}
+
+// ── Versions ──────────────────────────────────────────
+
+/// First line of `tool --version`, or None when it does not run.
+pub fn version_line(tool: &Tool) -> Option<String> {
+    let text = version_text(tool)?;
+    text.lines().map(str::trim).find(|l| !l.is_empty()).map(str::to_string)
+}
+
+fn version_text(tool: &Tool) -> Option<String> {
+    let flag = if tool.name == "rustc" { "-vV" } else { "--version" };
+    let out = tool.command().arg(flag).output().ok()?;
+    let mut text = String::from_utf8_lossy(&out.stdout).to_string();
+    text.push_str(&String::from_utf8_lossy(&out.stderr));
+    Some(text)
+}
+
+/// LLVM major version a tool was built with, from its version output
+/// ("LLVM version 15.0.7", "clang version 15.0.7", rustc's "LLVM version: 15.0.6").
+pub fn llvm_major_of(tool: &Tool) -> Option<u32> {
+    if tool.source == Source::Missing {
+        return None;
+    }
+    let text = version_text(tool)?;
+    let re = Regex::new(r"(?:LLVM|clang) version:? (\d+)").unwrap();
+    re.captures(&text)?.get(1)?.as_str().parse().ok()
+}
+
+// ── doctor ────────────────────────────────────────────
+
+/// Check every tool runs, print its version, and check that the bitcode
+/// clang and rustc emit is readable by KLEE's LLVM.
+pub fn doctor(tc: &Toolchain) -> Result<()> {
+    println!("\n  -- Toolchain --");
+    match &tc.config_file {
+        Some(p) => println!("     config file: {}", p),
+        None    => println!("     config file: none (EQCHECK_CONFIG, ./eqcheck.json, ~/.config/eqcheck/config.json)"),
+    }
+
+    let mut problems = Vec::new();
+    for tool in tc.tools() {
+        let source = match &tool.source {
+            Source::Env(var)   => format!("from {}", var),
+            Source::ConfigFile => "from config file".to_string(),
+            Source::Probed     => "probed".to_string(),
+            Source::Missing    => "not found".to_string(),
+        };
+        match version_line(tool) {
+            Some(v) => println!("  ✓ {:<11} {} ({})\n       {}", tool.name, tool.display(), source, v),
+            None    => {
+                println!("  ✗ {:<11} {} ({}) — does not run; set {}", tool.name, tool.display(), source, env_var(tool.name));
+                problems.push(format!("{} does not run", tool.name));
+            }
+        }
+    }
+    match &tc.klee_include {
+        Some(d) => println!("  ✓ {:<11} {}", "klee.h", d),
+        None    => {
+            println!("  ✗ {:<11} klee/klee.h not found; set {}", "klee.h", env_var("klee_include"));
+            problems.push("klee/klee.h not found".into());
+        }
+    }
+
+    println!("\n  -- LLVM versions --");
+    let klee_llvm = llvm_major_of(&tc.klee);
+    match klee_llvm {
+        Some(v) => println!("     KLEE built with LLVM {}", v),
+        None    => println!("     KLEE's LLVM version unknown"),
+    }
+    // LLVM reads bitcode from older releases, never from newer ones. llvm-link
+    // must also read what clang and rustc emit, so it has to match KLEE.
+    for (tool, exact) in [(&tc.clang, false), (&tc.rustc, false), (&tc.llvm_link, true)] {
+        match (klee_llvm, llvm_major_of(tool)) {
+            (Some(k), Some(v)) if v == k => println!("  ✓ {} emits LLVM {} bitcode", tool.name, v),
+            (Some(k), Some(v)) if v < k && !exact => println!("  ✓ {} emits LLVM {} bitcode, readable by KLEE's LLVM {}", tool.name, v, k),
+            (Some(k), Some(v)) => {
+                println!("  ✗ {} emits LLVM {} bitcode but KLEE uses LLVM {}", tool.name, v, k);
+                problems.push(format!("{} uses LLVM {}, KLEE LLVM {}", tool.name, v, k));
+            }
+            (None, Some(v)) => println!("       ⚠ {} emits LLVM {} bitcode; compatibility with KLEE not checked", tool.name, v),
+            (_, None)       => println!("       ⚠ {}: LLVM version unknown, compatibility not checked", tool.name),
+        }
+    }
+
+    if problems.is_empty() {
+        println!("\n  ✓ Toolchain ready");
+        Ok(())
+    } else {
+        Err(CheckerError::ToolchainError(problems.join("; ")).into())
+    }
+}
@@ -6,51 +6,881 @@ pub struct AnalysisConfig {
     pub c_file: String,
     pub rust_file: String,
     pub function_name: String,
+    /// Empty means "derive from the C signature" (see `validator::bind_bounds`)
     pub bounds: Vec<InputBound>,
+    /// Window used for derived bounds instead of each type's full range
+    pub default_window: Option<(i64, i64)>,
+    /// What string inputs may contain when the Rust side takes `&str`
+    pub utf8: Utf8Mode,
+    /// Set after validation when the function returns a struct
+    pub return_struct: Option<StructLayout>,
+    /// Out-parameters declared with `--outputs`; after validation, every value
+    /// observed after the call (the C return first, if any)
+    pub outputs: Vec<OutputParam>,
+    /// How a C failure corresponds to a Rust `None`/`Err` (`--error-map`)
+    pub error_map: Option<ErrorMap>,
+    /// Set after validation: the scalar C return type (`None` for void or a struct)
+    pub return_type: Option<VarType>,
+    /// How floating-point results are compared (`--float`)
+    pub float_compare: FloatCompare,
+    /// Link both sides into one KLEE run that asserts equal results (`--miter`)
+    pub miter: bool,
+    /// Run every input when the bounded domain has at most this many
+    /// (`--exhaustive-limit`, 0 disables)
+    pub exhaustive_limit: u64,
+    /// Seeded random sampling and fuzzing
+    pub sampling: Sampling,
+    /// Keep testing after the first divergence and cluster them all (`--collect-all`)
+    pub collect_all: bool,
+    /// KLEE's test limit per program (`--max-paths`, 0 for none)
     pub max_paths: u32,
+    /// Seconds both KLEE runs share (`--timeout`)
     pub timeout: u32,
+    /// KLEE search heuristic and limits (`--search`, `--max-forks`, …)
+    pub klee: KleeOptions,
+    /// Where this run's intermediate files and reports go
+    pub workspace: crate::workspace::Workspace,
+    /// Reuse and record cached artifacts and results (off with `--no-cache`)
+    pub cache: bool,
 }
 
+impl AnalysisConfig {
+    /// Every bound flattened into scalar slots, in runner argument order.
+    pub fn slots(&self) -> Vec<InputBound> {
+        self.bounds.iter().flat_map(InputBound::slots).collect()
+    }
+
+    /// How the plain return value is printed and compared.
+    pub fn return_kind(&self) -> ReturnKind {
+        ReturnKind::of(self.return_type)
+    }
+
+    /// Does any compared result (return value, struct field, output) hold a float?
+    pub fn has_float_results(&self) -> bool {
+        self.return_type.is_some_and(|t| t.is_float())
+            || self.error_map.as_ref().and_then(|m| m.ok_type).is_some_and(|t| t.is_float())
+            || self.outputs.iter().any(|o| o.var_type.is_float())
+            || self.return_struct.as_ref().is_some_and(|s| s.fields.iter().any(|f| f.var_type.is_float()))
+    }
+
+    /// Number of distinct inputs within the bounds, counted over runner slots
+    /// as the checker generates them. `None` for floats, UTF-8-checked
+    /// strings, or a count beyond u128.
+    pub fn domain_size(&self) -> Option<u128> {
+        self.bounds.iter().try_fold(1u128, |acc, b| acc.checked_mul(b.domain_size()?))
+    }
+
+    /// Type of the harness's symbolic `result` object: the return value, the
+    /// first output of a void function, or the success value under an error
+    /// map. `None` when nothing is tracked (void, struct returns).
+    pub fn result_type(&self) -> Option<VarType> {
+        if self.return_struct.is_some() {
+            return None;
+        }
+        if let Some(map) = &self.error_map {
+            // A void success is tracked as 0 / -1
+            return Some(map.ok_type.unwrap_or(VarType::I32));
+        }
+        match self.outputs.first() {
+            Some(first) => Some(first.var_type),
+            None        => self.return_type,
+        }
+    }
+}
+
+/// The C/Rust type of one input variable.
+/// Parsed from the bound spec: "x:i32:-10:10" or just "x:-10:10" (defaults to i32)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum VarType {
+    I8, I16, #[default] I32, I64,
+    U8, U16, U32, U64,
+    F32, F64,
+    Bool,
+    /// Plain C `char`, signed as on the targets KLEE runs on; Rust holds
+    /// the same byte as a `u8`
+    Char,
+}
+
+impl VarType {
+    /// The C type string for declarations
+    pub fn c_type(&self) -> &'static str {
+        match self {
+            VarType::I8  => "int8_t",   VarType::I16 => "int16_t",
+            VarType::I32 => "int",       VarType::I64 => "long long",
+            VarType::U8  => "uint8_t",  VarType::U16 => "uint16_t",
+            VarType::U32 => "unsigned",  VarType::U64 => "unsigned long long",
+            VarType::F32 => "float",     VarType::F64 => "double",
+            VarType::Bool => "int",      VarType::Char => "char",
+        }
+    }
+
+    /// The Rust type string for declarations
+    pub fn rust_type(&self) -> &'static str {
+        match self {
+            VarType::I8  => "i8",  VarType::I16 => "i16",
+            VarType::I32 => "i32", VarType::I64 => "i64",
+            VarType::U8  => "u8",  VarType::U16 => "u16",
+            VarType::U32 => "u32", VarType::U64 => "u64",
+            VarType::F32 => "f32", VarType::F64 => "f64",
+            VarType::Bool => "bool", VarType::Char => "u8",
+        }
+    }
+
+    /// sizeof in C — one element of a symbolic array
+    pub fn byte_size(&self) -> usize {
+        match self {
+            VarType::I8  | VarType::U8  | VarType::Char => 1,
+            VarType::I16 | VarType::U16 => 2,
+            VarType::I32 | VarType::U32 | VarType::F32 | VarType::Bool => 4,
+            VarType::I64 | VarType::U64 | VarType::F64 => 8,
+        }
+    }
+
+    /// Is this a floating-point type?
+    pub fn is_float(&self) -> bool {
+        matches!(self, VarType::F32 | VarType::F64)
+    }
+
+    /// Parse from a string like "i32", "f64", "int", "double", etc.
+    /// Also accepts clang `qualType` spellings ("const unsigned int", "long int").
+    pub fn parse(s: &str) -> Option<VarType> {
+        let s = s.to_lowercase();
+        let words: Vec<&str> = s.split_whitespace()
+            .filter(|w| !matches!(*w, "const" | "volatile"))
+            .collect();
+        match words.join(" ").as_str() {
+            "i8"  | "int8_t" | "signed char" => Some(VarType::I8),
+            "i16" | "int16_t" | "short" | "short int" => Some(VarType::I16),
+            "i32" | "int" | "int32_t" | "signed" | "signed int" => Some(VarType::I32),
+            "i64" | "long" | "long long" | "long int" | "long long int"
+                | "int64_t" | "isize" | "ssize_t" => Some(VarType::I64),
+            "u8"  | "uint8_t" | "unsigned char" => Some(VarType::U8),
+            "u16" | "uint16_t" | "unsigned short" | "unsigned short int" => Some(VarType::U16),
+            "u32" | "unsigned" | "uint" | "uint32_t" | "unsigned int" => Some(VarType::U32),
+            "u64" | "uint64_t" | "unsigned long" | "unsigned long int" | "unsigned long long"
+                | "unsigned long long int" | "usize" | "size_t" => Some(VarType::U64),
+            "f32" | "float"             => Some(VarType::F32),
+            "f64" | "double"            => Some(VarType::F64),
+            "bool" | "_bool"            => Some(VarType::Bool),
+            "char"                      => Some(VarType::Char),
+            _                           => None,
+        }
+    }
+
+    // ── Value encoding ────────────────────────────────
+    //
+    // Every input value travels through the pipeline as an i64 holding the
+    // bit pattern of the declared type: signed integers sign-extended,
+    // unsigned integers zero-extended (u64 reinterpreted), floats as their
+    // IEEE-754 bits. This is the same layout KLEE writes into .ktest files.
+
+    /// Inclusive integer range of the type (floats report the range of
+    /// integral bounds accepted for them).
+    pub fn range(&self) -> (i128, i128) {
+        match self {
+            VarType::I8   => (i8::MIN as i128,  i8::MAX as i128),
+            VarType::I16  => (i16::MIN as i128, i16::MAX as i128),
+            VarType::I32  => (i32::MIN as i128, i32::MAX as i128),
+            VarType::I64  => (i64::MIN as i128, i64::MAX as i128),
+            VarType::U8   => (0, u8::MAX as i128),
+            VarType::U16  => (0, u16::MAX as i128),
+            VarType::U32  => (0, u32::MAX as i128),
+            VarType::U64  => (0, u64::MAX as i128),
+            VarType::Bool => (0, 1),
+            VarType::Char => (i8::MIN as i128, i8::MAX as i128),
+            VarType::F32 | VarType::F64 => (i64::MIN as i128, i64::MAX as i128),
+        }
+    }
+
+    /// Encode an integer value in this type's bit layout.
+    pub fn encode(&self, v: i128) -> i64 {
+        match self {
+            VarType::F32 | VarType::F64 => self.encode_f64(v as f64),
+            VarType::U64 => v as u64 as i64,
+            _            => v as i64,
+        }
+    }
+
+    /// Decode an integer value from this type's bit layout.
+    pub fn decode(&self, v: i64) -> i128 {
+        match self {
+            VarType::F32 | VarType::F64 => self.decode_f64(v) as i128,
+            VarType::U64 => v as u64 as i128,
+            _            => v as i128,
+        }
+    }
+
+    pub fn encode_f64(&self, f: f64) -> i64 {
+        match self {
+            VarType::F32 => (f as f32).to_bits() as i64,
+            VarType::F64 => f.to_bits() as i64,
+            _            => self.encode(f as i128),
+        }
+    }
+
+    pub fn decode_f64(&self, v: i64) -> f64 {
+        match self {
+            VarType::F32 => f32::from_bits(v as u32) as f64,
+            VarType::F64 => f64::from_bits(v as u64),
+            _            => self.decode(v) as f64,
+        }
+    }
+
+    /// Re-interpret a raw sign-extended KLEE/ktest value in this type's layout.
+    pub fn normalize_raw(&self, raw: i64) -> i64 {
+        match self {
+            VarType::I8  | VarType::Char => raw as i8 as i64,
+            VarType::I16 => raw as i16 as i64,
+            VarType::I32 => raw as i32 as i64,
+            VarType::U8  => raw as u8 as i64,
+            VarType::U16 => raw as u16 as i64,
+            VarType::U32 | VarType::F32 => raw as u32 as i64,
+            VarType::Bool => raw & 1,
+            VarType::I64 | VarType::U64 | VarType::F64 => raw,
+        }
+    }
+
+    /// Order two encoded values of this type.
+    pub fn cmp_values(&self, a: i64, b: i64) -> std::cmp::Ordering {
+        if self.is_float() {
+            self.decode_f64(a).total_cmp(&self.decode_f64(b))
+        } else {
+            self.decode(a).cmp(&self.decode(b))
+        }
+    }
+
+    /// Parse a literal from a bound spec or runner output into the encoded layout.
+    pub fn parse_value(&self, s: &str) -> Option<i64> {
+        let s = s.trim();
+        if self.is_float() {
+            let f = s.parse::<f64>().ok().filter(|f| f.is_finite())?;
+            return Some(self.encode_f64(f));
+        }
+        let v = match s {
+            "true"  if *self == VarType::Bool => 1,
+            "false" if *self == VarType::Bool => 0,
+            _ => s.parse::<i128>().ok()?,
+        };
+        let (lo, hi) = self.range();
+        if v < lo || v > hi { return None; }
+        Some(self.encode(v))
+    }
+
+    /// Render an encoded value as a plain literal (runner argument / display).
+    pub fn format_value(&self, v: i64) -> String {
+        if self.is_float() {
+            format!("{:?}", self.decode_f64(v))
+        } else {
+            self.decode(v).to_string()
+        }
+    }
+}
+
+/// Shape of one symbolic input.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum InputKind {
+    #[default]
+    Scalar,
+    /// `T *name, <int> len` in C ↔ `&[T]` in Rust, with a symbolic length
+    /// of at most `max_len` elements. `min`/`max` bound every element.
+    Array { max_len: usize },
+    /// NUL-terminated `char *` in C ↔ `&[u8]`/`&str` in Rust: up to `max_len`
+    /// symbolic bytes. `min`/`max` bound every byte before the terminator.
+    Str { max_len: usize, rust: RustStr },
+    /// `struct point p` in C ↔ `p: Point` (`#[repr(C)]`) in Rust. Each field
+    /// is its own symbolic input named `p.x`, bounded by `fields[i]`.
+    Struct { layout: StructLayout, fields: Vec<InputBound> },
+}
+
+/// One field of a struct, as each language names it.
 #[derive(Debug, Clone, PartialEq)]
-pub enum ParamType {
-    Integer,
-    Float,
+pub struct StructField {
+    pub c_name:    String,
+    pub rust_name: String,
+    pub var_type:  VarType,
 }
 
-impl Default for ParamType {
-    fn default() -> Self { ParamType::Integer }
+/// A struct declared on both sides with corresponding field lists.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructLayout {
+    /// Spelling of the type in C ("struct point", or a typedef name)
+    pub c_type:    String,
+    /// Name of the `#[repr(C)]` struct in Rust
+    pub rust_type: String,
+    pub fields:    Vec<StructField>,
 }
 
-#[derive(Debug, Clone)]
+/// A value observed after the call — the C return value (named "return") or
+/// a pointer parameter the function writes — and where the Rust return value
+/// holds it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputParam {
+    pub name:     String,
+    /// Tuple index or field name on the Rust return value; empty for the whole value
+    pub rust:     String,
+    pub var_type: VarType,
+    /// Position among the C parameters (`None` for the return value)
+    pub c_index:  Option<usize>,
+}
+
+impl OutputParam {
+    /// Parse one output spec: "rem" (matched by position) or "rem=1" /
+    /// "rem=remainder" (tuple index or field of the Rust return value).
+    pub fn parse(spec: &str) -> anyhow::Result<OutputParam> {
+        let (name, rust) = match spec.trim().split_once('=') {
+            Some((n, r)) => (n.trim(), r.trim()),
+            None         => (spec.trim(), ""),
+        };
+        let ident = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !ident(name) || (!rust.is_empty() && !ident(rust)) {
+            return Err(anyhow::anyhow!("Invalid output spec '{}'. Use name or name=index|field", spec));
+        }
+        Ok(OutputParam { name: name.to_string(), rust: rust.to_string(), var_type: VarType::default(), c_index: None })
+    }
+
+    pub fn is_return(&self) -> bool { self.name == "return" }
+}
+
+/// How the C function signals failure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorSignal {
+    /// Returns exactly this value ("-1")
+    Sentinel(i64),
+    /// Returns any negative value ("<0")
+    Negative,
+    /// Sets `errno` to a nonzero value ("errno")
+    Errno,
+}
+
+/// Which fallible type the Rust function returns.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RustFallible {
+    #[default]
+    Option,
+    Result,
+}
+
+/// "C fails ⇔ Rust returns None/Err; otherwise the C value equals the
+/// Some/Ok value".
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorMap {
+    pub signal:  ErrorSignal,
+    /// Resolved by the validator from the Rust return type
+    pub rust:    RustFallible,
+    /// Type of the success value; `None` when the C function returns void
+    pub ok_type: Option<VarType>,
+}
+
+impl ErrorMap {
+    /// Parse "-1" / "==-1" (sentinel), "<0" (any negative) or "errno".
+    pub fn parse(spec: &str) -> anyhow::Result<ErrorMap> {
+        let s = spec.trim();
+        let signal = match s {
+            "errno" => ErrorSignal::Errno,
+            "<0"    => ErrorSignal::Negative,
+            _ => s.trim_start_matches("==").trim().parse::<i64>()
+                .map(ErrorSignal::Sentinel)
+                .map_err(|_| anyhow::anyhow!("Invalid error map '{}'. Use -1, <0 or errno", spec))?,
+        };
+        Ok(ErrorMap { signal, rust: RustFallible::default(), ok_type: None })
+    }
+}
+
+/// How two floating-point results are judged equal once the special-value
+/// policies below have been applied.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FloatMode {
+    /// Identical bit patterns
+    Exact,
+    /// At most this many representable values apart (in the result's own width)
+    Ulp(u64),
+    /// |c - rust| <= eps
+    Abs(f64),
+    /// |c - rust| <= eps * max(|c|, |rust|)
+    Rel(f64),
+}
+
+/// When NaN results are equal.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum NanPolicy {
+    /// Any NaN equals any other NaN
+    #[default]
+    Any,
+    /// NaNs are equal only with identical sign and payload
+    Payload,
+    /// NaN never equals anything (IEEE `==`)
+    Never,
+}
+
+/// Whether +0.0 and -0.0 are equal.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ZeroPolicy {
+    #[default]
+    Distinct,
+    Equal,
+}
+
+/// How infinities compare.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum InfPolicy {
+    /// An infinity equals only the same infinity
+    #[default]
+    Strict,
+    /// An infinity also matches the largest finite value of the same sign,
+    /// for ports that saturate instead of overflowing
+    Saturate,
+}
+
+/// Floating-point comparison settings, parsed from
+/// "MODE[,nan=any|payload|never][,zero=distinct|equal][,inf=strict|saturate]"
+/// where MODE is exact, ulp:N, abs:EPS or rel:EPS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatCompare {
+    pub mode: FloatMode,
+    pub nan:  NanPolicy,
+    pub zero: ZeroPolicy,
+    pub inf:  InfPolicy,
+}
+
+impl Default for FloatCompare {
+    fn default() -> Self {
+        FloatCompare {
+            mode: FloatMode::Exact,
+            nan:  NanPolicy::default(),
+            zero: ZeroPolicy::default(),
+            inf:  InfPolicy::default(),
+        }
+    }
+}
+
+impl FloatCompare {
+    pub fn parse(spec: &str) -> anyhow::Result<FloatCompare> {
+        let mut cmp = FloatCompare::default();
+        let bad = |part: &str| anyhow::anyhow!(
+            "Invalid float comparison '{}'. Use exact, ulp:N, abs:EPS or rel:EPS, \
+             optionally followed by ,nan=any|payload|never ,zero=distinct|equal ,inf=strict|saturate",
+            part
+        );
+        for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part.split_once(['=', ':']).unwrap_or((part, ""));
+            match (key.to_lowercase().as_str(), value.to_lowercase().as_str()) {
+                ("exact", "") => cmp.mode = FloatMode::Exact,
+                ("ulp", v)    => cmp.mode = FloatMode::Ulp(v.parse().map_err(|_| bad(part))?),
+                ("abs", v)    => cmp.mode = FloatMode::Abs(
+                    v.parse().ok().filter(|e: &f64| *e >= 0.0).ok_or_else(|| bad(part))?
+                ),
+                ("rel", v)    => cmp.mode = FloatMode::Rel(
+                    v.parse().ok().filter(|e: &f64| *e >= 0.0).ok_or_else(|| bad(part))?
+                ),
+                ("nan", "any")       => cmp.nan  = NanPolicy::Any,
+                ("nan", "payload")   => cmp.nan  = NanPolicy::Payload,
+                ("nan", "never")     => cmp.nan  = NanPolicy::Never,
+                ("zero", "distinct") => cmp.zero = ZeroPolicy::Distinct,
+                ("zero", "equal")    => cmp.zero = ZeroPolicy::Equal,
+                ("inf", "strict")    => cmp.inf  = InfPolicy::Strict,
+                ("inf", "saturate")  => cmp.inf  = InfPolicy::Saturate,
+                _ => return Err(bad(part)),
+            }
+        }
+        Ok(cmp)
+    }
+}
+
+impl std::fmt::Display for FloatCompare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.mode {
+            FloatMode::Exact  => write!(f, "exact")?,
+            FloatMode::Ulp(n) => write!(f, "ulp:{}", n)?,
+            FloatMode::Abs(e) => write!(f, "abs:{:e}", e)?,
+            FloatMode::Rel(e) => write!(f, "rel:{:e}", e)?,
+        }
+        let nan = match self.nan {
+            NanPolicy::Any     => "any",
+            NanPolicy::Payload => "payload",
+            NanPolicy::Never   => "never",
+        };
+        let zero = match self.zero { ZeroPolicy::Distinct => "distinct", ZeroPolicy::Equal => "equal" };
+        let inf  = match self.inf  { InfPolicy::Strict => "strict", InfPolicy::Saturate => "saturate" };
+        write!(f, ",nan={},zero={},inf={}", nan, zero, inf)
+    }
+}
+
+/// How a string input reaches the Rust function.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RustStr {
+    /// `&[u8]` — the bytes as they are
+    #[default]
+    Bytes,
+    /// `&str` over bytes restricted to ASCII
+    Ascii,
+    /// `&str` over any bytes; ill-formed UTF-8 is outside the input domain
+    Utf8,
+}
+
+/// Assumption made about string inputs passed to a Rust `&str`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Utf8Mode {
+    /// Only ASCII bytes (1..=127), which are always valid UTF-8
+    #[default]
+    Ascii,
+    /// Any non-NUL bytes, with both sides restricted to well-formed UTF-8
+    Checked,
+}
+
+impl Utf8Mode {
+    pub fn parse(s: &str) -> anyhow::Result<Utf8Mode> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "ascii" => Ok(Utf8Mode::Ascii),
+            "checked"    => Ok(Utf8Mode::Checked),
+            other => Err(anyhow::anyhow!("Unknown UTF-8 mode '{}'. Use ascii or checked", other)),
+        }
+    }
+}
+
+/// How random inputs are spread over each slot's range.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Distribution {
+    /// Every value in range equally likely
+    #[default]
+    Uniform,
+    /// Half the draws land within a few steps of min, max or zero
+    EdgeBiased,
+    /// Magnitude drawn uniformly per power of two, for wide ranges
+    LogScale,
+}
+
+impl Distribution {
+    pub fn parse(s: &str) -> anyhow::Result<Distribution> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "uniform"         => Ok(Distribution::Uniform),
+            "edge" | "edge-biased" => Ok(Distribution::EdgeBiased),
+            "log" | "log-scale"    => Ok(Distribution::LogScale),
+            other => Err(anyhow::anyhow!("Unknown distribution '{}'. Use uniform, edge or log", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for Distribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            Distribution::Uniform    => "uniform",
+            Distribution::EdgeBiased => "edge",
+            Distribution::LogScale   => "log",
+        })
+    }
+}
+
+/// Seeded random sampling and the fuzzing loop that follows it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sampling {
+    /// Random inputs drawn before fuzzing (`--random`, 0 disables)
+    pub count:        usize,
+    pub distribution: Distribution,
+    /// Fixed seed (`--seed`); a fresh one is drawn and reported otherwise
+    pub seed:         Option<u64>,
+    /// Seconds spent mutating inputs near branch constants (`--fuzz-time`)
+    pub fuzz_secs:    u64,
+}
+
+impl Default for Sampling {
+    fn default() -> Self {
+        Sampling { count: 100, distribution: Distribution::Uniform, seed: None, fuzz_secs: 5 }
+    }
+}
+
+/// Order in which KLEE picks the next state to run (`--search`).
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Search {
+    #[default]
+    #[serde(rename = "dfs")]
+    Dfs,
+    #[serde(rename = "bfs")]
+    Bfs,
+    #[serde(rename = "random-path")]
+    RandomPath,
+    /// Favour states likely to cover new code
+    #[serde(rename = "nurs:covnew")]
+    NursCovNew,
+    /// random-path and nurs:covnew taking turns (KLEE's own default)
+    #[serde(rename = "interleaved")]
+    Interleaved,
+}
+
+impl Search {
+    pub fn parse(s: &str) -> anyhow::Result<Search> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "dfs"               => Ok(Search::Dfs),
+            "bfs"                    => Ok(Search::Bfs),
+            "random-path"            => Ok(Search::RandomPath),
+            "nurs:covnew" | "covnew" => Ok(Search::NursCovNew),
+            "interleaved"            => Ok(Search::Interleaved),
+            other => Err(anyhow::anyhow!(
+                "Unknown search heuristic '{}'. Use dfs, bfs, random-path, nurs:covnew or interleaved", other)),
+        }
+    }
+
+    /// KLEE `--search` values; several interleave.
+    pub fn klee_names(&self) -> &'static [&'static str] {
+        match self {
+            Search::Dfs         => &["dfs"],
+            Search::Bfs         => &["bfs"],
+            Search::RandomPath  => &["random-path"],
+            Search::NursCovNew  => &["nurs:covnew"],
+            Search::Interleaved => &["random-path", "nurs:covnew"],
+        }
+    }
+}
+
+impl std::fmt::Display for Search {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            Search::Dfs         => "dfs",
+            Search::Bfs         => "bfs",
+            Search::RandomPath  => "random-path",
+            Search::NursCovNew  => "nurs:covnew",
+            Search::Interleaved => "interleaved",
+        })
+    }
+}
+
+/// KLEE search heuristic and exploration limits. The config file's "klee"
+/// section overrides the defaults; flags and form fields override both.
+/// A limit of 0 means none.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KleeOptions {
+    pub search:         Search,
+    /// Forks before KLEE stops branching (`--max-forks`)
+    pub max_forks:      u32,
+    /// Symbolic branches along one path (`--max-depth`)
+    pub max_depth:      u32,
+    /// Memory cap in MB (`--max-memory`)
+    pub max_memory:     u32,
+    /// Seconds one solver query may take (`--solver-timeout`)
+    pub solver_timeout: u32,
+}
+
+impl Default for KleeOptions {
+    fn default() -> Self {
+        KleeOptions { search: Search::Dfs, max_forks: 500, max_depth: 500, max_memory: 1000, solver_timeout: 0 }
+    }
+}
+
+/// One symbolic input. `min`/`max` are encoded in `var_type`'s bit layout.
+#[derive(Debug, Clone, PartialEq)]
 pub struct InputBound {
     pub name: String,
+    pub var_type: VarType,
     pub min: i64,
     pub max: i64,
-    pub param_type: ParamType,
+    pub kind: InputKind,
+    /// The spec named a type ("x:i64:…"); untyped bounds adopt the
+    /// parameter's declared type instead
+    pub explicit_type: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+impl InputBound {
+    /// Parse one bound spec: "x:0:100" (default i32), "x:i64:-5:5" (explicit type)
+    /// "arr:i32[8]:-10:10" (array of up to 8 elements) or "s:str[8]" (string of
+    /// up to 8 ASCII bytes; "s:str[8]:32:126" narrows the byte range)
+    pub fn parse(spec: &str) -> anyhow::Result<InputBound> {
+        let p: Vec<&str> = spec.trim().split(':').map(str::trim).collect();
+        let (name, ty, min, max) = match p.as_slice() {
+            [name, min, max]     => (*name, None, *min, *max),
+            [name, ty, min, max] => (*name, Some(*ty), *min, *max),
+            [name, ty] if ty.starts_with("str") => (*name, Some(*ty), "1", "127"),
+            _ => return Err(anyhow::anyhow!(
+                "Invalid bounds '{}'. Use name:min:max or name:type:min:max", spec
+            )),
+        };
+        // "[N]" may follow either the type ("i32[8]") or, when untyped, the name
+        let (name, ty, max_len) = match ty {
+            Some(ty) => { let (ty, n) = split_array_suffix(ty, spec)?; (name, Some(ty), n) }
+            None     => { let (name, n) = split_array_suffix(name, spec)?; (name, None, n) }
+        };
+        let is_str = ty == Some("str");
+        let var_type = match ty {
+            Some("str") => VarType::U8,
+            Some(ty) => VarType::parse(ty).ok_or_else(|| anyhow::anyhow!(
+                "Unknown type '{}' in bounds '{}'", ty, spec
+            ))?,
+            None => VarType::default(),
+        };
+        let kind = match max_len {
+            Some(max_len) if is_str => InputKind::Str { max_len, rust: RustStr::default() },
+            None if is_str => return Err(anyhow::anyhow!(
+                "Invalid bounds '{}': strings need a maximum length, e.g. {}:str[8]", spec, name
+            )),
+            Some(_) if var_type == VarType::Bool => return Err(anyhow::anyhow!(
+                "Invalid bounds '{}': bool arrays are not supported", spec
+            )),
+            Some(max_len) => InputKind::Array { max_len },
+            None          => InputKind::Scalar,
+        };
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("Invalid bounds '{}': missing name", spec));
+        }
+        let parse = |v: &str| var_type.parse_value(v).ok_or_else(|| anyhow::anyhow!(
+            "Bound '{}' is not a valid {} value in '{}'", v, var_type.rust_type(), spec
+        ));
+        let (min, max) = (parse(min)?, parse(max)?);
+        if var_type.cmp_values(min, max) == std::cmp::Ordering::Greater {
+            return Err(anyhow::anyhow!("Invalid bounds '{}': min > max", spec));
+        }
+        if is_str && min == 0 {
+            return Err(anyhow::anyhow!(
+                "Invalid bounds '{}': string bytes start at 1 (0 terminates the string)", spec
+            ));
+        }
+        Ok(InputBound { name: name.to_string(), var_type, min, max, kind, explicit_type: ty.is_some() })
+    }
+
+    /// Bound covering a parameter's whole type range, narrowed to `window` if given.
+    pub fn for_type(name: &str, var_type: VarType, window: Option<(i64, i64)>) -> InputBound {
+        let (lo, hi) = var_type.range();
+        let (lo, hi) = match window {
+            Some((wlo, whi)) => ((wlo as i128).clamp(lo, hi), (whi as i128).clamp(lo, hi)),
+            None             => (lo, hi),
+        };
+        InputBound {
+            name: name.to_string(),
+            var_type,
+            min: var_type.encode(lo),
+            max: var_type.encode(hi.max(lo)),
+            kind: InputKind::Scalar,
+            explicit_type: false,
+        }
+    }
+
+    /// The same range re-read in another type; `None` if it does not fit.
+    pub fn retyped(&self, var_type: VarType) -> Option<InputBound> {
+        let read = |v: i64| var_type.parse_value(&self.var_type.format_value(v));
+        Some(InputBound {
+            name: self.name.clone(),
+            var_type,
+            min: read(self.min)?,
+            max: read(self.max)?,
+            kind: self.kind.clone(),
+            explicit_type: self.explicit_type,
+        })
+    }
+
+    /// Name of the symbolic length variable of an array input.
+    pub fn len_name(&self) -> String {
+        format!("{}_len", self.name)
+    }
+
+    /// This input as scalar slots: itself, or an array's length followed by
+    /// its elements (`arr_len`, `arr[0]`, `arr[1]`, ...).
+    pub fn slots(&self) -> Vec<InputBound> {
+        match self.kind {
+            InputKind::Scalar => vec![self.clone()],
+            InputKind::Array { max_len } => {
+                let len = InputBound {
+                    name:     self.len_name(),
+                    var_type: VarType::U32,
+                    min:      0,
+                    max:      max_len as i64,
+                    kind:     InputKind::Scalar,
+                    explicit_type: false,
+                };
+                std::iter::once(len).chain((0..max_len).map(|i| InputBound {
+                    name: format!("{}[{}]", self.name, i),
+                    kind: InputKind::Scalar,
+                    ..self.clone()
+                })).collect()
+            }
+            // Raw buffer bytes; the first 0 ends the string
+            InputKind::Str { max_len, .. } => (0..max_len).map(|i| InputBound {
+                name: format!("{}[{}]", self.name, i),
+                min:  0,
+                kind: InputKind::Scalar,
+                ..self.clone()
+            }).collect(),
+            InputKind::Struct { ref fields, .. } => fields.clone(),
+        }
+    }
+
+    /// Number of distinct values this input takes within its bounds (see
+    /// `AnalysisConfig::domain_size`).
+    pub fn domain_size(&self) -> Option<u128> {
+        if self.var_type.is_float() {
+            return None;
+        }
+        let width = |lo: i64, hi: i64| {
+            let (lo, hi) = (self.var_type.decode(lo), self.var_type.decode(hi));
+            u128::try_from(hi - lo + 1).ok()
+        };
+        match self.kind {
+            InputKind::Scalar => width(self.min, self.max),
+            // Every element slot varies independently of the length
+            InputKind::Array { max_len } => {
+                let elems = width(self.min, self.max)?.checked_pow(max_len as u32)?;
+                elems.checked_mul(max_len as u128 + 1)
+            }
+            // Bytes after the terminator are zeroed, so count each length
+            InputKind::Str { rust: RustStr::Utf8, .. } => None,
+            InputKind::Str { max_len, .. } => {
+                let bytes = width(self.min.max(1), self.max)?;
+                (0..=max_len as u32)
+                    .try_fold(0u128, |acc, len| acc.checked_add(bytes.checked_pow(len)?))
+            }
+            InputKind::Struct { ref fields, .. } => fields.iter()
+                .try_fold(1u128, |acc, f| acc.checked_mul(f.domain_size()?)),
+        }
+    }
+
+    /// Clamp an encoded value into [min, max].
+    pub fn clamp(&self, v: i64) -> i64 {
+        use std::cmp::Ordering::*;
+        match (self.var_type.cmp_values(v, self.min), self.var_type.cmp_values(v, self.max)) {
+            (Less, _)    => self.min,
+            (_, Greater) => self.max,
+            _            => v,
+        }
+    }
+}
+
+/// Split "i32[8]" into ("i32", Some(8)).
+fn split_array_suffix<'a>(s: &'a str, spec: &str) -> anyhow::Result<(&'a str, Option<usize>)> {
+    let Some(open) = s.find('[') else { return Ok((s, None)) };
+    let n = s[open + 1..].strip_suffix(']')
+        .and_then(|n| n.trim().parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .ok_or_else(|| anyhow::anyhow!("Invalid array length in bounds '{}'", spec))?;
+    Ok((s[..open].trim(), Some(n)))
+}
+
+/// Parse a default window spec: "MIN:MAX"
+pub fn parse_window(s: &str) -> anyhow::Result<(i64, i64)> {
+    let (lo, hi) = s.split_once(':')
+        .ok_or_else(|| anyhow::anyhow!("Invalid window '{}'. Use min:max", s))?;
+    let (lo, hi) = (lo.trim().parse::<i64>()?, hi.trim().parse::<i64>()?);
+    if lo > hi {
+        return Err(anyhow::anyhow!("Invalid window '{}': min > max", s));
+    }
+    Ok((lo, hi))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub enum ReturnKind {
+    #[default]
     Integer,
     Float,
     Bool,
     Void,
 }
 
-impl Default for ReturnKind {
-    fn default() -> Self { ReturnKind::Integer }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct EquivalenceDetail {
-    pub inputs_tested: u32,
-    pub return_value_match: Option<bool>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DivergingInput {
-    pub name: String,
-    pub value: i64,
+impl ReturnKind {
+    /// Kind of a C return type; `None` is void.
+    pub fn of(ty: Option<VarType>) -> ReturnKind {
+        match ty {
+            None                                   => ReturnKind::Void,
+            Some(VarType::F32 | VarType::F64)      => ReturnKind::Float,
+            Some(VarType::Bool)                    => ReturnKind::Bool,
+            Some(_)                                => ReturnKind::Integer,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -59,6 +889,14 @@ pub struct ValidationResult {
     pub c_signature: Option<FunctionSignature>,
     pub rust_signature: Option<FunctionSignature>,
     pub errors: Vec<String>,
+    /// Layouts of the struct types in the signature, keyed by C spelling
+    pub structs: Vec<StructLayout>,
+    /// Resolved outputs when `--outputs` is given (see `AnalysisConfig::outputs`)
+    pub outputs: Vec<OutputParam>,
+    /// Resolved error mapping when `--error-map` is given
+    pub error_map: Option<ErrorMap>,
+    /// Scalar C return type; `None` for void or a struct
+    pub return_type: Option<VarType>,
 }
 
 #[derive(Debug, Clone)]
@@ -78,6 +916,9 @@ pub struct PathSummary {
     pub observables: ObservableEffects,
     #[serde(default)]
     pub label_map: HashMap<String, String>,
+    /// KLEE error the path ended in (`abort` for a Rust panic, `ptr`, `div`, …)
+    #[serde(default)]
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -99,6 +940,8 @@ pub struct FileOperation {
 pub enum ProgramKind {
     C,
     Rust,
+    /// Both sides linked into one module, results compared by `klee_assert`
+    Miter,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,15 +953,283 @@ pub struct EquivalenceResult {
     pub statistics: CheckerStatistics,
     pub c_path: Option<PathSummary>,
     pub rust_path: Option<PathSummary>,
+    /// Float comparison settings, when any compared result is a float
+    #[serde(default)]
+    pub float_compare: Option<String>,
+    /// What an equivalent verdict rests on (`None` unless equivalent)
+    #[serde(default)]
+    pub evidence: Option<Evidence>,
+    /// What stopped KLEE before it explored every path, e.g. "C: fork limit reached"
+    #[serde(default)]
+    pub cut_short: Option<String>,
+    /// Set when the whole bounded domain was enumerated
+    #[serde(default)]
+    pub exhaustive: Option<ExhaustiveRun>,
+    /// Seed of the random and fuzzing stages, to reproduce the run
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Every divergence found, grouped by path pair (`--collect-all`)
+    #[serde(default)]
+    pub clusters: Vec<DivergenceCluster>,
+    /// The counterexample generalised to every input on its path pair whose results differ
+    #[serde(default)]
+    pub region: Option<InputRegion>,
+    /// Each side's piecewise definition, C and Rust partitions aligned by overlap
+    #[serde(default)]
+    pub partitions: Vec<PartitionRow>,
+    /// Which C and Rust paths share inputs, and why the others match nothing
+    #[serde(default)]
+    pub correspondence: Option<PathCorrespondence>,
+    /// KLEE settings the paths were explored with
+    #[serde(default)]
+    pub exploration: Option<Exploration>,
+}
+
+impl EquivalenceResult {
+    /// A result carrying only its verdict and statistics; each return site
+    /// states what else it found. Timing, float settings and exploration are
+    /// filled in by `equivalence::check`.
+    pub fn new(verdict: Verdict, statistics: CheckerStatistics) -> EquivalenceResult {
+        EquivalenceResult {
+            verdict,
+            paths_compared: 0,
+            counterexample: None,
+            time_taken:     0.0,
+            statistics,
+            c_path:         None,
+            rust_path:      None,
+            float_compare:  None,
+            evidence:       None,
+            cut_short:      None,
+            exhaustive:     None,
+            seed:           None,
+            clusters:       Vec::new(),
+            region:         None,
+            partitions:     Vec::new(),
+            correspondence: None,
+            exploration:    None,
+        }
+    }
+}
+
+/// The effective KLEE settings of a run, so it can be repeated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Exploration {
+    pub klee:      KleeOptions,
+    pub max_paths: u32,
+    pub timeout:   u32,
+}
+
+impl Exploration {
+    pub fn of(config: &AnalysisConfig) -> Exploration {
+        Exploration { klee: config.klee, max_paths: config.max_paths, timeout: config.timeout }
+    }
+
+    /// The checker flags that repeat this exploration.
+    pub fn flags(&self) -> String {
+        format!(
+            "--search {} --max-paths {} --timeout {} --max-forks {} --max-depth {} --max-memory {} --solver-timeout {}",
+            self.klee.search, self.max_paths, self.timeout,
+            self.klee.max_forks, self.klee.max_depth, self.klee.max_memory, self.klee.solver_timeout,
+        )
+    }
+}
+
+/// One piece of a function's definition: a path's input condition and the
+/// result it returns there.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Partition {
+    pub path:      String,
+    pub condition: String,
+    pub result:    String,
+}
+
+/// A C partition and a Rust partition whose conditions overlap; a side is
+/// `None` when its partition overlaps nothing on the other.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartitionRow {
+    pub c:     Option<Partition>,
+    pub rust:  Option<Partition>,
+    /// Results agree wherever both conditions hold; `None` when not decided
+    pub agree: Option<bool>,
+}
+
+/// How the two sides partition the input domain into paths.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathCorrespondence {
+    pub c_paths:    Vec<String>,
+    pub rust_paths: Vec<String>,
+    /// `overlap[i][j]`: some input within bounds takes C path `i` and Rust
+    /// path `j` (`None` when Z3 could not decide)
+    pub overlap:    Vec<Vec<Option<bool>>>,
+    /// Paths with no counterpart on the other side
+    pub unmatched:  Vec<UnmatchedPath>,
+}
+
+/// A path no path on the other side overlaps while ending the same way
+/// (returning, or failing in KLEE).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmatchedPath {
+    pub path:    String,
+    pub program: ProgramKind,
+    pub kind:    UnmatchedKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UnmatchedKind {
+    /// Ends in this KLEE error where the paths it overlaps return
+    Check(String),
+    /// No input within the bounds takes it
+    OutsideBounds,
+    /// Inputs within the bounds take it, but no path found on the other side
+    Uncovered,
+    /// Its constraints could not be encoded or Z3 returned unknown
+    Undecided,
+}
+
+impl UnmatchedKind {
+    pub fn label(&self) -> String {
+        match self {
+            UnmatchedKind::Check(kind) => match kind.as_str() {
+                "abort"    => "panic".into(),
+                "ptr"      => "bounds check".into(),
+                "div"      => "division by zero".into(),
+                "overflow" => "overflow check".into(),
+                "assert"   => "assertion".into(),
+                other      => format!("KLEE error ({})", other),
+            },
+            UnmatchedKind::OutsideBounds => "outside bounds".into(),
+            UnmatchedKind::Uncovered     => "uncovered".into(),
+            UnmatchedKind::Undecided     => "undecided".into(),
+        }
+    }
+
+    pub fn meaning(&self) -> &'static str {
+        match self {
+            UnmatchedKind::Check(_)      => "a runtime check fails here where the other side returns a value",
+            UnmatchedKind::OutsideBounds => "no input within the bounds takes this path",
+            UnmatchedKind::Uncovered     => "inputs within the bounds take this path, but the other side's exploration never reached them",
+            UnmatchedKind::Undecided     => "its constraints could not be encoded, so its overlap is unknown",
+        }
+    }
+}
+
+/// Inputs that follow the counterexample's (C path, Rust path) pair and
+/// still produce different results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputRegion {
+    /// (slot, least, greatest) for each slot the region narrows below its
+    /// bounds, formatted in the slot's type
+    pub intervals: Vec<(String, String, String)>,
+    /// Every input in the box spanned by `intervals` is in the region
+    pub exact:     bool,
+}
+
+/// Divergent inputs that follow the same (C path, Rust path) pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DivergenceCluster {
+    /// Path ids; `None` when no path's constraints placed the inputs
+    pub c_path:         Option<String>,
+    pub rust_path:      Option<String>,
+    /// Failing inputs seen in this cluster
+    pub count:          u64,
+    /// Shrunk counterexample standing for the cluster
+    pub representative: Counterexample,
+    /// Where the pair's results differ, simplified by Z3 (`b == 0 ∧ a > 0`)
+    pub region:         Option<String>,
+}
+
+/// Outcome of running every input of a small bounded domain on both sides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExhaustiveRun {
+    pub domain_size:     u64,
+    pub inputs_run:      u64,
+    /// Inputs on which a runner produced no result
+    pub runner_errors:   u64,
+    pub divergent_count: u64,
+    /// The first divergent inputs, each as (name, value) pairs
+    pub divergent:       Vec<Vec<(String, String)>>,
+}
+
+impl ExhaustiveRun {
+    /// Every input in the domain ran on both sides.
+    pub fn is_complete(&self) -> bool {
+        self.runner_errors == 0 && self.inputs_run == self.domain_size
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Verdict {
+    /// Z3 showed every pair of C and Rust paths returns the same value, and
+    /// KLEE explored every path within the bounds
+    ProvedEquivalent,
     Equivalent,
     NotEquivalent,
     Unknown,
 }
 
+impl Verdict {
+    pub fn is_equivalent(&self) -> bool {
+        matches!(self, Verdict::ProvedEquivalent | Verdict::Equivalent)
+    }
+}
+
+/// Strength of the evidence behind an equivalent verdict, strongest first.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Evidence {
+    /// Symbolic proof over complete KLEE path coverage
+    SymbolicProof,
+    /// Every input in the bounded domain was run on both sides
+    Exhaustive,
+    /// KLEE explored every path and each one was run concretely
+    PathCoverage,
+    /// Only a sample of inputs was compared
+    Sampled,
+    /// KLEE stopped before exploring every path (time, fork, depth, memory
+    /// or test limit); `EquivalenceResult::cut_short` says which
+    Incomplete,
+}
+
+impl Evidence {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Evidence::SymbolicProof => "symbolic proof",
+            Evidence::Exhaustive    => "exhaustive enumeration",
+            Evidence::PathCoverage  => "KLEE path coverage complete",
+            Evidence::Sampled       => "sampling only",
+            Evidence::Incomplete    => "KLEE exploration incomplete",
+        }
+    }
+
+    /// What "equivalent" means under this evidence, for reviewers.
+    pub fn meaning(&self) -> &'static str {
+        match self {
+            Evidence::SymbolicProof => "Z3 showed the results agree on every input within the bounds",
+            Evidence::Exhaustive    => "every input within the bounds was run and the results agreed",
+            Evidence::PathCoverage  => "every path KLEE found was run with a witness input and the results agreed",
+            Evidence::Sampled       => "the inputs tried agreed; untested inputs may still differ",
+            Evidence::Incomplete    => "KLEE stopped early; only the paths it reached were sampled",
+        }
+    }
+
+    /// Verdict banner for an equivalent result backed by this evidence.
+    pub fn headline(&self) -> &'static str {
+        match self {
+            Evidence::SymbolicProof => "PROVED EQUIVALENT WITHIN BOUNDS",
+            Evidence::Exhaustive    => "EXHAUSTIVELY EQUIVALENT WITHIN BOUNDS",
+            Evidence::PathCoverage  => "EQUIVALENT ON EVERY KLEE PATH",
+            Evidence::Sampled       => "NO DIFFERENCE FOUND (SAMPLED)",
+            Evidence::Incomplete    => "NO DIFFERENCE FOUND (KLEE STOPPED EARLY)",
+        }
+    }
+
+    /// Only some inputs were compared: no claim about the rest of the domain.
+    pub fn is_sampled(&self) -> bool {
+        matches!(self, Evidence::Sampled | Evidence::Incomplete)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Counterexample {
     pub inputs: Vec<(String, i64)>,
@@ -126,11 +1237,17 @@ pub struct Counterexample {
     pub c_behavior: ConcreteBehavior,
     pub rust_behavior: ConcreteBehavior,
     pub differences: Vec<Difference>,
+    /// The counterexample as first found, when shrinking simplified it
+    #[serde(default)]
+    pub original: Option<Box<Counterexample>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ConcreteBehavior {
     pub return_value: String,
+    /// Out-parameter values after the call, by C parameter name
+    #[serde(default)]
+    pub outputs: Vec<(String, String)>,
     pub stdout: Vec<String>,
     pub stderr: Vec<String>,
     pub globals: Vec<(String, String)>,
@@ -146,12 +1263,27 @@ pub struct Difference {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DifferenceKind {
     ReturnValue,
+    /// One field of a returned struct (C field name)
+    ReturnField(String),
+    /// An out-parameter (C parameter name)
+    OutputParam(String),
     Stdout,
     Stderr,
     GlobalVariable(String),
     FileOperation,
 }
 
+impl DifferenceKind {
+    /// Short label for a per-field or per-output difference: ".x" or "*rem".
+    pub fn label(&self) -> Option<String> {
+        match self {
+            DifferenceKind::ReturnField(f) => Some(format!(".{}", f)),
+            DifferenceKind::OutputParam(p) => Some(format!("*{}", p)),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct CheckerStatistics {
     pub total_paths_c: usize,
@@ -159,6 +1291,12 @@ pub struct CheckerStatistics {
     pub merged_pairs: usize,
     pub z3_queries: u32,
     pub z3_time_ms: u64,
+    /// KLEE constraints the Z3 encoding could not translate (left out of queries)
+    #[serde(default)]
+    pub unsupported_constraints: usize,
+    /// Paths KLEE explored through the single-miter harness (0 when not used)
+    #[serde(default)]
+    pub miter_paths: usize,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -177,6 +1315,156 @@ pub enum CheckerError {
     EquivalenceError(String),
     #[error("Z3 solver error: {0}")]
     Z3Error(String),
+    #[error("Toolchain error: {0}")]
+    ToolchainError(String),
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn u64_max_travels_as_its_i64_bit_pattern() {
+        let max = u64::MAX as i128;
+        assert_eq!(VarType::U64.encode(max), -1);
+        assert_eq!(VarType::U64.decode(-1), max);
+        assert_eq!(VarType::U64.parse_value("18446744073709551615"), Some(-1));
+        assert_eq!(VarType::U64.parse_value("18446744073709551616"), None);
+        assert_eq!(VarType::U64.format_value(-1), "18446744073709551615");
+        assert_eq!(VarType::U64.cmp_values(0, -1), std::cmp::Ordering::Less);
+    }
+
+    #[test]
+    fn normalize_raw_reads_the_declared_width() {
+        assert_eq!(VarType::I8.normalize_raw(0xff), -1);
+        assert_eq!(VarType::U8.normalize_raw(-1), 255);
+        assert_eq!(VarType::Char.normalize_raw(0xfb), -5);
+        assert_eq!(VarType::I16.normalize_raw(0x8000), i16::MIN as i64);
+        assert_eq!(VarType::U32.normalize_raw(-1), u32::MAX as i64);
+        assert_eq!(VarType::Bool.normalize_raw(3), 1);
+        assert_eq!(VarType::I64.normalize_raw(-7), -7);
+        // KLEE sign-extends a 4-byte float object like any other
+        let bits = (-1.0f32).to_bits() as i32 as i64;
+        assert_eq!(VarType::F32.decode_f64(VarType::F32.normalize_raw(bits)), -1.0);
+    }
+
+    #[test]
+    fn floats_encode_as_ieee_bits() {
+        assert_eq!(VarType::F64.encode_f64(-1.0), (-1.0f64).to_bits() as i64);
+        assert_eq!(VarType::F64.decode_f64(VarType::F64.encode_f64(0.1)), 0.1);
+        assert_eq!(VarType::F32.decode_f64(VarType::F32.encode_f64(1.5)), 1.5);
+        assert_eq!(VarType::F64.parse_value("inf"), None);
+        assert_eq!(VarType::F64.format_value(VarType::F64.encode_f64(-0.5)), "-0.5");
+    }
+
+    #[test]
+    fn plain_char_is_signed() {
+        assert_eq!(VarType::Char.range(), (-128, 127));
+        let b = InputBound::parse("c:char:-5:5").unwrap();
+        assert_eq!((b.min, b.max), (-5, 5));
+    }
+
+    #[test]
+    fn parse_untyped_bound_defaults_to_i32() {
+        let b = InputBound::parse("x:-10:10").unwrap();
+        assert_eq!(b.var_type, VarType::I32);
+        assert_eq!((b.min, b.max), (-10, 10));
+        assert_eq!(b.kind, InputKind::Scalar);
+    }
+
+    #[test]
+    fn parse_typed_bounds() {
+        let b = InputBound::parse("n:u64:0:18446744073709551615").unwrap();
+        assert_eq!((b.var_type, b.min, b.max), (VarType::U64, 0, -1));
+
+        let f = InputBound::parse("f:f64:-1:1").unwrap();
+        assert_eq!(f.var_type, VarType::F64);
+        assert_eq!(f.min, (-1.0f64).to_bits() as i64);
+        assert_eq!(f.max, 1.0f64.to_bits() as i64);
+        assert_eq!(f.clamp(VarType::F64.encode_f64(2.0)), f.max);
+        assert_eq!(f.clamp(VarType::F64.encode_f64(0.25)), VarType::F64.encode_f64(0.25));
+    }
+
+    #[test]
+    fn parse_arrays_and_strings() {
+        let a = InputBound::parse("arr:i32[8]:-10:10").unwrap();
+        assert_eq!(a.kind, InputKind::Array { max_len: 8 });
+        assert_eq!(a.slots().len(), 9);
+        assert_eq!(a.slots()[0].name, "arr_len");
+
+        let untyped = InputBound::parse("arr[3]:0:1").unwrap();
+        assert_eq!((untyped.name.as_str(), untyped.kind), ("arr", InputKind::Array { max_len: 3 }));
+
+        let s = InputBound::parse("s:str[4]").unwrap();
+        assert_eq!(s.kind, InputKind::Str { max_len: 4, rust: RustStr::Bytes });
+        assert_eq!((s.var_type, s.min, s.max), (VarType::U8, 1, 127));
+    }
+
+    #[test]
+    fn parse_rejects_bad_bounds() {
+        for spec in [
+            "x:5:1",            // min > max
+            "x:i8:-200:5",      // outside i8
+            "x:u32:-1:5",       // negative unsigned
+            "x:f64:-inf:1",     // not finite
+            "x:i33:0:1",        // unknown type
+            "s:str",            // string without a length
+            "s:str[4]:0:100",   // 0 is the terminator
+            "b:bool[2]:0:1",    // bool arrays
+            "arr:i32[0]:0:1",   // empty array
+            ":0:1",             // missing name
+            "x:1",              // too few fields
+        ] {
+            assert!(InputBound::parse(spec).is_err(), "{} should be rejected", spec);
+        }
+    }
+
+    #[test]
+    fn domain_size_counts_runner_slots() {
+        assert_eq!(InputBound::parse("x:-2:2").unwrap().domain_size(), Some(5));
+        assert_eq!(InputBound::parse("b:bool:0:1").unwrap().domain_size(), Some(2));
+        // Lengths 0..=2, each element in 0..=1
+        assert_eq!(InputBound::parse("a:u8[2]:0:1").unwrap().domain_size(), Some(3 * 4));
+        // "", one byte, two bytes over 'a'..='c'
+        assert_eq!(InputBound::parse("s:str[2]:97:99").unwrap().domain_size(), Some(1 + 3 + 9));
+        assert_eq!(InputBound::parse("f:f32:0:1").unwrap().domain_size(), None);
+    }
+
+    #[test]
+    fn retyped_rereads_the_range() {
+        let b = InputBound::parse("x:-5:5").unwrap();
+        let wide = b.retyped(VarType::I64).unwrap();
+        assert_eq!((wide.var_type, wide.min, wide.max), (VarType::I64, -5, 5));
+        assert!(b.retyped(VarType::U32).is_none());
+    }
+
+    #[test]
+    fn float_compare_parses_mode_and_policies() {
+        let cmp = FloatCompare::parse("ulp:4, nan=payload, zero=equal, inf=saturate").unwrap();
+        assert_eq!(cmp.mode, FloatMode::Ulp(4));
+        assert_eq!((cmp.nan, cmp.zero, cmp.inf), (NanPolicy::Payload, ZeroPolicy::Equal, InfPolicy::Saturate));
+
+        assert_eq!(FloatCompare::parse("").unwrap(), FloatCompare::default());
+        assert_eq!(FloatCompare::parse("ABS:1e-9").unwrap().mode, FloatMode::Abs(1e-9));
+        assert_eq!(FloatCompare::parse("rel=0.5").unwrap().mode, FloatMode::Rel(0.5));
+        // Policies alone keep the exact mode
+        assert_eq!(FloatCompare::parse("nan=never").unwrap().mode, FloatMode::Exact);
+    }
+
+    #[test]
+    fn float_compare_rejects_bad_specs() {
+        for spec in ["ulp", "ulp:-1", "abs:-0.1", "rel:x", "exact:1", "nan=maybe", "zero=sometimes", "fuzzy"] {
+            assert!(FloatCompare::parse(spec).is_err(), "{} should be rejected", spec);
+        }
+    }
+
+    #[test]
+    fn float_compare_display_round_trips() {
+        for spec in ["exact", "ulp:3,nan=never", "abs:0.001,zero=equal", "rel:1e-6,inf=saturate"] {
+            let cmp = FloatCompare::parse(spec).unwrap();
+            assert_eq!(FloatCompare::parse(&cmp.to_string()).unwrap(), cmp);
+        }
+    }
+}
@@ -4,9 +4,12 @@
 // Checks file existence, syntax, and function signatures
 // ═══════════════════════════════════════════════════════
 
-use crate::types::{AnalysisConfig, ValidationResult, FunctionSignature, CheckerError};
+use crate::types::{
+    AnalysisConfig, ValidationResult, FunctionSignature, CheckerError, InputBound, InputKind,
+    ErrorMap, ErrorSignal, OutputParam, RustFallible, RustStr, StructField, StructLayout, Utf8Mode, VarType,
+};
+use crate::toolchain;
 use anyhow::Result;
-use std::process::Command;
 use std::path::Path;
 use serde_json::Value;
 use quote::ToTokens;
@@ -32,6 +35,10 @@ pub fn validate(config: &AnalysisConfig) -> Result<ValidationResult> {
             c_signature: None,
             rust_signature: None,
             errors,
+            structs: Vec::new(),
+            outputs: Vec::new(),
+            error_map: None,
+            return_type: None,
         });
     }
 
@@ -44,7 +51,7 @@ pub fn validate(config: &AnalysisConfig) -> Result<ValidationResult> {
 
     // Step 3: Check Rust syntax  
     println!("  Checking Rust syntax...");
-    match check_rust_syntax(&config.rust_file) {
+    match check_rust_syntax(&config.rust_file, &config.workspace.file("rust_syntax_check.rmeta")) {
         Ok(_) => {},
         Err(e) => errors.push(format!("Rust syntax error: {}", e)),
     }
@@ -56,6 +63,10 @@ pub fn validate(config: &AnalysisConfig) -> Result<ValidationResult> {
             c_signature: None,
             rust_signature: None,
             errors,
+            structs: Vec::new(),
+            outputs: Vec::new(),
+            error_map: None,
+            return_type: None,
         });
     }
 
@@ -89,42 +100,786 @@ pub fn validate(config: &AnalysisConfig) -> Result<ValidationResult> {
     };
 
     // Step 6: Compare signatures if both found
+    let mut outputs = Vec::new();
+    let mut error_map = None;
     if let (Some(ref c), Some(ref r)) = (&c_sig, &rust_sig) {
         println!("  Comparing function signatures...");
         
-        // Check parameter count
-        if c.params.len() != r.params.len() {
+        // Check parameter count — a C (pointer, length) pair is one Rust slice,
+        // and out-parameters come back in the Rust return value
+        let c_params: Vec<(String, String)> = c.params.iter().enumerate()
+            .map(|(i, p)| split_c_param(p, i))
+            .collect();
+        let outs = output_indices(config, &c_params);
+        let arrays = array_params(&c_params, &outs);
+        if c_params.len() - arrays.len() - outs.len() != r.params.len() {
+            let mut notes = Vec::new();
+            if !arrays.is_empty() {
+                notes.push(format!("{} pointer/length pairs count as one slice each", arrays.len()));
+            }
+            if !outs.is_empty() {
+                notes.push(format!("{} out-parameters are returned", outs.len()));
+            }
             errors.push(format!(
-                "Parameter count mismatch: C has {}, Rust has {}",
-                c.params.len(), r.params.len()
+                "Parameter count mismatch: C has {}, Rust has {}{}",
+                c.params.len(), r.params.len(),
+                if notes.is_empty() { String::new() } else { format!(" ({})", notes.join("; ")) }
             ));
+        } else {
+            for &i in &arrays {
+                let rust_param = &r.params[rust_position(i, &arrays, &outs)];
+                if !rust_param.contains("& [") && !rust_param.contains("& mut [") {
+                    errors.push(format!(
+                        "C parameter '{}' is an array with length '{}', but Rust parameter '{}' is not a slice",
+                        c_params[i].0, c_params[i + 1].0, rust_param
+                    ));
+                }
+            }
+            for (i, (name, c_type)) in c_params.iter().enumerate() {
+                if !is_c_string(c_type) || arrays.contains(&i) || outs.contains(&i) { continue; }
+                let rust_param = &r.params[rust_position(i, &arrays, &outs)];
+                if !rust_param.ends_with("str") && !rust_param.ends_with("[u8]") {
+                    errors.push(format!(
+                        "C parameter '{}' is a string, but Rust parameter '{}' is neither &str nor &[u8]",
+                        name, rust_param
+                    ));
+                }
+            }
         }
 
-        // Check return types are compatible
-        if !are_types_compatible(&c.return_type, &r.return_type) {
+        // Check return types are compatible (struct returns are checked in step 7,
+        // returns carrying out-parameters or errors here)
+        if let Some(map) = &config.error_map {
+            match resolve_error_map(config, map, c, r) {
+                Ok(m) => {
+                    let c_fail = match m.signal {
+                        ErrorSignal::Sentinel(v) => format!("returns {}", v),
+                        ErrorSignal::Negative    => "returns < 0".to_string(),
+                        ErrorSignal::Errno       => "sets errno".to_string(),
+                    };
+                    let r_fail = match m.rust { RustFallible::Option => "None", RustFallible::Result => "Err" };
+                    println!("  Error map: C {} ⇔ Rust {}", c_fail, r_fail);
+                    error_map = Some(m);
+                }
+                Err(e) => errors.push(e.to_string()),
+            }
+        } else if !config.outputs.is_empty() {
+            match resolve_outputs(config, c, r, &c_params) {
+                Ok(found) => {
+                    for o in &found {
+                        let rust = if o.rust.is_empty() { "".to_string() } else { format!(".{}", o.rust) };
+                        println!("  Output {}: {} ↔ Rust return{}", o.name, o.var_type.rust_type(), rust);
+                    }
+                    outputs = found;
+                }
+                Err(e) => errors.push(e.to_string()),
+            }
+        } else if !is_c_struct(&c.return_type) && !are_types_compatible(&c.return_type, &r.return_type) {
             errors.push(format!(
-                "Return type mismatch: C returns '{}', Rust returns '{}'",
-                c.return_type, r.return_type
+                "Return type mismatch: C returns '{}', Rust returns '{}'{}",
+                c.return_type, r.return_type,
+                if rust_fallible(&r.return_type).is_some() {
+                    " (map the C failure value with --error-map, e.g. -1 or errno)"
+                } else { "" }
             ));
         }
     }
 
+    // Step 7: Resolve the layouts of struct parameters and return values
+    let mut structs = Vec::new();
+    if let (Some(ref c), Some(ref r)) = (&c_sig, &rust_sig) {
+        if errors.is_empty() {
+            match resolve_structs(config, c, r) {
+                Ok(found) => {
+                    for s in &found {
+                        let fields: Vec<String> = s.fields.iter()
+                            .map(|f| format!("{}: {}", f.c_name, f.var_type.rust_type()))
+                            .collect();
+                        println!("  Struct {} ↔ {} {{ {} }}", s.c_type, s.rust_type, fields.join(", "));
+                    }
+                    structs = found;
+                }
+                Err(e) => errors.push(e.to_string()),
+            }
+        }
+    }
+
+    // Step 8: Every non-void result must be compared as something
+    let return_type = c_sig.as_ref().and_then(|c| VarType::parse(&c.return_type));
+    if let Some(c) = &c_sig {
+        if errors.is_empty() && return_type.is_none() {
+            if let Some(e) = uncompared_return(&c.return_type, &structs, error_map.is_some() || !outputs.is_empty()) {
+                errors.push(e);
+            }
+        }
+    }
+
     // Build result
     Ok(ValidationResult {
         success: errors.is_empty(),
+        return_type,
         c_signature: c_sig,
         rust_signature: rust_sig,
         errors,
+        structs,
+        outputs,
+        error_map,
     })
 }
 
+/// Bind the inputs and copy everything validation resolved about the
+/// function's results into the config.
+pub fn apply(config: &mut AnalysisConfig, validation: &ValidationResult) -> Result<()> {
+    config.bounds        = bind_bounds(config, validation)?;
+    config.return_struct = return_struct(validation);
+    config.outputs       = validation.outputs.clone();
+    config.error_map     = validation.error_map.clone();
+    config.return_type   = validation.return_type;
+    Ok(())
+}
+
+/// Why a C return that is not a scalar would go uncompared: only `void`,
+/// a resolved struct, or an error map or out-parameters covering the
+/// result are allowed, since the harnesses otherwise print nothing for it.
+fn uncompared_return(c_return: &str, structs: &[StructLayout], covered: bool) -> Option<String> {
+    let t = c_return.trim().trim_start_matches("const ").trim();
+    if t == "void" || covered || structs.iter().any(|s| s.c_type == t) {
+        return None;
+    }
+    Some(format!(
+        "C return type '{}' is neither a scalar nor a struct, so its result cannot be compared",
+        c_return
+    ))
+}
+
+// STRUCT LAYOUTS
+
+/// Could this C type be a struct? Scalars, pointers and void are not; a
+/// typedef name is settled by looking it up in the AST.
+fn is_c_struct(c_type: &str) -> bool {
+    let t = c_type.trim().trim_start_matches("const ").trim();
+    t.starts_with("struct ")
+        || (!t.ends_with('*') && t != "void" && VarType::parse(t).is_none())
+}
+
+/// Pair every struct-typed C parameter (and the return type) with its Rust
+/// counterpart and check that the field lists correspond.
+fn resolve_structs(
+    config: &AnalysisConfig,
+    c:      &FunctionSignature,
+    r:      &FunctionSignature,
+) -> Result<Vec<StructLayout>> {
+    let c_params: Vec<(String, String)> = c.params.iter().enumerate()
+        .map(|(i, p)| split_c_param(p, i))
+        .collect();
+    let outs = output_indices(config, &c_params);
+    let arrays = array_params(&c_params, &outs);
+
+    // (C type, Rust type) pairs, positions shifted by collapsed array pairs
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    for (i, (_, c_type)) in c_params.iter().enumerate() {
+        if !is_c_struct(c_type) || (i > 0 && arrays.contains(&(i - 1))) { continue; }
+        let rust_param = &r.params[rust_position(i, &arrays, &outs)];
+        let rust_type = rust_param.split_once(':').map_or("", |(_, ty)| ty.trim());
+        pairs.push((c_type.clone(), rust_type.to_string()));
+    }
+    if is_c_struct(&c.return_type) {
+        pairs.push((c.return_type.clone(), r.return_type.trim().to_string()));
+    }
+    if pairs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let root = c_ast(&config.c_file)?;
+    let mut layouts: Vec<StructLayout> = Vec::new();
+    for (c_type, rust_type) in pairs {
+        let c_type = c_type.trim().trim_start_matches("const ").trim().to_string();
+        if layouts.iter().any(|l| l.c_type == c_type) { continue; }
+        // Typedefs of scalars land here too — they are simply not structs
+        let Some(c_fields) = c_struct_fields(&root, &c_type) else { continue };
+        let rust_fields = rust_struct_fields(&config.rust_file, &rust_type)?;
+
+        if c_fields.len() != rust_fields.len() {
+            return Err(CheckerError::ValidationError(format!(
+                "'{}' has {} fields in C but '{}' has {} in Rust",
+                c_type, c_fields.len(), rust_type, rust_fields.len()
+            )).into());
+        }
+        let mut fields = Vec::new();
+        for ((c_name, c_ft), (r_name, r_ft)) in c_fields.iter().zip(rust_fields.iter()) {
+            let var_type = VarType::parse(c_ft).ok_or_else(|| CheckerError::ValidationError(format!(
+                "Field '{}' of '{}' has type '{}', which cannot be made symbolic", c_name, c_type, c_ft
+            )))?;
+            if !scalar_type_matches(var_type, r_ft) {
+                return Err(CheckerError::ValidationError(format!(
+                    "Field '{}: {}' of '{}' does not match '{}: {}' of '{}'",
+                    c_name, c_ft, c_type, r_name, r_ft, rust_type
+                )).into());
+            }
+            fields.push(StructField { c_name: c_name.clone(), rust_name: r_name.clone(), var_type });
+        }
+        layouts.push(StructLayout { c_type, rust_type, fields });
+    }
+    Ok(layouts)
+}
+
+/// Fields (name, qualType) of a C struct named `struct tag` or through a typedef.
+fn c_struct_fields(root: &Value, c_type: &str) -> Option<Vec<(String, String)>> {
+    let kind = |n: &Value| n.get("kind").and_then(|k| k.as_str()).unwrap_or("").to_string();
+    let name = |n: &Value| n.get("name").and_then(|k| k.as_str()).unwrap_or("").to_string();
+    let has_fields = |n: &Value| n.get("inner").and_then(|v| v.as_array())
+        .is_some_and(|inner| inner.iter().any(|c| kind(c) == "FieldDecl"));
+
+    let record = match c_type.strip_prefix("struct ") {
+        Some(tag) => find_node(root, &|n| kind(n) == "RecordDecl" && name(n) == tag.trim() && has_fields(n))?,
+        None => {
+            // typedef struct [tag] { ... } Name; — follow the referenced RecordDecl
+            let typedef = find_node(root, &|n| kind(n) == "TypedefDecl" && name(n) == c_type)?;
+            let referenced = find_node(typedef, &|n| {
+                n.get("ownedTagDecl").or_else(|| n.get("decl"))
+                    .is_some_and(|d| kind(d) == "RecordDecl")
+            })?;
+            let decl = referenced.get("ownedTagDecl").or_else(|| referenced.get("decl"))?;
+            let id  = decl.get("id").and_then(|v| v.as_str()).unwrap_or("");
+            let tag = name(decl);
+            find_node(root, &|n| {
+                kind(n) == "RecordDecl" && has_fields(n)
+                    && (n.get("id").and_then(|v| v.as_str()) == Some(id) || (!tag.is_empty() && name(n) == tag))
+            })?
+        }
+    };
+
+    let fields = record.get("inner")?.as_array()?.iter()
+        .filter(|c| kind(c) == "FieldDecl")
+        .map(|f| {
+            let ty = f.get("type").and_then(|t| t.get("qualType")).and_then(|s| s.as_str()).unwrap_or("unknown");
+            (name(f), ty.to_string())
+        })
+        .collect();
+    Some(fields)
+}
+
+/// Depth-first search through clang's "inner" children.
+fn find_node<'a>(node: &'a Value, pred: &dyn Fn(&Value) -> bool) -> Option<&'a Value> {
+    if pred(node) { return Some(node); }
+    node.get("inner").and_then(|v| v.as_array())?
+        .iter()
+        .find_map(|child| find_node(child, pred))
+}
+
+/// Fields (name, type) of a `#[repr(C)]` struct in the Rust file.
+fn rust_struct_fields(rust_file: &str, struct_name: &str) -> Result<Vec<(String, String)>> {
+    let content = std::fs::read_to_string(rust_file)?;
+    let file_ast = syn::parse_file(&content).map_err(|e| {
+        CheckerError::ValidationError(format!("Rust parse failed: {}", e))
+    })?;
+
+    let item = file_ast.items.iter().find_map(|item| match item {
+        Item::Struct(s) if s.ident == struct_name => Some(s),
+        _ => None,
+    }).ok_or_else(|| CheckerError::ValidationError(format!(
+        "Struct '{}' not found in Rust", struct_name
+    )))?;
+
+    let mut repr_c = false;
+    for attr in item.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") { repr_c = true; }
+            Ok(())
+        });
+    }
+    if !repr_c {
+        return Err(CheckerError::ValidationError(format!(
+            "Struct '{}' must be #[repr(C)] to match its C layout", struct_name
+        )).into());
+    }
+
+    match &item.fields {
+        syn::Fields::Named(named) => Ok(named.named.iter().map(|f| (
+            f.ident.as_ref().map(|i| i.to_string()).unwrap_or_default(),
+            f.ty.to_token_stream().to_string(),
+        )).collect()),
+        _ => Err(CheckerError::ValidationError(format!(
+            "Struct '{}' must have named fields", struct_name
+        )).into()),
+    }
+}
+
+/// Layout of the struct the function returns, if it returns one.
+pub fn return_struct(validation: &ValidationResult) -> Option<StructLayout> {
+    let ret = validation.c_signature.as_ref()?.return_type.trim().trim_start_matches("const ").trim().to_string();
+    validation.structs.iter().find(|s| s.c_type == ret).cloned()
+}
+
+// OUT-PARAMETERS
+
+/// Indices of the C parameters declared as outputs.
+fn output_indices(config: &AnalysisConfig, params: &[(String, String)]) -> Vec<usize> {
+    params.iter().enumerate()
+        .filter(|(_, (name, _))| config.outputs.iter().any(|o| &o.name == name))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Type and Rust location of every value observed after the call: the C
+/// return value (unless void) followed by the out-parameters in C order.
+///
+/// A tuple return is matched by position unless an output names its index;
+/// any other return must name a field for each output (a single output may
+/// be the whole value).
+fn resolve_outputs(
+    config:   &AnalysisConfig,
+    c:        &FunctionSignature,
+    r:        &FunctionSignature,
+    c_params: &[(String, String)],
+) -> Result<Vec<OutputParam>> {
+    if is_c_struct(&c.return_type) {
+        return Err(CheckerError::ValidationError(
+            "Out-parameters cannot be combined with a struct return value".into()
+        ).into());
+    }
+    let declared = |name: &str| config.outputs.iter().find(|o| o.name == name);
+    for o in &config.outputs {
+        if !o.is_return() && !c_params.iter().any(|(n, _)| *n == o.name) {
+            return Err(CheckerError::ValidationError(format!(
+                "Output '{}' does not match any parameter of '{}'", o.name, config.function_name
+            )).into());
+        }
+    }
+
+    let mut outputs = Vec::new();
+    let c_ret = c.return_type.trim();
+    if c_ret != "void" {
+        let var_type = VarType::parse(c_ret).ok_or_else(|| CheckerError::ValidationError(format!(
+            "Return type '{}' cannot be compared", c_ret
+        )))?;
+        let rust = declared("return").map_or(String::new(), |o| o.rust.clone());
+        outputs.push(OutputParam { name: "return".into(), rust, var_type, c_index: None });
+    }
+    for (i, (name, c_type)) in c_params.iter().enumerate() {
+        let Some(o) = declared(name) else { continue };
+        // Exactly one level of non-const pointer to a scalar
+        let pointee = c_type.strip_suffix('*').map(str::trim)
+            .filter(|t| !t.ends_with('*') && !t.starts_with("const "));
+        let var_type = pointee.and_then(VarType::parse).ok_or_else(|| CheckerError::ValidationError(format!(
+            "Output '{}' has type '{}'; outputs must be pointers to a scalar", name, c_type
+        )))?;
+        outputs.push(OutputParam { name: name.clone(), rust: o.rust.clone(), var_type, c_index: Some(i) });
+    }
+
+    let rust_ret = r.return_type.trim();
+    let elements: Option<Vec<&str>> = rust_ret.strip_prefix('(').and_then(|t| t.strip_suffix(')'))
+        .map(|t| t.split(',').map(str::trim).filter(|e| !e.is_empty()).collect());
+    match elements {
+        Some(elements) => {
+            if elements.len() != outputs.len() {
+                return Err(CheckerError::ValidationError(format!(
+                    "Rust returns a {}-tuple but C produces {} values ({})",
+                    elements.len(), outputs.len(),
+                    outputs.iter().map(|o| o.name.as_str()).collect::<Vec<_>>().join(", ")
+                )).into());
+            }
+            for (k, o) in outputs.iter_mut().enumerate() {
+                if o.rust.is_empty() { o.rust = k.to_string(); }
+                let Some(elem) = o.rust.parse::<usize>().ok().and_then(|j| elements.get(j)) else {
+                    return Err(CheckerError::ValidationError(format!(
+                        "Output '{}' maps to '.{}', which is not an element of '{}'", o.name, o.rust, rust_ret
+                    )).into());
+                };
+                if !scalar_type_matches(o.var_type, elem) {
+                    return Err(CheckerError::ValidationError(format!(
+                        "Output '{}' is {} in C but element .{} of the Rust return is '{}'",
+                        o.name, o.var_type.c_type(), o.rust, elem
+                    )).into());
+                }
+            }
+        }
+        None if outputs.len() == 1 && outputs[0].rust.is_empty() => {
+            if !scalar_type_matches(outputs[0].var_type, rust_ret) {
+                return Err(CheckerError::ValidationError(format!(
+                    "Output '{}' is {} in C but Rust returns '{}'",
+                    outputs[0].name, outputs[0].var_type.c_type(), rust_ret
+                )).into());
+            }
+        }
+        // A struct: fields are checked when the runner is compiled
+        None => {
+            if let Some(o) = outputs.iter().find(|o| o.rust.is_empty()) {
+                return Err(CheckerError::ValidationError(format!(
+                    "Rust returns '{}'; name the field holding '{}' with --outputs {}=<field>",
+                    rust_ret, o.name, o.name
+                )).into());
+            }
+        }
+    }
+    Ok(outputs)
+}
+
+/// Does a Rust scalar type spelling carry a C scalar? C `char` is a byte in Rust.
+fn scalar_type_matches(c: VarType, rust_type: &str) -> bool {
+    match (c, VarType::parse(rust_type)) {
+        (VarType::Char, Some(VarType::U8)) => true,
+        (c, Some(r))                       => c == r,
+        (_, None)                          => false,
+    }
+}
+
+// ERROR CONVENTIONS
+
+/// `Option<T>` / `Result<T, E>` return types, with the spelling of `T`.
+fn rust_fallible(rust_type: &str) -> Option<(RustFallible, String)> {
+    let t: String = rust_type.chars().filter(|c| !c.is_whitespace()).collect();
+    let t = t.trim_start_matches("std::option::").trim_start_matches("std::result::");
+    if let Some(inner) = t.strip_prefix("Option<").and_then(|i| i.strip_suffix('>')) {
+        return Some((RustFallible::Option, inner.to_string()));
+    }
+    let inner = t.strip_prefix("Result<").and_then(|i| i.strip_suffix('>'))?;
+    // The success type is a scalar or (), so the first comma ends it
+    let ok = inner.split(',').next().unwrap_or(inner);
+    Some((RustFallible::Result, ok.to_string()))
+}
+
+/// Check that the C return type and the Rust `Option`/`Result` carry the
+/// same success value, and record which Rust type signals failure.
+fn resolve_error_map(
+    config: &AnalysisConfig,
+    map:    &ErrorMap,
+    c:      &FunctionSignature,
+    r:      &FunctionSignature,
+) -> Result<ErrorMap> {
+    let fail = |msg: String| -> Result<ErrorMap> { Err(CheckerError::ValidationError(msg).into()) };
+    if !config.outputs.is_empty() || is_c_struct(&c.return_type) {
+        return fail("--error-map applies to scalar returns without out-parameters".into());
+    }
+    let Some((rust, ok)) = rust_fallible(&r.return_type) else {
+        return fail(format!(
+            "--error-map needs a Rust Option or Result return, but '{}' returns '{}'",
+            r.name, r.return_type
+        ));
+    };
+    let c_ret = c.return_type.trim();
+    if c_ret == "void" {
+        if map.signal != ErrorSignal::Errno || ok != "()" {
+            return fail(format!(
+                "A void C function can only map errno to a Rust Result<(), _> or Option<()>, not '{}'",
+                r.return_type
+            ));
+        }
+        return Ok(ErrorMap { rust, ok_type: None, ..map.clone() });
+    }
+    let Some(var_type) = VarType::parse(c_ret) else {
+        return fail(format!("Return type '{}' cannot be compared", c_ret));
+    };
+    if map.signal != ErrorSignal::Errno && (var_type.is_float() || var_type == VarType::Bool) {
+        return fail(format!("A sentinel error value needs an integer return, but C returns '{}'", c_ret));
+    }
+    if !scalar_type_matches(var_type, &ok) {
+        return fail(format!(
+            "Return type mismatch: C returns '{}', Rust returns '{}' (success value '{}')",
+            c_ret, r.return_type, ok
+        ));
+    }
+    Ok(ErrorMap { rust, ok_type: Some(var_type), ..map.clone() })
+}
+
+// BOUND BINDING
+
+/// Bind the symbolic inputs to the C function's parameters, in parameter order.
+///
+/// Without `--bounds` every parameter gets its type's full range (or the
+/// configured default window). With `--bounds`, every bound must name a
+/// parameter; parameters left out are derived the same way, and untyped
+/// bounds (`x:0:100`) take the parameter's declared type.
+///
+/// A pointer followed by an integer length (`int *arr, size_t len`) is one
+/// array input; its length is symbolic up to the bound's `[N]`, or
+/// `DEFAULT_ARRAY_LEN` when derived.
+pub fn bind_bounds(config: &AnalysisConfig, validation: &ValidationResult) -> Result<Vec<InputBound>> {
+    let c_sig = validation.c_signature.as_ref().ok_or_else(|| {
+        CheckerError::ValidationError("No C signature available to bind bounds".into())
+    })?;
+
+    let params: Vec<(String, String)> = c_sig.params.iter().enumerate()
+        .map(|(i, p)| split_c_param(p, i))
+        .collect();
+
+    for b in &config.bounds {
+        // Struct fields are bounded as "p.x"; the field itself is checked when binding
+        let param = b.name.split('.').next().unwrap_or(&b.name);
+        if !params.iter().any(|(name, _)| name == param) {
+            let names: Vec<&str> = params.iter().map(|(n, _)| n.as_str()).collect();
+            return Err(CheckerError::ValidationError(format!(
+                "Bound '{}' does not match any parameter of '{}' (parameters: {})",
+                b.name, config.function_name,
+                if names.is_empty() { "none".to_string() } else { names.join(", ") }
+            )).into());
+        }
+    }
+
+    let outs = output_indices(config, &params);
+    let arrays = array_params(&params, &outs);
+    // Type spelling of the Rust parameter carrying C parameter `i`
+    let rust_type = |i: usize| -> &str {
+        validation.rust_signature.as_ref()
+            .and_then(|r| r.params.get(rust_position(i, &arrays, &outs)))
+            .and_then(|p| p.split_once(':'))
+            .map_or("", |(_, ty)| ty.trim())
+    };
+    let mut bound = Vec::new();
+    for (i, (name, c_type)) in params.iter().enumerate() {
+        let user = config.bounds.iter().find(|b| &b.name == name);
+
+        // Out-parameters are written by the function, not chosen
+        if outs.contains(&i) {
+            if user.is_some() {
+                return Err(CheckerError::ValidationError(format!(
+                    "'{}' is declared as an output and cannot be bounded", name
+                )).into());
+            }
+            continue;
+        }
+
+        // Length of the preceding array — bound through the array itself
+        if i > 0 && arrays.contains(&(i - 1)) {
+            if user.is_some() {
+                return Err(CheckerError::ValidationError(format!(
+                    "'{}' is the length of array '{}'; bound it as {}:<type>[N]:min:max",
+                    name, params[i - 1].0, params[i - 1].0
+                )).into());
+            }
+            continue;
+        }
+
+        if arrays.contains(&i) {
+            let elem_c_type = c_type.trim_end_matches('*').trim();
+            let elem_type = VarType::parse(elem_c_type)
+                .filter(|t| *t != VarType::Bool)
+                .ok_or_else(|| CheckerError::ValidationError(format!(
+                    "Parameter '{}' is an array of '{}', which cannot be made symbolic", name, elem_c_type
+                )))?;
+            let b = match user {
+                Some(user) if !matches!(user.kind, InputKind::Array { .. }) => {
+                    return Err(CheckerError::ValidationError(format!(
+                        "Parameter '{}' is an array; give its bound as {}:{}[N]:min:max",
+                        name, name, elem_type.rust_type()
+                    )).into());
+                }
+                Some(user) => adopt_bound(user, elem_type, elem_c_type)?,
+                None => InputBound {
+                    kind: InputKind::Array { max_len: DEFAULT_ARRAY_LEN },
+                    ..InputBound::for_type(name, elem_type, config.default_window)
+                },
+            };
+            // "& [i32]" / "& mut [i32]"
+            let rust_elem = rust_type(i).split_once('[')
+                .and_then(|(_, rest)| rest.rsplit_once(']'))
+                .map_or("", |(elem, _)| elem.trim());
+            check_rust_param(&b, rust_elem)?;
+            if let InputKind::Array { max_len } = b.kind {
+                println!(
+                    "  Bound {}: {} (length {} ≤ {}), elements in [{}, {}]",
+                    b.name, c_type, params[i + 1].0, max_len,
+                    b.var_type.format_value(b.min), b.var_type.format_value(b.max)
+                );
+            }
+            bound.push(b);
+            continue;
+        }
+
+        if let Some(layout) = validation.structs.iter().find(|s| s.c_type == c_type.trim_start_matches("const ").trim()) {
+            if user.is_some() {
+                return Err(CheckerError::ValidationError(format!(
+                    "Parameter '{}' is a struct; bound its fields instead, e.g. {}.{}:min:max",
+                    name, name, layout.fields.first().map_or("x", |f| f.c_name.as_str())
+                )).into());
+            }
+            let prefix = format!("{}.", name);
+            if let Some(stray) = config.bounds.iter().find(|b| {
+                b.name.strip_prefix(&prefix).is_some_and(|f| !layout.fields.iter().any(|lf| lf.c_name == f))
+            }) {
+                return Err(CheckerError::ValidationError(format!(
+                    "Bound '{}' does not match any field of '{}'", stray.name, layout.c_type
+                )).into());
+            }
+            let mut fields = Vec::new();
+            for f in &layout.fields {
+                let field_name = format!("{}.{}", name, f.c_name);
+                let b = match config.bounds.iter().find(|b| b.name == field_name) {
+                    Some(user) if user.kind != InputKind::Scalar => {
+                        return Err(CheckerError::ValidationError(format!(
+                            "Bound for field '{}' must be a plain range", field_name
+                        )).into());
+                    }
+                    Some(user) => adopt_bound(user, f.var_type, f.var_type.c_type())?,
+                    None => InputBound::for_type(&field_name, f.var_type, config.default_window),
+                };
+                println!(
+                    "  Bound {}: {} in [{}, {}]",
+                    b.name, f.var_type.rust_type(),
+                    b.var_type.format_value(b.min), b.var_type.format_value(b.max)
+                );
+                fields.push(b);
+            }
+            bound.push(InputBound {
+                kind: InputKind::Struct { layout: layout.clone(), fields },
+                ..InputBound::for_type(name, VarType::default(), None)
+            });
+            continue;
+        }
+
+        if is_c_string(c_type) {
+            let as_str = rust_type(i).ends_with("str");
+            let b = match user {
+                Some(user) if !matches!(user.kind, InputKind::Str { .. }) => {
+                    return Err(CheckerError::ValidationError(format!(
+                        "Parameter '{}' is a string; give its bound as {}:str[N]", name, name
+                    )).into());
+                }
+                Some(user) => user.clone(),
+                None => InputBound {
+                    name:     name.clone(),
+                    var_type: VarType::U8,
+                    min:      1,
+                    max:      if as_str && config.utf8 == Utf8Mode::Ascii { 127 } else { 255 },
+                    kind:     InputKind::Str { max_len: DEFAULT_STRING_LEN, rust: RustStr::Bytes },
+                    explicit_type: false,
+                },
+            };
+            // Bytes above 0x7F only reach a &str when checked as UTF-8
+            let rust = match (as_str, b.max > 127, config.utf8) {
+                (false, _, _)                   => RustStr::Bytes,
+                (true, false, _)                => RustStr::Ascii,
+                (true, true, Utf8Mode::Checked) => RustStr::Utf8,
+                (true, true, Utf8Mode::Ascii)   => {
+                    return Err(CheckerError::ValidationError(format!(
+                        "String '{}' allows bytes above 127 but Rust takes &str; use --utf8 checked or a maximum of 127",
+                        name
+                    )).into());
+                }
+            };
+            let max_len = match b.kind {
+                InputKind::Str { max_len, .. } => max_len,
+                _                              => DEFAULT_STRING_LEN,
+            };
+            let b = InputBound { kind: InputKind::Str { max_len, rust }, ..b };
+            println!(
+                "  Bound {}: {} (up to {} bytes in [{}, {}], Rust {:?})",
+                b.name, c_type, max_len, b.min, b.max, rust
+            );
+            bound.push(b);
+            continue;
+        }
+
+        let var_type = VarType::parse(c_type).ok_or_else(|| CheckerError::ValidationError(format!(
+            "Parameter '{}' has type '{}', which cannot be made symbolic", name, c_type
+        )))?;
+
+        let b = match user {
+            Some(user) if user.kind != InputKind::Scalar => {
+                return Err(CheckerError::ValidationError(format!(
+                    "Bound for '{}' is an array or string but the parameter is declared '{}'", name, c_type
+                )).into());
+            }
+            Some(user) => adopt_bound(user, var_type, c_type)?,
+            None => InputBound::for_type(name, var_type, config.default_window),
+        };
+        check_rust_param(&b, rust_type(i))?;
+        println!(
+            "  Bound {}: {} in [{}, {}]",
+            b.name, c_type, b.var_type.format_value(b.min), b.var_type.format_value(b.max)
+        );
+        bound.push(b);
+    }
+    Ok(bound)
+}
+
+/// Maximum symbolic length of an array parameter without an explicit `[N]`.
+pub const DEFAULT_ARRAY_LEN: usize = 4;
+
+/// Maximum length of a string parameter without an explicit `str[N]`.
+pub const DEFAULT_STRING_LEN: usize = 4;
+
+/// `char *`, `const char *`, `unsigned char *`, ... — a NUL-terminated string
+/// unless a length parameter follows (then it is a byte array).
+fn is_c_string(c_type: &str) -> bool {
+    c_type.strip_suffix('*')
+        .filter(|pointee| !pointee.trim_end().ends_with('*'))
+        .and_then(VarType::parse)
+        .is_some_and(|t| matches!(t, VarType::Char | VarType::I8 | VarType::U8))
+}
+
+/// A user bound checked against the declared type; untyped bounds adopt
+/// the declared type.
+fn adopt_bound(user: &InputBound, var_type: VarType, c_type: &str) -> Result<InputBound> {
+    if !user.explicit_type {
+        return user.retyped(var_type).ok_or_else(|| CheckerError::ValidationError(format!(
+            "Bound for '{}' does not fit its declared type '{}'", user.name, c_type
+        )).into());
+    }
+    if user.var_type == var_type {
+        return Ok(user.clone());
+    }
+    Err(CheckerError::ValidationError(format!(
+        "Bound for '{}' is tagged {} but the parameter is declared '{}'",
+        user.name, user.var_type.rust_type(), c_type
+    )).into())
+}
+
+/// The Rust parameter (or slice element) type must carry the bound's type.
+fn check_rust_param(b: &InputBound, rust_type: &str) -> Result<()> {
+    if scalar_type_matches(b.var_type, rust_type) {
+        return Ok(());
+    }
+    Err(CheckerError::ValidationError(format!(
+        "Parameter '{}' is bound as {} but the Rust parameter takes '{}'",
+        b.name, b.var_type.rust_type(), rust_type
+    )).into())
+}
+
+/// Indices of pointer parameters immediately followed by an integer length.
+/// Out-parameters are never arrays.
+fn array_params(params: &[(String, String)], outputs: &[usize]) -> Vec<usize> {
+    params.windows(2).enumerate()
+        .filter(|(i, w)| {
+            !outputs.contains(i)
+                && w[0].1.ends_with('*')
+                && VarType::parse(&w[1].1).is_some_and(|t| !t.is_float() && t != VarType::Bool)
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Position of C parameter `i` among the Rust parameters: array lengths and
+/// out-parameters have no Rust counterpart.
+fn rust_position(i: usize, arrays: &[usize], outputs: &[usize]) -> usize {
+    i - arrays.iter().filter(|&&a| a < i).count() - outputs.iter().filter(|&&o| o < i).count()
+}
+
+/// Split a C parameter as stored by `extract_signature_from_function_decl`
+/// ("const unsigned int n") into its name and type.
+/// Unnamed prototype parameters are called `argN`.
+fn split_c_param(param: &str, index: usize) -> (String, String) {
+    let param = param.trim();
+    match param.rsplit_once(' ') {
+        Some((ty, name))
+            if VarType::parse(param).is_none()
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_') =>
+        {
+            (name.to_string(), ty.trim().to_string())
+        }
+        _ => (format!("arg{}", index), param.to_string()),
+    }
+}
+
 // C VALIDATION HELPERS
 
 fn check_c_syntax(c_file: &str) -> Result<()> {
-    let output = Command::new("clang")
+    let clang = &toolchain::get().clang;
+    let output = clang.command()
         .arg("-fsyntax-only")
         .arg(c_file)
-        .output()?;
+        .output()
+        .map_err(|e| clang.spawn_error(e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -142,11 +897,29 @@ fn check_c_syntax(c_file: &str) -> Result<()> {
 
 
 pub fn find_c_function(c_file: &str, func_name: &str) -> Result<FunctionSignature> {
-    // 1) Ask clang for AST in JSON form
-    let output = Command::new("clang")
+    // 1-2) Ask clang for the AST in JSON form
+    let root = c_ast(c_file)?;
+
+    // 3) Walk AST to find matching FunctionDecl
+    let func_node = find_function_decl(&root, func_name)
+        .ok_or_else(|| CheckerError::ValidationError(format!(
+            "Function '{}' not found in C AST",
+            func_name
+        )))?;
+
+    // 4) Extract signature info
+    extract_signature_from_function_decl(&root, func_node, func_name)
+    
+}
+
+/// clang's AST of the C file as JSON.
+fn c_ast(c_file: &str) -> Result<Value> {
+    let clang = &toolchain::get().clang;
+    let output = clang.command()
         .args(["-Xclang", "-ast-dump=json", "-fsyntax-only"])
         .arg(c_file)
-        .output()?;
+        .output()
+        .map_err(|e| clang.spawn_error(e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -162,17 +935,7 @@ pub fn find_c_function(c_file: &str, func_name: &str) -> Result<FunctionSignatur
     let root: Value = serde_json::from_str(&json_text).map_err(|e| {
         CheckerError::ValidationError(format!("Failed to parse clang AST JSON: {}", e))
     })?;
-
-    // 3) Walk AST to find matching FunctionDecl
-    let func_node = find_function_decl(&root, func_name)
-        .ok_or_else(|| CheckerError::ValidationError(format!(
-            "Function '{}' not found in C AST",
-            func_name
-        )))?;
-
-    // 4) Extract signature info
-    extract_signature_from_function_decl(func_node, func_name)
-    
+    Ok(root)
 }
 
 fn find_function_decl<'a>(node: &'a Value, func_name: &str) -> Option<&'a Value> {
@@ -213,7 +976,23 @@ fn has_compound_body(node: &Value) -> bool {
     }
 }
 
-fn extract_signature_from_function_decl(node: &Value, func_name: &str) -> Result<FunctionSignature> {
+/// The scalar a typedef name stands for (`typedef long long ll;` gives
+/// "long long"), so a typedef'd return or parameter is checked and compared
+/// as the type it names. `None` for scalars, structs and unknown names.
+fn desugar_scalar(root: &Value, c_type: &str) -> Option<String> {
+    let t = c_type.trim().trim_start_matches("const ").trim();
+    if VarType::parse(t).is_some() { return None; }
+    let typedef = find_node(root, &|n| {
+        n.get("kind").and_then(|k| k.as_str()) == Some("TypedefDecl")
+            && n.get("name").and_then(|k| k.as_str()) == Some(t)
+    })?;
+    // A typedef of a typedef carries the fully desugared type alongside
+    let ty = typedef.get("type")?;
+    let underlying = ty.get("desugaredQualType").or_else(|| ty.get("qualType"))?.as_str()?;
+    VarType::parse(underlying).map(|_| underlying.to_string())
+}
+
+fn extract_signature_from_function_decl(root: &Value, node: &Value, func_name: &str) -> Result<FunctionSignature> {
     // Return type:
     // In clang json, return type usually appears in node["type"]["qualType"] like:
     // "int (int, int)"  OR sometimes return type via node["type"]["qualType"] parsing.
@@ -235,6 +1014,7 @@ fn extract_signature_from_function_decl(node: &Value, func_name: &str) -> Result
         let ret = qual.split(" (").next().unwrap_or(qual).trim();
         ret.to_string()
     };
+    let return_type = desugar_scalar(root, &return_type).unwrap_or(return_type);
 
     // Params: children with kind "ParmVarDecl"
     let mut params: Vec<String> = Vec::new();
@@ -246,6 +1026,7 @@ fn extract_signature_from_function_decl(node: &Value, func_name: &str) -> Result
                     .and_then(|t| t.get("qualType"))
                     .and_then(|s| s.as_str())
                     .unwrap_or("unknown");
+                let ptype = desugar_scalar(root, ptype).unwrap_or_else(|| ptype.to_string());
 
                 let pname = child.get("name").and_then(|n| n.as_str()).unwrap_or("");
 
@@ -270,15 +1051,17 @@ fn extract_signature_from_function_decl(node: &Value, func_name: &str) -> Result
 }
 
 // RUST VALIDATION HELPERS
-fn check_rust_syntax(rust_file: &str) -> Result<()> {
+fn check_rust_syntax(rust_file: &str, rmeta: &str) -> Result<()> {
     // Compile-check only (no linking), stable-compatible
-    let output = Command::new("rustc")
+    let rustc = &toolchain::get().rustc;
+    let output = rustc.command()
         .arg("--crate-type=lib")
         .arg("--emit=metadata")
         .arg(rust_file)
         .arg("-o")
-        .arg("/tmp/rust_syntax_check.rmeta")
-        .output()?;
+        .arg(rmeta)
+        .output()
+        .map_err(|e| rustc.spawn_error(e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -346,6 +1129,10 @@ fn are_types_compatible(c_type: &str, rust_type: &str) -> bool {
     let r = rust_type.trim();
     
     if c == r { return true; }
+    // Any spelling of the same width and signedness ("unsigned long long" ↔ u64)
+    if let Some(ct) = VarType::parse(c) {
+        if scalar_type_matches(ct, r) { return true; }
+    }
     
     matches!((c, r),
         ("int", "i32") | ("long", "i64") | ("short", "i16") | ("char", "i8") |
@@ -353,3 +1140,88 @@ fn are_types_compatible(c_type: &str, rust_type: &str) -> bool {
         ("float", "f32") | ("double", "f64") | ("void", "()")
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untyped_bounds_adopt_the_declared_type() {
+        let user = InputBound::parse("n:0:100").unwrap();
+        let b = adopt_bound(&user, VarType::I64, "long").unwrap();
+        assert_eq!((b.var_type, b.min, b.max), (VarType::I64, 0, 100));
+        // Still rejected when the range does not fit
+        let neg = InputBound::parse("n:-1:100").unwrap();
+        assert!(adopt_bound(&neg, VarType::U32, "unsigned").is_err());
+    }
+
+    #[test]
+    fn explicit_types_must_match_the_declaration() {
+        let user = InputBound::parse("n:i32:0:100").unwrap();
+        assert!(adopt_bound(&user, VarType::I32, "int").is_ok());
+        let err = adopt_bound(&user, VarType::I64, "long").unwrap_err().to_string();
+        assert!(err.contains("tagged i32"), "{}", err);
+        assert!(adopt_bound(&user, VarType::U32, "unsigned").is_err());
+    }
+
+    #[test]
+    fn rust_parameter_must_carry_the_bound_type() {
+        let b = InputBound::parse("n:u64:0:5").unwrap();
+        assert!(check_rust_param(&b, "u64").is_ok());
+        assert!(check_rust_param(&b, "usize").is_ok());
+        assert!(check_rust_param(&b, "i64").is_err());
+        let c = InputBound::parse("c:char:-5:5").unwrap();
+        assert!(check_rust_param(&c, "u8").is_ok());
+    }
+
+    /// `typedef long long ll; typedef ll wide; wide f(ll x) { ... }` as clang dumps it
+    fn typedef_ast() -> Value {
+        serde_json::json!({
+            "kind": "TranslationUnitDecl",
+            "inner": [
+                { "kind": "TypedefDecl", "name": "ll", "type": { "qualType": "long long" } },
+                { "kind": "TypedefDecl", "name": "wide",
+                  "type": { "qualType": "ll", "desugaredQualType": "long long" } },
+                { "kind": "FunctionDecl", "name": "f", "type": { "qualType": "wide (ll)" },
+                  "inner": [
+                      { "kind": "ParmVarDecl", "name": "x",
+                        "type": { "qualType": "ll", "desugaredQualType": "long long" } },
+                      { "kind": "CompoundStmt" }
+                  ] }
+            ]
+        })
+    }
+
+    #[test]
+    fn typedef_return_resolves_to_its_scalar() {
+        let root = typedef_ast();
+        let f = find_function_decl(&root, "f").unwrap();
+        let sig = extract_signature_from_function_decl(&root, f, "f").unwrap();
+        assert_eq!(sig.return_type, "long long");
+        assert_eq!(sig.params, ["long long x"]);
+        assert!(!is_c_struct(&sig.return_type));
+        assert_eq!(VarType::parse(&sig.return_type), Some(VarType::I64));
+    }
+
+    #[test]
+    fn unresolved_returns_fail_validation() {
+        assert!(uncompared_return("void", &[], false).is_none());
+        assert!(uncompared_return("int *", &[], true).is_none());
+        let err = uncompared_return("ll", &[], false).unwrap();
+        assert!(err.contains("'ll'"), "{}", err);
+        assert!(uncompared_return("int *", &[], false).is_some());
+        let point = StructLayout { c_type: "Point".into(), rust_type: "Point".into(), fields: Vec::new() };
+        assert!(uncompared_return("const Point", &[point], false).is_none());
+    }
+
+    #[test]
+    fn struct_typedefs_are_not_desugared() {
+        let root = serde_json::json!({
+            "kind": "TranslationUnitDecl",
+            "inner": [{ "kind": "TypedefDecl", "name": "Point", "type": { "qualType": "struct Point" } }]
+        });
+        assert_eq!(desugar_scalar(&root, "Point"), None);
+        assert_eq!(desugar_scalar(&root, "int"), None);
+        assert_eq!(desugar_scalar(&typedef_ast(), "const ll").as_deref(), Some("long long"));
+    }
+}
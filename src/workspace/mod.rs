@@ -0,0 +1,94 @@
+// src/workspace/mod.rs
+// ═══════════════════════════════════════════════════════
+// Run workspaces
+//
+// Every run gets a unique run ID and its own directories, so concurrent
+// runs of the same function (everyone on the web UI uploads `add`) never
+// share harnesses, bitcode, runners or KLEE output:
+//   <tmp>/equivalence_checker/<run id>/   intermediate files
+//   output/<run id>/                      reports and human-readable .ll
+// The intermediate directory is removed after the run unless kept
+// (--keep-workspace); the output directory always stays.
+// ═══════════════════════════════════════════════════════
+
+use anyhow::Result;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    /// `<function>-<unix time>-<random hex>`
+    pub run_id:     String,
+    /// Intermediate files of this run
+    pub dir:        PathBuf,
+    /// Reports and .ll dumps of this run
+    pub output_dir: PathBuf,
+    /// Leave `dir` in place after the run, for debugging
+    pub keep:       bool,
+    /// Digest of the C and Rust sources as compiled, for the cache keys;
+    /// computed once, since it runs clang and rustc
+    pub sources:    OnceLock<String>,
+}
+
+impl Workspace {
+    /// Create a fresh workspace for a run of `function_name`.
+    pub fn create(function_name: &str, keep: bool) -> Result<Workspace> {
+        let base = std::env::temp_dir().join("equivalence_checker");
+        fs::create_dir_all(&base)?;
+        let stem: String = function_name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+            .collect();
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        loop {
+            let run_id = format!("{}-{}-{:04x}", stem, secs, rand::random::<u16>());
+            let dir = base.join(&run_id);
+            match fs::create_dir(&dir) {
+                Ok(()) => {
+                    let output_dir = PathBuf::from("output").join(&run_id);
+                    return Ok(Workspace { run_id, dir, output_dir, keep, sources: OnceLock::new() });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Path of an intermediate file (or directory) of this run.
+    pub fn file(&self, name: &str) -> String {
+        self.dir.join(name).display().to_string()
+    }
+
+    /// Path of an output file of this run, creating its directory.
+    pub fn output(&self, name: &str) -> Result<String> {
+        let path = self.output_dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(path.display().to_string())
+    }
+
+    /// Finish the workspace when the returned guard drops, early returns included.
+    pub fn guard(&self) -> Guard {
+        Guard(self.clone())
+    }
+
+    /// Remove the intermediate files, unless the workspace is kept.
+    pub fn finish(&self) {
+        if self.keep {
+            println!("  Workspace kept: {}", self.dir.display());
+        } else if let Err(e) = fs::remove_dir_all(&self.dir) {
+            println!("  ⚠ Could not remove workspace {}: {}", self.dir.display(), e);
+        }
+    }
+}
+
+pub struct Guard(Workspace);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        self.0.finish();
+    }
+}